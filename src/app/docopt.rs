@@ -0,0 +1,585 @@
+// Std
+use std::ascii::AsciiExt;
+
+// Internal
+use INTERNAL_ERROR_MSG;
+use app::App;
+use app::settings::AppSettings;
+use args::{Arg, ArgGroup, ArgSettings};
+use errors::{Error, Result as ClapResult};
+use usage_parser::UsageParser;
+
+// Builds an App from a docopt document. The "Usage:" patterns give the commands, positional
+// arguments, groups, required-ness and conflicts, while the "Options:" sections describe the
+// options themselves
+pub fn app_from_docopt<'a>(doc: &'a str) -> ClapResult<App<'a, 'a>> {
+    debugln!("docopt::app_from_docopt;");
+    let (about, usage) = match usage_section(doc) {
+        Some(section) => section,
+        None => return Err(Error::invalid_definition("", "no 'Usage:' section found")),
+    };
+    let prog = match usage.split_whitespace().next() {
+        Some(prog) => prog,
+        None => return Err(Error::invalid_definition("Usage:", "no usage patterns found")),
+    };
+    let opts = try!(options_sections(doc));
+    let mut root = Cmd::new(prog);
+    for pattern in patterns(usage, prog) {
+        debugln!("docopt::app_from_docopt: pattern={:?}", pattern);
+        let toks = tokenize(pattern);
+        try!(check_brackets(pattern, &toks));
+        let mut i = 0;
+        let alts = parse_alts(pattern, &opts, &toks, &mut i);
+        let top = Node::Group(pattern.trim(), true, alts);
+        for variant in expand_node(&top, true, false) {
+            root.add(&variant, &opts);
+        }
+    }
+    if !root.uses_options() {
+        // Without any [options] shortcut, the options which are only described go to the top
+        for o in &opts {
+            if !is_builtin(o) && !root.uses(o.b.name) {
+                root.args.push(o.clone());
+            }
+        }
+    }
+
+    let mut app = try!(root.into_app(App::new(prog)));
+    if !about.is_empty() {
+        app = app.about(about);
+    }
+    Ok(app)
+}
+
+fn check_brackets(pattern: &str, toks: &[(usize, &str)]) -> ClapResult<()> {
+    let mut open = vec![];
+    for &(_, t) in toks {
+        match t {
+            "(" | "[" => open.push(t),
+            ")" | "]" => {
+                let expected = if t == ")" { "(" } else { "[" };
+                if open.pop() != Some(expected) {
+                    return Err(Error::invalid_definition(pattern.trim(),
+                                                         &*format!("unexpected '{}'", t)));
+                }
+            }
+            _ => (),
+        }
+    }
+    match open.pop() {
+        Some(t) => Err(Error::invalid_definition(pattern.trim(), &*format!("unclosed '{}'", t))),
+        None => Ok(()),
+    }
+}
+
+// A parsed piece of a usage pattern
+enum Node<'a> {
+    Command(&'a str),
+    Arg(Arg<'a, 'a>),
+    // The [options] shortcut
+    Options,
+    // -h, --help and --version, which clap already provides
+    Builtin,
+    // `--`, after which everything is taken as values
+    DoubleDash,
+    Repeat(Box<Node<'a>>),
+    // The source text, whether it's required, and its alternatives
+    Group(&'a str, bool, Vec<Vec<Node<'a>>>),
+}
+
+// A leaf of a usage pattern once its alternatives are expanded
+#[derive(Clone)]
+struct Use<'n, 'a: 'n> {
+    node: &'n Node<'a>,
+    required: bool,
+    multiple: bool,
+    // The group of a single argument alternative, and whether one of its args is required
+    group: Option<(&'a str, bool)>,
+}
+
+struct Cmd<'a> {
+    name: &'a str,
+    args: Vec<Arg<'a, 'a>>,
+    groups: Vec<ArgGroup<'a>>,
+    // The args of every pattern through this command, and whether they're required there
+    variants: Vec<Vec<(&'a str, bool)>>,
+    // Whether each of those patterns went on to a subcommand
+    continued: Vec<bool>,
+    options: bool,
+    trailing: bool,
+    subs: Vec<Cmd<'a>>,
+}
+
+impl<'a> Cmd<'a> {
+    fn new(name: &'a str) -> Self {
+        Cmd {
+            name: name,
+            args: vec![],
+            groups: vec![],
+            variants: vec![],
+            continued: vec![],
+            options: false,
+            trailing: false,
+            subs: vec![],
+        }
+    }
+
+    fn add<'n>(&mut self, uses: &[Use<'n, 'a>], opts: &[Arg<'a, 'a>]) {
+        debugln!("Cmd::add: cmd={}", self.name);
+        let mut variant = vec![];
+        let mut builtin = false;
+        let mut options = false;
+        for (i, u) in uses.iter().enumerate() {
+            match *u.node {
+                Node::Command(name) => {
+                    self.variants.push(variant);
+                    self.continued.push(true);
+                    let pos = match self.subs.iter().position(|s| s.name == name) {
+                        Some(pos) => pos,
+                        None => {
+                            self.subs.push(Cmd::new(name));
+                            self.subs.len() - 1
+                        }
+                    };
+                    self.subs[pos].add(&uses[i + 1..], opts);
+                    return;
+                }
+                Node::Arg(ref a) => {
+                    self.add_arg(a, u.multiple, u.group);
+                    variant.push((a.b.name, u.required && u.group.is_none()));
+                }
+                Node::Options => options = true,
+                Node::Builtin => builtin = true,
+                Node::DoubleDash => self.trailing = true,
+                Node::Repeat(_) | Node::Group(..) => unreachable!(),
+            }
+        }
+        if options {
+            // [options] stands for all the options the pattern doesn't already use
+            self.options = true;
+            for o in opts.iter().filter(|o| !is_builtin(o)) {
+                if !variant.iter().any(|&(n, _)| n == o.b.name) {
+                    self.add_arg(o, false, None);
+                    variant.push((o.b.name, false));
+                }
+            }
+        }
+        // Patterns such as `prog --version` don't say anything about the other args
+        if !(builtin && variant.is_empty()) {
+            self.variants.push(variant);
+            self.continued.push(false);
+        }
+    }
+
+    fn add_arg(&mut self, a: &Arg<'a, 'a>, multiple: bool, group: Option<(&'a str, bool)>) {
+        let name = a.b.name;
+        let pos = match self.args.iter().position(|b| b.b.name == name) {
+            Some(pos) => pos,
+            None => {
+                let mut a = a.clone();
+                if a.s.short.is_none() && a.s.long.is_none() {
+                    let idx = self.args.iter().filter(|b| b.index.is_some()).count() + 1;
+                    a = a.index(idx as u64);
+                }
+                self.args.push(a);
+                self.args.len() - 1
+            }
+        };
+        if multiple {
+            self.args[pos].setb(ArgSettings::Multiple);
+        }
+        if let Some((group, required)) = group {
+            if let Some(grp) = self.groups.iter_mut().find(|grp| grp.name == group) {
+                if !grp.args.contains(&name) {
+                    grp.args.push(name);
+                }
+                return;
+            }
+            self.groups.push(ArgGroup::with_name(group).required(required).arg(name));
+        }
+    }
+
+    fn uses(&self, name: &str) -> bool {
+        self.args.iter().any(|a| a.b.name == name) || self.subs.iter().any(|s| s.uses(name))
+    }
+
+    fn uses_options(&self) -> bool { self.options || self.subs.iter().any(|s| s.uses_options()) }
+
+    fn into_app(self, mut app: App<'a, 'a>) -> ClapResult<App<'a, 'a>> {
+        debugln!("Cmd::into_app: cmd={}", self.name);
+        let variants = self.variants;
+        let has = |v: &Vec<(&str, bool)>, name: &str| v.iter().any(|&(n, _)| n == name);
+        let listed = |name: &str| variants.iter().any(|v| has(v, name));
+        let mut positionals = vec![];
+        for a in &self.args {
+            let name = a.b.name;
+            let mut a = a.clone();
+            if listed(name) {
+                let required_in = |v: &Vec<(&str, bool)>| v.iter().any(|&(n, r)| n == name && r);
+                if variants.iter().all(&required_in) {
+                    a = a.required(true);
+                } else if variants.iter().all(|v| required_in(v) || !has(v, name)) {
+                    // Only required in its own patterns, so it's needed unless another pattern's
+                    // required args are used
+                    let lacking = variants.iter().filter(|v| !has(v, name)).collect::<Vec<_>>();
+                    if lacking.iter().all(|v| v.iter().any(|&(_, r)| r)) {
+                        let mut others = vec![];
+                        for &(n, r) in lacking.iter().flat_map(|v| v.iter()) {
+                            if r && !others.contains(&n) {
+                                others.push(n);
+                            }
+                        }
+                        a = a.required_unless_one(&*others);
+                    }
+                }
+                for other in self.args.iter().map(|b| b.b.name) {
+                    if other != name && listed(other) &&
+                       !variants.iter().any(|v| has(v, name) && has(v, other)) {
+                        a = a.conflicts_with(other);
+                    }
+                }
+            }
+            if let Some(idx) = a.index {
+                positionals.push((idx, name, a.is_set(ArgSettings::Required)));
+            }
+            app = app.arg(a);
+        }
+        if try!(missing_positional(self.name, positionals)) {
+            app = app.setting(AppSettings::AllowMissingPositional);
+        }
+        for g in self.groups {
+            app = app.group(g);
+        }
+        if self.trailing {
+            app = app.setting(AppSettings::TrailingVarArg);
+        }
+        if !self.subs.is_empty() && self.continued.iter().all(|&c| c) {
+            app = app.setting(AppSettings::SubcommandRequiredElseHelp);
+        }
+        for sub in self.subs {
+            let name = sub.name;
+            app = app.subcommand(try!(sub.into_app(App::new(name))));
+        }
+        Ok(app)
+    }
+}
+
+// Whether the positional arguments of command `cmd`, as `(index, name, required)`, need
+// AppSettings::AllowMissingPositional. That covers an optional argument right before the last,
+// required one (i.e. `<a> [<b>] <c>`), while any other optional argument before a required one
+// can't be told apart from it
+fn missing_positional(cmd: &str, mut positionals: Vec<(u64, &str, bool)>) -> ClapResult<bool> {
+    positionals.sort();
+    let last_required = positionals.iter().rposition(|&(_, _, r)| r);
+    let optional = positionals.iter().position(|&(_, _, r)| !r);
+    match (optional, last_required) {
+        (Some(o), Some(r)) if o < r => {
+            if o + 1 == r && r + 1 == positionals.len() {
+                return Ok(true);
+            }
+            Err(Error::invalid_definition(cmd,
+                                          &*format!("the optional argument <{}> can't come \
+                                                     before the required argument <{}>",
+                                                    positionals[o].1,
+                                                    positionals[r].1)))
+        }
+        _ => Ok(false),
+    }
+}
+
+fn expand<'n, 'a>(nodes: &'n [Node<'a>], required: bool, multiple: bool) -> Vec<Vec<Use<'n, 'a>>> {
+    nodes.iter().fold(vec![vec![]], |vars, n| {
+        let tails = expand_node(n, required, multiple);
+        vars.iter()
+            .flat_map(|v| tails.iter().map(move |t| v.iter().chain(t).cloned().collect()))
+            .collect()
+    })
+}
+
+// Expands the alternatives of a node into one list of leaves per combination, except for
+// alternatives of single args which are kept together as a group
+fn expand_node<'n, 'a>(n: &'n Node<'a>, required: bool, multiple: bool) -> Vec<Vec<Use<'n, 'a>>> {
+    match *n {
+        Node::Repeat(ref n) => expand_node(n, required, true),
+        Node::Group(name, req, ref alts) => {
+            let required = required && req;
+            if alts.len() > 1 && alts.iter().all(|a| a.len() == 1 && is_arg(&a[0])) {
+                let uses = alts.iter()
+                    .flat_map(|a| expand_node(&a[0], false, multiple).remove(0))
+                    .map(|u| Use { group: Some((name, required)), ..u })
+                    .collect();
+                vec![uses]
+            } else {
+                alts.iter().flat_map(|a| expand(a, required, multiple)).collect()
+            }
+        }
+        _ => {
+            vec![vec![Use {
+                          node: n,
+                          required: required,
+                          multiple: multiple,
+                          group: None,
+                      }]]
+        }
+    }
+}
+
+fn is_arg(n: &Node) -> bool {
+    match *n {
+        Node::Arg(_) => true,
+        Node::Repeat(ref n) => is_arg(n),
+        _ => false,
+    }
+}
+
+fn is_builtin(a: &Arg) -> bool { a.s.long == Some("help") || a.s.long == Some("version") }
+
+// Parses the tokens of pattern `p` up to the closing bracket of the current group into its
+// alternatives
+fn parse_alts<'a>(p: &'a str,
+                  opts: &[Arg<'a, 'a>],
+                  toks: &[(usize, &'a str)],
+                  i: &mut usize)
+                  -> Vec<Vec<Node<'a>>> {
+    let mut alts = vec![vec![]];
+    while let Some(&(start, t)) = toks.get(*i) {
+        *i += 1;
+        match t {
+            ")" | "]" => break,
+            "|" => alts.push(vec![]),
+            "(" | "[" => {
+                let inner = parse_alts(p, opts, toks, i);
+                let end = match toks.get(*i - 1) {
+                    Some(&(end, ")")) | Some(&(end, "]")) => end,
+                    _ => p.len(),
+                };
+                let text = p[start + 1..end].trim();
+                alts.last_mut().expect(INTERNAL_ERROR_MSG).push(Node::Group(text, t == "(", inner));
+            }
+            "..." => {
+                let alt = alts.last_mut().expect(INTERNAL_ERROR_MSG);
+                if let Some(n) = alt.pop() {
+                    alt.push(Node::Repeat(Box::new(n)));
+                }
+            }
+            _ => {
+                let nodes = leaves(opts, toks, i, t);
+                alts.last_mut().expect(INTERNAL_ERROR_MSG).extend(nodes);
+            }
+        }
+    }
+    alts
+}
+
+// The nodes of a single word, which may consume the next one as the value of an option
+fn leaves<'a>(opts: &[Arg<'a, 'a>],
+              toks: &[(usize, &'a str)],
+              i: &mut usize,
+              w: &'a str)
+              -> Vec<Node<'a>> {
+    debugln!("docopt::leaves: word={}", w);
+    let mut consume_value = |a: &Arg| if a.is_set(ArgSettings::TakesValue) {
+        if let Some(&(_, next)) = toks.get(*i) {
+            if is_value(next) {
+                *i += 1;
+            }
+        }
+    };
+    if w == "options" {
+        vec![Node::Options]
+    } else if w == "--" {
+        vec![Node::DoubleDash]
+    } else if w == "-" {
+        // The usual name for stdin is a value like any other
+        vec![Node::Arg(Arg::with_name(w))]
+    } else if w.starts_with("--") {
+        let long = w[2..].split('=').next().expect(INTERNAL_ERROR_MSG);
+        let mut a = opts.iter()
+            .find(|o| o.s.long == Some(long))
+            .cloned()
+            .unwrap_or_else(|| UsageParser::from_usage(w).parse());
+        a.unsetb(ArgSettings::Required);
+        if !w.contains('=') {
+            consume_value(&a);
+        }
+        vec![node(a)]
+    } else if w.starts_with('-') && w.len() > 1 {
+        // Stacked shorts, the last of which may be followed by its value
+        let mut nodes = vec![];
+        for (j, c) in w.char_indices().skip(1) {
+            let a = opts.iter()
+                .find(|o| o.s.short == Some(c))
+                .cloned()
+                .unwrap_or_else(|| {
+                    let name = &w[j..j + c.len_utf8()];
+                    Arg::with_name(name).short(name)
+                });
+            let takes_value = a.is_set(ArgSettings::TakesValue);
+            if takes_value && j + c.len_utf8() == w.len() {
+                consume_value(&a);
+            }
+            nodes.push(node(a));
+            if takes_value {
+                break;
+            }
+        }
+        nodes
+    } else if is_value(w) {
+        vec![Node::Arg(Arg::with_name(w.trim_matches(|c| c == '<' || c == '>')))]
+    } else {
+        vec![Node::Command(w)]
+    }
+}
+
+fn node<'a>(a: Arg<'a, 'a>) -> Node<'a> {
+    if is_builtin(&a) {
+        Node::Builtin
+    } else {
+        Node::Arg(a)
+    }
+}
+
+// <value> or VALUE
+fn is_value(w: &str) -> bool {
+    (w.starts_with('<') && w.ends_with('>')) ||
+    (w.chars().any(|c| c.is_alphabetic()) && !w.chars().any(|c| c.is_lowercase()) &&
+     !w.starts_with('-'))
+}
+
+// Splits a pattern into words, brackets, `|` and `...`, along with their positions
+fn tokenize(p: &str) -> Vec<(usize, &str)> {
+    let bytes = p.as_bytes();
+    let mut toks = vec![];
+    let mut i = 0;
+    while i < bytes.len() {
+        let start = i;
+        match bytes[i] {
+            b' ' | b'\t' | b'\r' | b'\n' => {
+                i += 1;
+                continue;
+            }
+            b'(' | b')' | b'[' | b']' | b'|' => i += 1,
+            _ if p[i..].starts_with("...") => i += 3,
+            _ => {
+                while i < bytes.len() {
+                    match bytes[i] {
+                        b'<' => i += p[i..].find('>').map_or(p.len() - i, |e| e + 1),
+                        b' ' | b'\t' | b'\r' | b'\n' | b'(' | b')' | b'[' | b']' | b'|' => break,
+                        _ if p[i..].starts_with("...") => break,
+                        _ => i += 1,
+                    }
+                }
+            }
+        }
+        toks.push((start, &p[start..i]));
+    }
+    toks
+}
+
+// The text before the usage section, and the usage section without its "Usage:" header
+fn usage_section(doc: &str) -> Option<(&str, &str)> {
+    let mut section = None;
+    let mut end = 0;
+    let mut offset = 0;
+    for line in doc.split('\n') {
+        let line_end = offset + line.len();
+        if section.is_none() {
+            if let Some(i) = find_ci(line, "usage:") {
+                section = Some((offset, offset + i + "usage:".len()));
+                end = line_end;
+            }
+        } else if line.trim().is_empty() {
+            break;
+        } else {
+            end = line_end;
+        }
+        offset = line_end + 1;
+    }
+    section.map(|(line_start, start)| (doc[..line_start].trim(), &doc[start..end]))
+}
+
+// Each pattern starts with the program name, and may be continued on the following lines
+fn patterns<'a>(usage: &'a str, prog: &str) -> Vec<&'a str> {
+    let mut pats: Vec<(usize, usize)> = vec![];
+    let mut offset = 0;
+    for line in usage.split('\n') {
+        let end = offset + line.len();
+        let trimmed = line.trim_left();
+        if trimmed.split_whitespace().next() == Some(prog) {
+            pats.push((end - trimmed.len() + prog.len(), end));
+        } else if let Some(last) = pats.last_mut() {
+            last.1 = end;
+        }
+        offset = end + 1;
+    }
+    pats.into_iter().map(|(start, end)| &usage[start..end]).collect()
+}
+
+// The options described in every section whose header ends with "options:"
+fn options_sections(doc: &str) -> ClapResult<Vec<Arg>> {
+    let mut opts: Vec<Arg> = vec![];
+    let mut in_section = false;
+    for line in doc.lines().map(str::trim) {
+        if line.is_empty() {
+            in_section = false;
+        } else if find_ci(line, "options:").map_or(false, |i| i + "options:".len() == line.len()) {
+            in_section = true;
+        } else if in_section && line.starts_with('-') {
+            opts.push(try!(option(line)));
+        } else if in_section {
+            // The default may be given on a continuation line of the description
+            if let (Some(o), Some(val)) = (opts.last_mut(), default_of(line)) {
+                *o = o.clone().default_value(val);
+            }
+        }
+    }
+    Ok(opts)
+}
+
+// An option line, i.e. "-s, --speed=<kn>  Speed in knots [default: 10]."
+fn option(line: &str) -> ClapResult<Arg> {
+    debugln!("docopt::option: line={}", line);
+    let spec_end = line.find("  ").or_else(|| line.find('\t')).unwrap_or(line.len());
+    let (spec, desc) = (line[..spec_end].trim(), line[spec_end..].trim());
+    // UsageParser expects every switch to have a name
+    for w in spec.split(|c| c == ' ' || c == ',' || c == '=').filter(|w| w.starts_with('-')) {
+        if w == "-" || w == "--" {
+            return Err(Error::invalid_definition(line, &*format!("'{}' has no name", w)));
+        }
+    }
+    let mut a = UsageParser::from_usage(spec).parse();
+    a.unsetb(ArgSettings::Required);
+    if a.v.val_names.is_none() {
+        if let Some(val) = spec.split(|c| c == ' ' || c == ',' || c == '=')
+            .find(|w| !w.is_empty() && !w.starts_with('-')) {
+            a = a.value_name(val);
+        }
+    }
+    let help = match find_ci(desc, "[default:") {
+        Some(i) => {
+            if let Some(val) = default_of(desc) {
+                a = a.default_value(val);
+            }
+            desc[..i].trim()
+        }
+        None => desc,
+    };
+    if !help.is_empty() {
+        a = a.help(help);
+    }
+    Ok(a)
+}
+
+fn default_of(s: &str) -> Option<&str> {
+    find_ci(s, "[default:").and_then(|i| {
+        let rest = &s[i + "[default:".len()..];
+        rest.find(']').map(|end| rest[..end].trim())
+    })
+}
+
+// Finds `needle`, which must be ASCII, ignoring case
+fn find_ci(haystack: &str, needle: &str) -> Option<usize> {
+    haystack.as_bytes()
+        .windows(needle.len())
+        .position(|w| AsciiExt::eq_ignore_ascii_case(w, needle.as_bytes()))
+}
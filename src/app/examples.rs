@@ -0,0 +1,78 @@
+// Checks the examples added with `App::example` for `App::verify_examples`
+
+// Internal
+use app::App;
+use errors::{Error, ErrorKind};
+use errors::Result as ClapResult;
+
+/// Parses the command line of every example of `app` and its subcommands against `app`.
+pub fn verify(app: &App) -> ClapResult<()> {
+    debugln!("examples::verify;");
+    let mut cmds = vec![];
+    collect(app, &mut cmds);
+    for cmd in cmds {
+        debugln!("examples::verify: cmd={}", cmd);
+        let res = app.clone().get_matches_from_safe(split(cmd));
+        match res {
+            Err(mut e) => {
+                if e.kind != ErrorKind::HelpDisplayed && e.kind != ErrorKind::VersionDisplayed {
+                    e.message = format!("{}\n\nExample: {}", e.message, cmd);
+                    return Err(e);
+                }
+            }
+            _ => (),
+        }
+    }
+    Ok(())
+}
+
+fn collect<'a, 'b>(app: &App<'a, 'b>, cmds: &mut Vec<&'b str>) {
+    cmds.extend(app.p.meta.examples.iter().map(|&(cmd, _)| cmd));
+    for sc in &app.p.subcommands {
+        collect(sc, cmds);
+    }
+}
+
+// Splits a command line on whitespace, except inside single or double quotes
+fn split(cmd: &str) -> Vec<String> {
+    let mut words = vec![];
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut quote = None;
+    for c in cmd.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => word.push(c),
+            (None, '\'') | (None, '"') => {
+                quote = Some(c);
+                in_word = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_word {
+                    words.push(word);
+                    word = String::new();
+                    in_word = false;
+                }
+            }
+            (None, c) => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    words
+}
+
+#[cfg(test)]
+mod test {
+    use super::split;
+
+    #[test]
+    fn split_quotes() {
+        assert_eq!(split("tool  --msg 'a b' \"c 'd'\" e'f g'h ''"),
+                   vec!["tool", "--msg", "a b", "c 'd'", "ef gh", ""]);
+    }
+}
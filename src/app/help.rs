@@ -12,6 +12,7 @@ use args::{AnyArg, ArgSettings, DispOrder};
 use errors::{Error, Result as ClapResult};
 use fmt::{Format, Colorizer};
 use app::usage;
+use app::markup;
 
 // Third Party
 use unicode_width::UnicodeWidthStr;
@@ -37,10 +38,53 @@ macro_rules! find_longest {
     }};
 }
 
-fn str_width(s: &str) -> usize { UnicodeWidthStr::width(s) }
+// The columns `s` takes up on the terminal, ANSI escape sequences such as colors taking none
+pub fn str_width(s: &str) -> usize {
+    let mut width = 0;
+    let mut rest = s;
+    while let Some(i) = rest.find('\x1b') {
+        width += UnicodeWidthStr::width(&rest[..i]);
+        rest = skip_escape(&rest[i..]);
+    }
+    width + UnicodeWidthStr::width(rest)
+}
+
+// Skips the escape sequence `s` starts with, i.e. `ESC [` up to its final byte for the color and
+// cursor codes (CSI), or `ESC` and the single character after it otherwise
+fn skip_escape(s: &str) -> &str {
+    let mut chars = s.char_indices().skip(1);
+    match chars.next() {
+        Some((_, '[')) => {
+            for (i, c) in chars {
+                if c >= '@' && c <= '~' {
+                    return &s[i + 1..];
+                }
+            }
+            ""
+        }
+        Some((i, c)) => &s[i + c.len_utf8()..],
+        None => "",
+    }
+}
 
 const TAB: &'static str = "    ";
 
+// The columns between the start of a line and the help, besides the longest name: the leading
+// tab, the room for a short (i.e. "-s, ") and the tab before the help
+const HELP_GAP: usize = 12;
+
+// The share of the terminal the names may take while the help column is still considered roomy
+const ROOMY_RATIO: f32 = 0.40;
+
+// The quantile of the name widths that must keep their help on the same line (i.e. 3/4 of them)
+const KEPT_NUM: usize = 3;
+const KEPT_DEN: usize = 4;
+
+// How many times wider than the kept names (i.e. 3/2) the longest name must be before the wider
+// names are moved to their own lines
+const WIDER_NUM: usize = 3;
+const WIDER_DEN: usize = 2;
+
 // These are just convenient traits to make the code easier to read.
 trait ArgWithDisplay<'b, 'c>: AnyArg<'b, 'c> + Display {}
 impl<'b, 'c, T> ArgWithDisplay<'b, 'c> for T where T: AnyArg<'b, 'c> + Display {}
@@ -88,8 +132,11 @@ pub struct Help<'a> {
     color: bool,
     cizer: Colorizer,
     longest: usize,
+    narrow: bool,
     force_next_line: bool,
     use_long: bool,
+    markup: bool,
+    show_hidden: bool,
 }
 
 // Public Functions
@@ -102,7 +149,8 @@ impl<'a> Help<'a> {
                cizer: Colorizer,
                term_w: Option<usize>,
                max_w: Option<usize>,
-               use_long: bool)
+               use_long: bool,
+               markup: bool)
                -> Self {
         debugln!("Help::new;");
         Help {
@@ -122,8 +170,11 @@ impl<'a> Help<'a> {
             color: color,
             cizer: cizer,
             longest: 0,
+            narrow: false,
             force_next_line: false,
             use_long: use_long,
+            markup: markup,
+            show_hidden: false,
         }
     }
 
@@ -149,15 +200,30 @@ impl<'a> Help<'a> {
         Self::_write_parser_help(w, parser, true, false)
     }
 
+    /// Reads help settings from a Parser and writes its help to the wrapped stream, including the
+    /// hidden arguments, subcommands and aliases, marked as such.
+    pub fn write_parser_help_all(w: &'a mut Write, parser: &Parser, use_long: bool) -> ClapResult<()> {
+        debugln!("Help::write_parser_help_all;");
+        let mut help = Self::from_parser(w, parser, false, use_long);
+        help.show_hidden = true;
+        help.write_help(parser)
+    }
+
     #[doc(hidden)]
     pub fn _write_parser_help(w: &'a mut Write, parser: &Parser, stderr: bool, use_long: bool) -> ClapResult<()> {
         debugln!("Help::write_parser_help;");
+        Self::from_parser(w, parser, stderr, use_long).write_help(parser)
+    }
+
+    fn from_parser(w: &'a mut Write, parser: &Parser, stderr: bool, use_long: bool) -> Self {
+        debugln!("Help::from_parser;");
         let nlh = parser.is_set(AppSettings::NextLineHelp);
         let hide_v = parser.is_set(AppSettings::HidePossibleValuesInHelp);
         let color = parser.is_set(AppSettings::ColoredHelp);
         let cizer = Colorizer {
             use_stderr: stderr,
             when: parser.color(),
+            theme: parser.meta.theme,
         };
         Self::new(w,
                   nlh,
@@ -166,8 +232,8 @@ impl<'a> Help<'a> {
                   cizer,
                   parser.meta.term_w,
                   parser.meta.max_w,
-                  use_long)
-                .write_help(parser)
+                  use_long,
+                  parser.is_set(AppSettings::HelpMarkup))
     }
 
     /// Writes the parser help to the wrapped stream.
@@ -191,18 +257,17 @@ impl<'a> Help<'a> {
         where I: Iterator<Item = &'d ArgWithOrder<'b, 'c>>
     {
         debugln!("Help::write_args_unsorted;");
-        // The shortest an arg can legally be is 2 (i.e. '-x')
-        self.longest = 2;
         let mut arg_v = Vec::with_capacity(10);
+        let mut sizes = Vec::with_capacity(10);
+        let show_hidden = self.show_hidden;
         for arg in args.filter(|arg| {
                                    !(arg.is_set(ArgSettings::Hidden)) ||
-                                   arg.is_set(ArgSettings::NextLineHelp)
+                                   arg.is_set(ArgSettings::NextLineHelp) || show_hidden
                                }) {
-            if arg.longest_filter() {
-                self.longest = cmp::max(self.longest, arg.to_string().len());
-            }
+            sizes.push(self.arg_size(arg.as_base()));
             arg_v.push(arg)
         }
+        self.layout(&*sizes);
         let mut first = true;
         for arg in arg_v {
             if first {
@@ -220,24 +285,22 @@ impl<'a> Help<'a> {
         where I: Iterator<Item = &'d ArgWithOrder<'b, 'c>>
     {
         debugln!("Help::write_args;");
-        // The shortest an arg can legally be is 2 (i.e. '-x')
-        self.longest = 2;
         let mut ord_m = VecMap::new();
+        let mut sizes = Vec::with_capacity(10);
+        let show_hidden = self.show_hidden;
         // Determine the longest
         for arg in args.filter(|arg| {
             // If it's NextLineHelp, but we don't care to compute how long because it may be
             // NextLineHelp on purpose *because* it's so long and would throw off all other
             // args alignment
-            !arg.is_set(ArgSettings::Hidden) || arg.is_set(ArgSettings::NextLineHelp)
+            !arg.is_set(ArgSettings::Hidden) || arg.is_set(ArgSettings::NextLineHelp) ||
+            show_hidden
         }) {
-            if arg.longest_filter() {
-                debugln!("Help::write_args: Current Longest...{}", self.longest);
-                self.longest = cmp::max(self.longest, arg.to_string().len());
-                debugln!("Help::write_args: New Longest...{}", self.longest);
-            }
+            sizes.push(self.arg_size(arg.as_base()));
             let btm = ord_m.entry(arg.disp_ord()).or_insert(BTreeMap::new());
             btm.insert(arg.name(), arg);
         }
+        self.layout(&*sizes);
         let mut first = true;
         for btm in ord_m.values() {
             for arg in btm.values() {
@@ -252,6 +315,52 @@ impl<'a> Help<'a> {
         Ok(())
     }
 
+    /// The width of an argument's name column and of its help, as the layout of its section sees
+    /// them.
+    fn arg_size<'b, 'c>(&self, arg: &ArgWithDisplay<'b, 'c>) -> (usize, usize) {
+        let w = if arg.longest_filter() {
+            str_width(&*arg.to_string())
+        } else {
+            0
+        };
+        let h = arg.help().unwrap_or("");
+        (w, str_width(h) + str_width(&*self.spec_vals(arg)))
+    }
+
+    /// Picks the help column of a section from the `(name, help)` widths of its args.
+    ///
+    /// The column fits the longest name, unless that leaves too little room for the help on a
+    /// narrow terminal. Then if about a quarter of the names are much wider than the rest, they go
+    /// on lines of their own so the rest get a wider help column. If even that doesn't fit, the
+    /// whole section is written as with `NextLineHelp`.
+    fn layout(&mut self, sizes: &[(usize, usize)]) {
+        debugln!("Help::layout: sizes={:?}", sizes);
+        let mut widths: Vec<usize> = sizes.iter().map(|&(w, _)| w).collect();
+        widths.sort();
+        let term_w = self.term_w;
+        let roomy = |col: usize| (col + HELP_GAP) as f32 / term_w as f32 <= ROOMY_RATIO;
+        let fits = |col: usize| {
+            col + HELP_GAP <= term_w &&
+            sizes.iter().all(|&(w, h)| w > col || h <= term_w - col - HELP_GAP)
+        };
+        // The shortest an arg can legally be is 2 (i.e. '-x')
+        let longest = cmp::max(2, widths.last().cloned().unwrap_or(0));
+        self.longest = longest;
+        self.narrow = false;
+        if widths.is_empty() || roomy(longest) || fits(longest) {
+            debugln!("Help::layout: longest={}", longest);
+            return;
+        }
+        let most = cmp::max(2, widths[(widths.len() - 1) * KEPT_NUM / KEPT_DEN]);
+        if longest * WIDER_DEN > most * WIDER_NUM && (roomy(most) || fits(most)) {
+            debugln!("Help::layout: longest={}, wider names on their own line", most);
+            self.longest = most;
+        } else {
+            debugln!("Help::layout: too narrow, help on the next line");
+            self.narrow = true;
+        }
+    }
+
     /// Writes help for an argument to the wrapped stream.
     fn write_arg<'b, 'c>(&mut self, arg: &ArgWithDisplay<'b, 'c>) -> io::Result<()> {
         debugln!("Help::write_arg;");
@@ -267,7 +376,7 @@ impl<'a> Help<'a> {
         debugln!("Help::short;");
         try!(write!(self.writer, "{}", TAB));
         if let Some(s) = arg.short() {
-            color!(self, "-{}", s, good)
+            color!(self, "-{}", s, literal)
         } else if arg.has_switch() {
             write!(self.writer, "{}", TAB)
         } else {
@@ -286,7 +395,7 @@ impl<'a> Help<'a> {
                 if arg.short().is_some() {
                     try!(write!(self.writer, ", "));
                 }
-                try!(color!(self, "--{}", l, good))
+                try!(color!(self, "--{}", l, literal))
             }
 
             let sep = if arg.is_set(ArgSettings::RequireEquals) {
@@ -299,7 +408,7 @@ impl<'a> Help<'a> {
             if arg.short().is_some() {
                 try!(write!(self.writer, ", "));
             }
-            try!(color!(self, "--{}", l, good));
+            try!(color!(self, "--{}", l, literal));
         }
         Ok(())
     }
@@ -311,60 +420,53 @@ impl<'a> Help<'a> {
             if let Some(vec) = arg.val_names() {
                 let mut it = vec.iter().peekable();
                 while let Some((_, val)) = it.next() {
-                    try!(color!(self, "<{}>", val, good));
+                    try!(color!(self, "<{}>", val, placeholder));
                     if it.peek().is_some() {
                         try!(write!(self.writer, " "));
                     }
                 }
                 let num = vec.len();
                 if arg.is_set(ArgSettings::Multiple) && num == 1 {
-                    try!(color!(self, "...", good));
+                    try!(color!(self, "...", placeholder));
                 }
             } else if let Some(num) = arg.num_vals() {
                 let mut it = (0..num).peekable();
                 while let Some(_) = it.next() {
-                    try!(color!(self, "<{}>", arg.name(), good));
+                    try!(color!(self, "<{}>", arg.name(), placeholder));
                     if it.peek().is_some() {
                         try!(write!(self.writer, " "));
                     }
                 }
                 if arg.is_set(ArgSettings::Multiple) && num == 1 {
-                    try!(color!(self, "...", good));
+                    try!(color!(self, "...", placeholder));
                 }
             } else if arg.has_switch() {
-                try!(color!(self, "<{}>", arg.name(), good));
+                try!(color!(self, "<{}>", arg.name(), placeholder));
                 if arg.is_set(ArgSettings::Multiple) {
-                    try!(color!(self, "...", good));
+                    try!(color!(self, "...", placeholder));
                 }
             } else {
-                try!(color!(self, "{}", arg, good));
+                try!(color!(self, "{}", arg, literal));
             }
         }
 
         let spec_vals = self.spec_vals(arg);
-        let h = arg.help().unwrap_or("");
-        let h_w = str_width(h) + str_width(&*spec_vals);
         let nlh = self.next_line_help || arg.is_set(ArgSettings::NextLineHelp);
-        let taken = self.longest + 12;
-        self.force_next_line = !nlh && self.term_w >= taken &&
-                               (taken as f32 / self.term_w as f32) > 0.40 &&
-                               h_w > (self.term_w - taken);
+        self.force_next_line = !nlh &&
+                               (self.narrow ||
+                                arg.longest_filter() &&
+                                str_width(&*arg.to_string()) > self.longest);
 
         debug!("Help::val: Has switch...");
         if arg.has_switch() {
             sdebugln!("Yes");
             debugln!("Help::val: force_next_line...{:?}", self.force_next_line);
             debugln!("Help::val: nlh...{:?}", nlh);
-            debugln!("Help::val: taken...{}", taken);
-            debugln!("Help::val: help_width > (width - taken)...{} > ({} - {})",
-                     h_w,
-                     self.term_w,
-                     taken);
             debugln!("Help::val: longest...{}", self.longest);
             debug!("Help::val: next_line...");
             if !(nlh || self.force_next_line) {
                 sdebugln!("No");
-                let self_len = arg.to_string().len();
+                let self_len = str_width(&*arg.to_string());
                 // subtract ourself
                 let mut spcs = self.longest - self_len;
                 // Since we're writing spaces from the tab point we first need to know if we
@@ -383,7 +485,7 @@ impl<'a> Help<'a> {
             }
         } else if !(nlh || self.force_next_line) {
             sdebugln!("No, and not next_line");
-            write_nspaces!(self.writer, self.longest + 4 - (arg.to_string().len()));
+            write_nspaces!(self.writer, self.longest + 4 - str_width(&*arg.to_string()));
         } else {
             sdebugln!("No");
         }
@@ -399,7 +501,10 @@ impl<'a> Help<'a> {
         let too_long = str_width(h) >= self.term_w;
 
         debug!("Help::write_before_after_help: Too long...");
-        if too_long || h.contains("{n}") {
+        if self.markup {
+            sdebugln!("Markup");
+            help = self.render_markup(h, self.term_w);
+        } else if too_long || h.contains("{n}") {
             sdebugln!("Yes");
             help.push_str(h);
             debugln!("Help::write_before_after_help: help: {}", help);
@@ -448,7 +553,7 @@ impl<'a> Help<'a> {
         let spcs = if nlh || self.force_next_line {
             12 // "tab" * 3
         } else {
-            self.longest + 12
+            self.longest + HELP_GAP
         };
 
         let too_long = spcs + str_width(h) + str_width(&*spec_vals) >= self.term_w;
@@ -459,7 +564,15 @@ impl<'a> Help<'a> {
         }
 
         debug!("Help::help: Too long...");
-        if too_long && spcs <= self.term_w || h.contains("{n}") {
+        if self.markup {
+            sdebugln!("Markup");
+            let avail_chars = if spcs <= self.term_w {
+                self.term_w - spcs
+            } else {
+                usize::MAX
+            };
+            help = self.render_markup_with(h, &*spec_vals, avail_chars);
+        } else if too_long && spcs <= self.term_w || h.contains("{n}") {
             sdebugln!("Yes");
             help.push_str(h);
             help.push_str(&*spec_vals);
@@ -483,16 +596,32 @@ impl<'a> Help<'a> {
             help.push_str(&*spec_vals);
             &*help
         };
+        let with_table;
+        let help = if self.pv_table(arg) {
+            let table = try!(self.possible_vals_table(arg));
+            with_table = if help.is_empty() {
+                table
+            } else {
+                format!("{}\n\n{}", help, table)
+            };
+            &*with_table
+        } else {
+            help
+        };
         if help.contains('\n') {
             if let Some(part) = help.lines().next() {
                 try!(write!(self.writer, "{}", part));
             }
             for part in help.lines().skip(1) {
                 try!(write!(self.writer, "\n"));
+                // No trailing spaces on the blank lines between paragraphs
+                if part.is_empty() {
+                    continue;
+                }
                 if nlh || self.force_next_line {
                     try!(write!(self.writer, "{}{}{}", TAB, TAB, TAB));
                 } else if arg.has_switch() {
-                    write_nspaces!(self.writer, self.longest + 12);
+                    write_nspaces!(self.writer, self.longest + HELP_GAP);
                 } else {
                     write_nspaces!(self.writer, self.longest + 8);
                 }
@@ -507,6 +636,63 @@ impl<'a> Help<'a> {
         Ok(())
     }
 
+    // The long help lists the possible values with their descriptions instead of `[values: ...]`
+    fn pv_table(&self, a: &ArgWithDisplay) -> bool {
+        self.use_long && !self.hide_pv && !a.is_set(ArgSettings::HidePossibleValues) &&
+        a.possible_vals_help().is_some()
+    }
+
+    // One line per possible value, its description aligned and wrapped after the longest value
+    fn possible_vals_table(&self, a: &ArgWithDisplay) -> io::Result<String> {
+        debugln!("Help::possible_vals_table: a={}", a);
+        let pvs = a.possible_vals().unwrap_or(&[]);
+        let helps = a.possible_vals_help().unwrap_or(&[]);
+        let longest = pvs.iter().map(|v| str_width(v)).max().unwrap_or(0);
+        // The help of the arg is indented by 12, the values by 4 more
+        let spcs = 12 + 4 + longest + 4;
+        let avail_chars = if spcs < self.term_w {
+            self.term_w - spcs
+        } else {
+            usize::MAX
+        };
+        let mut table = vec![];
+        try!(write!(table, "Possible values:"));
+        for pv in pvs {
+            try!(write!(table, "\n{}", TAB));
+            if self.color {
+                try!(write!(table, "{}", self.cizer.literal(pv)));
+            } else {
+                try!(write!(table, "{}", pv));
+            }
+            let h = match helps.iter().find(|&&(v, _)| v == *pv) {
+                Some(&(_, h)) => h,
+                None => continue,
+            };
+            let mut h = h.replace("{n}", "\n");
+            let longest_w = find_longest!(h);
+            wrap_help(&mut h, longest_w, avail_chars);
+            for (i, line) in h.lines().enumerate() {
+                if i == 0 {
+                    write_nspaces!(table, longest - str_width(pv) + 4);
+                } else {
+                    try!(write!(table, "\n"));
+                    write_nspaces!(table, 4 + longest + 4);
+                }
+                try!(write!(table, "{}", line));
+            }
+        }
+        Ok(unsafe { String::from_utf8_unchecked(table) })
+    }
+
+    // Renders the markup of `AppSettings::HelpMarkup`, styled when colors are on
+    fn render_markup(&self, s: &str, width: usize) -> String { self.render_markup_with(s, "", width) }
+
+    // Like `render_markup`, followed by the generated `tail` (i.e. the default value) as it is
+    fn render_markup_with(&self, s: &str, tail: &str, width: usize) -> String {
+        let cizer = if self.color { Some(&self.cizer) } else { None };
+        markup::render(&s.replace("{n}", "\n"), tail, width, cizer)
+    }
+
     fn spec_vals(&self, a: &ArgWithDisplay) -> String {
         debugln!("Help::spec_vals: a={}", a);
         let mut spec_vals = vec![];
@@ -515,7 +701,7 @@ impl<'a> Help<'a> {
                 debugln!("Help::spec_vals: Found default value...[{:?}]", pv);
                 spec_vals.push(format!(" [default: {}]",
                                        if self.color {
-                                           self.cizer.good(pv.to_string_lossy())
+                                           self.cizer.literal(pv.to_string_lossy())
                                        } else {
                                            Format::None(pv.to_string_lossy())
                                        }));
@@ -527,20 +713,35 @@ impl<'a> Help<'a> {
                                    if self.color {
                                        aliases
                                            .iter()
-                                           .map(|v| format!("{}", self.cizer.good(v)))
+                                           .map(|v| format!("{}", self.cizer.literal(v)))
                                            .collect::<Vec<_>>()
                                            .join(", ")
                                    } else {
                                        aliases.join(", ")
                                    }));
         }
-        if !self.hide_pv && !a.is_set(ArgSettings::HidePossibleValues) {
+        if self.show_hidden {
+            if let Some(ref aliases) = a.hidden_aliases() {
+                debugln!("Help::spec_vals: Found hidden aliases...{:?}", aliases);
+                spec_vals.push(format!(" [hidden aliases: {}]",
+                                       if self.color {
+                                           aliases
+                                               .iter()
+                                               .map(|v| format!("{}", self.cizer.literal(v)))
+                                               .collect::<Vec<_>>()
+                                               .join(", ")
+                                       } else {
+                                           aliases.join(", ")
+                                       }));
+            }
+        }
+        if !self.hide_pv && !a.is_set(ArgSettings::HidePossibleValues) && !self.pv_table(a) {
             if let Some(pv) = a.possible_vals() {
                 debugln!("Help::spec_vals: Found possible vals...{:?}", pv);
                 spec_vals.push(if self.color {
                                    format!(" [values: {}]",
                                            pv.iter()
-                                               .map(|v| format!("{}", self.cizer.good(v)))
+                                               .map(|v| format!("{}", self.cizer.literal(v)))
                                                .collect::<Vec<_>>()
                                                .join(", "))
                                } else {
@@ -548,6 +749,21 @@ impl<'a> Help<'a> {
                                });
             }
         }
+        if self.show_hidden && a.is_set(ArgSettings::Hidden) {
+            spec_vals.push(if self.color {
+                               format!(" [{}]", self.cizer.header("hidden"))
+                           } else {
+                               " [hidden]".to_owned()
+                           });
+        }
+        if a.deprecated().is_some() {
+            debugln!("Help::spec_vals: Found deprecated");
+            spec_vals.push(if self.color {
+                               format!(" [{}]", self.cizer.warning("deprecated"))
+                           } else {
+                               " [deprecated]".to_owned()
+                           });
+        }
         spec_vals.join(" ")
     }
 }
@@ -560,12 +776,16 @@ impl<'a> Help<'a> {
     #[cfg_attr(feature = "lints", allow(useless_let_if_seq))]
     pub fn write_all_args(&mut self, parser: &Parser) -> ClapResult<()> {
         debugln!("Help::write_all_args;");
-        let flags = parser.has_flags();
+        // Args with a help heading are left out of the usual sections and get their own
+        let unheaded = |arg: &&ArgWithOrder| parser.help_heading_of(arg.name()).is_none();
+        let show_hidden = self.show_hidden;
+        let flags = parser.flags().map(as_arg_trait).any(|arg| unheaded(&arg));
         let pos = parser
             .positionals()
-            .filter(|arg| !arg.is_set(ArgSettings::Hidden))
-            .count() > 0;
-        let opts = parser.has_opts();
+            .map(as_arg_trait)
+            .filter(|arg| !arg.is_set(ArgSettings::Hidden) || show_hidden)
+            .any(|arg| unheaded(&arg));
+        let opts = parser.opts().map(as_arg_trait).any(|arg| unheaded(&arg));
         let subcmds = parser.has_subcommands();
 
         let unified_help = parser.is_set(AppSettings::UnifiedHelpMessage);
@@ -576,22 +796,23 @@ impl<'a> Help<'a> {
             let opts_flags = parser
                 .flags()
                 .map(as_arg_trait)
-                .chain(parser.opts().map(as_arg_trait));
-            try!(color!(self, "OPTIONS:\n", warning));
+                .chain(parser.opts().map(as_arg_trait))
+                .filter(&unheaded);
+            try!(color!(self, "OPTIONS:\n", header));
             try!(self.write_args(opts_flags));
             first = false;
         } else {
             if flags {
-                try!(color!(self, "FLAGS:\n", warning));
-                try!(self.write_args(parser.flags().map(as_arg_trait)));
+                try!(color!(self, "FLAGS:\n", header));
+                try!(self.write_args(parser.flags().map(as_arg_trait).filter(&unheaded)));
                 first = false;
             }
             if opts {
                 if !first {
                     try!(self.writer.write_all(b"\n\n"));
                 }
-                try!(color!(self, "OPTIONS:\n", warning));
-                try!(self.write_args(parser.opts().map(as_arg_trait)));
+                try!(color!(self, "OPTIONS:\n", header));
+                try!(self.write_args(parser.opts().map(as_arg_trait).filter(&unheaded)));
                 first = false;
             }
         }
@@ -600,8 +821,32 @@ impl<'a> Help<'a> {
             if !first {
                 try!(self.writer.write_all(b"\n\n"));
             }
-            try!(color!(self, "ARGS:\n", warning));
-            try!(self.write_args_unsorted(parser.positionals().map(as_arg_trait)));
+            try!(color!(self, "ARGS:\n", header));
+            let args = parser.positionals().map(as_arg_trait).filter(&unheaded);
+            try!(self.write_args_unsorted(args));
+            first = false;
+        }
+
+        for heading in &parser.help_headings {
+            let headed = |arg: &&ArgWithOrder| {
+                parser.help_heading_of(arg.name()) == Some(heading)
+            };
+            let args = || {
+                parser
+                    .flags()
+                    .map(as_arg_trait)
+                    .chain(parser.opts().map(as_arg_trait))
+                    .chain(parser.positionals().map(as_arg_trait))
+                    .filter(&headed)
+            };
+            if !args().any(|arg| !arg.is_set(ArgSettings::Hidden) || show_hidden) {
+                continue;
+            }
+            if !first {
+                try!(self.writer.write_all(b"\n\n"));
+            }
+            try!(color!(self, format!("{}:\n", heading), header));
+            try!(self.write_args(args()));
             first = false;
         }
 
@@ -609,7 +854,7 @@ impl<'a> Help<'a> {
             if !first {
                 try!(self.writer.write_all(b"\n\n"));
             }
-            try!(color!(self, "SUBCOMMANDS:\n", warning));
+            try!(color!(self, "SUBCOMMANDS:\n", header));
             try!(self.write_subcommands(&parser));
         }
 
@@ -619,19 +864,20 @@ impl<'a> Help<'a> {
     /// Writes help for subcommands of a Parser Object to the wrapped stream.
     fn write_subcommands(&mut self, parser: &Parser) -> io::Result<()> {
         debugln!("Help::write_subcommands;");
-        // The shortest an arg can legally be is 2 (i.e. '-x')
-        self.longest = 2;
         let mut ord_m = VecMap::new();
+        let mut sizes = Vec::with_capacity(parser.subcommands.len());
+        let show_hidden = self.show_hidden;
         for sc in parser
                 .subcommands
                 .iter()
-                .filter(|s| !s.p.is_set(AppSettings::Hidden)) {
+                .filter(|s| !s.p.is_set(AppSettings::Hidden) || show_hidden) {
             let btm = ord_m
                 .entry(sc.p.meta.disp_ord)
                 .or_insert(BTreeMap::new());
-            self.longest = cmp::max(self.longest, sc.p.meta.name.len());
+            sizes.push(self.arg_size(sc));
             btm.insert(sc.p.meta.name.clone(), sc.clone());
         }
+        self.layout(&*sizes);
 
         let mut first = true;
         for btm in ord_m.values() {
@@ -647,6 +893,40 @@ impl<'a> Help<'a> {
         Ok(())
     }
 
+    /// Writes the examples of a Parser Object to the wrapped stream, each command followed by its
+    /// description on the next lines.
+    fn write_examples(&mut self, parser: &Parser) -> io::Result<()> {
+        debugln!("Help::write_examples;");
+        let avail_chars = if self.term_w > 8 {
+            self.term_w - 8
+        } else {
+            usize::MAX
+        };
+        for (i, &(cmd, desc)) in parser.meta.examples.iter().enumerate() {
+            if i > 0 {
+                try!(self.writer.write_all(b"\n"));
+            }
+            try!(write!(self.writer, "{}", TAB));
+            try!(color!(self, cmd, literal));
+            let desc = if self.markup {
+                self.render_markup(desc, avail_chars)
+            } else {
+                let mut desc = desc.to_owned();
+                let longest_w = find_longest!(desc);
+                desc = desc.replace("{n}", "\n");
+                wrap_help(&mut desc, longest_w, avail_chars);
+                desc
+            };
+            for line in desc.lines() {
+                try!(self.writer.write_all(b"\n"));
+                if !line.is_empty() {
+                    try!(write!(self.writer, "{}{}{}", TAB, TAB, line));
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Writes version of a Parser Object to the wrapped stream.
     fn write_version(&mut self, parser: &Parser) -> io::Result<()> {
         debugln!("Help::write_version;");
@@ -663,13 +943,13 @@ impl<'a> Help<'a> {
                 let longest_w = find_longest!(name);            
                 name = name.replace("{n}", "\n");
                 wrap_help(&mut name, longest_w, self.term_w);
-                try!(color!(self, &*name, good));
+                try!(color!(self, &*name, literal));
             }};
         }
         if let Some(bn) = parser.meta.bin_name.as_ref() {
             if bn.contains(' ') {
                 // Incase we're dealing with subcommands i.e. git mv is translated to git-mv
-                try!(color!(self, bn.replace(" ", "-"), good))
+                try!(color!(self, bn.replace(" ", "-"), literal))
             } else {
                 write_name!();
             }
@@ -705,10 +985,15 @@ impl<'a> Help<'a> {
             write_thing!(author)
         }
         if let Some(about) = parser.meta.about {
-            write_thing!(about)
+            if self.markup {
+                let about = self.render_markup(about, self.term_w);
+                try!(write!(self.writer, "{}\n", about));
+            } else {
+                write_thing!(about)
+            }
         }
 
-        try!(color!(self, "\nUSAGE:", warning));
+        try!(color!(self, "\nUSAGE:", header));
         try!(write!(self.writer,
                     "\n{}{}\n\n",
                     TAB,
@@ -718,15 +1003,24 @@ impl<'a> Help<'a> {
         let pos = parser.has_positionals();
         let opts = parser.has_opts();
         let subcmds = parser.has_subcommands();
+        let examples = !parser.meta.examples.is_empty();
 
         if flags || opts || pos || subcmds {
             try!(self.write_all_args(&parser));
         }
 
-        if let Some(h) = parser.meta.more_help {
+        if examples {
             if flags || opts || pos || subcmds {
                 try!(self.writer.write_all(b"\n\n"));
             }
+            try!(color!(self, "EXAMPLES:\n", header));
+            try!(self.write_examples(&parser));
+        }
+
+        if let Some(h) = parser.meta.more_help {
+            if flags || opts || pos || subcmds || examples {
+                try!(self.writer.write_all(b"\n\n"));
+            }
             try!(self.write_before_after_help(h));
         }
 
@@ -858,13 +1152,19 @@ impl<'a> Help<'a> {
     ///     * `{subcommands}` - Help for subcommands.
     ///     * `{after-help}`  - Info to be displayed after the help message.
     ///     * `{before-help}` - Info to be displayed before the help message.
+    ///     * `{long-about}`  - Long description, or the short one when there is none.
+    ///     * `{examples}`    - The examples of the app.
+    ///     * `{heading:H}`   - Help for the arguments under the help heading `H`.
+    ///
+    /// A block between `{?tag}` and `{/tag}` is only written when `tag` has something to show.
     ///
     /// The template system is, on purpose, very simple. Therefore the tags have to writen
     /// in the lowercase and without spacing.
     fn write_templated_help(&mut self, parser: &Parser, template: &str) -> ClapResult<()> {
         debugln!("Help::write_templated_help;");
+        let template = resolve_conditionals(template, |tag| has_section(parser, tag));
         let mut tmplr = Cursor::new(&template);
-        let mut tag_buf = Cursor::new(vec![0u8; 15]);
+        let mut tag_buf = Cursor::new(vec![0u8; 64]);
 
         // The strategy is to copy the template from the the reader to wrapped stream
         // until a tag is found. Depending on its value, the appropriate content is copied
@@ -872,6 +1172,9 @@ impl<'a> Help<'a> {
         // The copy from template is then resumed, repeating this sequence until reading
         // the complete template.
 
+        // Args with a help heading are only written by their `{heading:..}` tag
+        let unheaded = |arg: &&ArgWithOrder| parser.help_heading_of(arg.name()).is_none();
+
         loop {
             let tag_length = match copy_and_capture(&mut tmplr, &mut self.writer, &mut tag_buf) {
                 None => return Ok(()),
@@ -904,9 +1207,24 @@ impl<'a> Help<'a> {
                                 parser.meta.author.unwrap_or("unknown author")));
                 }
                 b"about" => {
-                    try!(write!(self.writer,
-                                "{}",
-                                parser.meta.about.unwrap_or("unknown about")));
+                    let about = parser.meta.about.unwrap_or("unknown about");
+                    if self.markup {
+                        try!(self.write_before_after_help(about));
+                    } else {
+                        try!(write!(self.writer, "{}", about));
+                    }
+                }
+                b"long-about" => {
+                    let about = parser
+                        .meta
+                        .long_about
+                        .or(parser.meta.about)
+                        .unwrap_or("unknown long-about");
+                    if self.markup {
+                        try!(self.write_before_after_help(about));
+                    } else {
+                        try!(write!(self.writer, "{}", about));
+                    }
                 }
                 b"usage" => {
                     try!(write!(self.writer, "{}", usage::create_usage_no_title(parser, &[])));
@@ -918,30 +1236,54 @@ impl<'a> Help<'a> {
                     let opts_flags = parser
                         .flags()
                         .map(as_arg_trait)
-                        .chain(parser.opts().map(as_arg_trait));
+                        .chain(parser.opts().map(as_arg_trait))
+                        .filter(&unheaded);
                     try!(self.write_args(opts_flags));
                 }
                 b"flags" => {
-                    try!(self.write_args(parser.flags().map(as_arg_trait)));
+                    try!(self.write_args(parser.flags().map(as_arg_trait).filter(&unheaded)));
                 }
                 b"options" => {
-                    try!(self.write_args(parser.opts().map(as_arg_trait)));
+                    try!(self.write_args(parser.opts().map(as_arg_trait).filter(&unheaded)));
                 }
                 b"positionals" => {
-                    try!(self.write_args(parser.positionals().map(as_arg_trait)));
+                    let args = parser.positionals().map(as_arg_trait).filter(&unheaded);
+                    try!(self.write_args(args));
+                }
+                r if r.starts_with(b"heading:") => {
+                    let heading = String::from_utf8_lossy(&r[8..]);
+                    let headed = |arg: &&ArgWithOrder| {
+                        parser.help_heading_of(arg.name()) == Some(&*heading)
+                    };
+                    let args = parser
+                        .flags()
+                        .map(as_arg_trait)
+                        .chain(parser.opts().map(as_arg_trait))
+                        .chain(parser.positionals().map(as_arg_trait))
+                        .filter(&headed);
+                    try!(self.write_args(args));
+                }
+                b"examples" => {
+                    try!(self.write_examples(&parser));
                 }
                 b"subcommands" => {
                     try!(self.write_subcommands(&parser));
                 }
                 b"after-help" => {
-                    try!(write!(self.writer,
-                                "{}",
-                                parser.meta.more_help.unwrap_or("unknown after-help")));
+                    let after = parser.meta.more_help.unwrap_or("unknown after-help");
+                    if self.markup {
+                        try!(self.write_before_after_help(after));
+                    } else {
+                        try!(write!(self.writer, "{}", after));
+                    }
                 }
                 b"before-help" => {
-                    try!(write!(self.writer,
-                                "{}",
-                                parser.meta.pre_help.unwrap_or("unknown before-help")));
+                    let before = parser.meta.pre_help.unwrap_or("unknown before-help");
+                    if self.markup {
+                        try!(self.write_before_after_help(before));
+                    } else {
+                        try!(write!(self.writer, "{}", before));
+                    }
                 }
                 // Unknown tag, write it back.
                 r => {
@@ -954,6 +1296,95 @@ impl<'a> Help<'a> {
     }
 }
 
+/// Keeps the conditional blocks `{?tag}...{/tag}` of a template whose tag has something to show,
+/// according to `shown`, and drops the others. The block markers are removed either way, and
+/// blocks may be nested.
+fn resolve_conditionals<F>(template: &str, shown: F) -> String
+    where F: Fn(&str) -> bool
+{
+    debugln!("resolve_conditionals;");
+    let mut ret = String::with_capacity(template.len());
+    // The tags of the open blocks, and whether the text they contain is kept
+    let mut blocks: Vec<(&str, bool)> = vec![];
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let keep = blocks.last().map_or(true, |&(_, k)| k);
+        let (text, tag) = rest.split_at(start);
+        if keep {
+            ret.push_str(text);
+        }
+        let end = match tag.find('}') {
+            Some(end) => end,
+            None => {
+                rest = tag;
+                break;
+            }
+        };
+        let name = &tag[1..end];
+        if name.starts_with('?') {
+            debugln!("resolve_conditionals: block={}", &name[1..]);
+            blocks.push((&name[1..], keep && shown(&name[1..])));
+        } else if name.starts_with('/') && blocks.last().map_or(false, |&(n, _)| n == &name[1..]) {
+            blocks.pop();
+        } else if keep {
+            ret.push_str(&tag[..end + 1]);
+        }
+        rest = &tag[end + 1..];
+    }
+    if blocks.last().map_or(true, |&(_, k)| k) {
+        ret.push_str(rest);
+    }
+    ret
+}
+
+/// Whether the template tag `tag` writes anything for `parser`.
+fn has_section(parser: &Parser, tag: &str) -> bool {
+    let visible = |arg: &&ArgWithOrder| !arg.is_set(ArgSettings::Hidden);
+    let unheaded = |arg: &&ArgWithOrder| parser.help_heading_of(arg.name()).is_none();
+    let flags = || parser.flags().map(as_arg_trait).filter(&visible).any(|a| unheaded(&a));
+    let opts = || parser.opts().map(as_arg_trait).filter(&visible).any(|a| unheaded(&a));
+    match tag {
+        "bin" | "usage" => true,
+        "version" => parser.meta.version.is_some(),
+        "author" => parser.meta.author.is_some(),
+        "about" => parser.meta.about.is_some(),
+        "long-about" => parser.meta.long_about.or(parser.meta.about).is_some(),
+        "before-help" => parser.meta.pre_help.is_some(),
+        "after-help" => parser.meta.more_help.is_some(),
+        "examples" => !parser.meta.examples.is_empty(),
+        "all-args" => {
+            parser
+                .flags()
+                .map(as_arg_trait)
+                .chain(parser.opts().map(as_arg_trait))
+                .chain(parser.positionals().map(as_arg_trait))
+                .any(|a| visible(&a)) ||
+            has_section(parser, "subcommands")
+        }
+        "unified" => flags() || opts(),
+        "flags" => flags(),
+        "options" => opts(),
+        "positionals" => {
+            parser
+                .positionals()
+                .map(as_arg_trait)
+                .filter(&visible)
+                .any(|a| unheaded(&a))
+        }
+        "subcommands" => parser.subcommands.iter().any(|sc| !sc.p.is_set(AppSettings::Hidden)),
+        t if t.starts_with("heading:") => {
+            parser
+                .flags()
+                .map(as_arg_trait)
+                .chain(parser.opts().map(as_arg_trait))
+                .chain(parser.positionals().map(as_arg_trait))
+                .filter(&visible)
+                .any(|a| parser.help_heading_of(a.name()) == Some(&t[8..]))
+        }
+        _ => false,
+    }
+}
+
 fn wrap_help(help: &mut String, longest_w: usize, avail_chars: usize) {
     debugln!("Help::wrap_help: longest_w={}, avail_chars={}",
              longest_w,
@@ -999,7 +1430,7 @@ fn wrap_help(help: &mut String, longest_w: usize, avail_chars: usize) {
 
 #[cfg(test)]
 mod test {
-    use super::wrap_help;
+    use super::{resolve_conditionals, str_width, wrap_help};
 
     #[test]
     fn wrap_help_last_word() {
@@ -1007,4 +1438,25 @@ mod test {
         wrap_help(&mut help, 3, 5);
         assert_eq!(help, "foo\nbar\nbaz");
     }
+
+    #[test]
+    fn str_width_skips_escapes() {
+        assert_eq!(str_width("\x1b[1;32mfoo\x1b[0m bar"), 7);
+        assert_eq!(str_width("\x1b[38;5;244mé\x1b[0m"), 1);
+        assert_eq!(str_width("foo\x1b[3"), 3);
+    }
+
+    #[test]
+    fn wrap_help_colored() {
+        let mut help = String::from("\x1b[32mfoo\x1b[0m bar \x1b[33mbaz\x1b[0m qux");
+        wrap_help(&mut help, 3, 8);
+        assert_eq!(help, "\x1b[32mfoo\x1b[0m bar\n\x1b[33mbaz\x1b[0m qux");
+    }
+
+    #[test]
+    fn resolve_nested_conditionals() {
+        let tmpl = "a{?x}b{?y}c{/y}d{/x}{?y}e{?x}f{/x}{/y}{tag} {unclosed";
+        assert_eq!(resolve_conditionals(tmpl, |t| t == "x"), "abd{tag} {unclosed");
+        assert_eq!(resolve_conditionals(tmpl, |_| false), "a{tag} {unclosed");
+    }
 }
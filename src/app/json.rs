@@ -0,0 +1,260 @@
+// Std
+use std::ffi::OsStr;
+
+// Third Party
+use serde_json::{self, Map, Value};
+use vec_map::VecMap;
+
+// Internal
+use app::parser::Parser;
+use app::settings::APP_SETTINGS;
+use args::{ArgGroup, ArgSettings, Base, Switched, Valued};
+use fmt::Theme;
+
+/// The version of the document written by `App::to_json`, which is bumped whenever a key is
+/// renamed or changes meaning (adding keys doesn't bump it)
+pub const SCHEMA_VERSION: u64 = 1;
+
+pub const ARG_SETTINGS: [ArgSettings; 16] = [ArgSettings::Required,
+                                             ArgSettings::Multiple,
+                                             ArgSettings::EmptyValues,
+                                             ArgSettings::Global,
+                                             ArgSettings::Hidden,
+                                             ArgSettings::TakesValue,
+                                             ArgSettings::UseValueDelimiter,
+                                             ArgSettings::NextLineHelp,
+                                             ArgSettings::RequiredUnlessAll,
+                                             ArgSettings::RequireDelimiter,
+                                             ArgSettings::ValueDelimiterNotSet,
+                                             ArgSettings::HidePossibleValues,
+                                             ArgSettings::AllowLeadingHyphen,
+                                             ArgSettings::RequireEquals,
+                                             ArgSettings::Last,
+                                             ArgSettings::HideDefaultValue];
+
+// Inserts `$v` under `$k` when it's `Some`, converting it with `$f`
+macro_rules! insert_some {
+    ($m:ident, $k:expr, $v:expr) => {
+        insert_some!($m, $k, $v, |v| Value::from(v))
+    };
+    ($m:ident, $k:expr, $v:expr, $f:expr) => {
+        if let Some(v) = $v {
+            $m.insert($k.to_owned(), $f(v));
+        }
+    };
+}
+
+pub fn to_json(p: &Parser) -> String {
+    let mut doc = Map::new();
+    doc.insert("schema_version".to_owned(), Value::from(SCHEMA_VERSION));
+    if let Value::Object(app) = app_to_value(p) {
+        doc.extend(app);
+    }
+    serde_json::to_string_pretty(&Value::Object(doc)).expect("failed to serialize the App")
+}
+
+fn app_to_value(p: &Parser) -> Value {
+    debugln!("json::app_to_value: name={}", p.meta.name);
+    let mut m = Map::new();
+    m.insert("name".to_owned(), Value::from(&*p.meta.name));
+    insert_some!(m, "bin_name", p.meta.bin_name.as_ref().map(|s| &**s));
+    insert_some!(m, "version", p.meta.version);
+    insert_some!(m, "long_version", p.meta.long_version);
+    insert_some!(m, "author", p.meta.author);
+    insert_some!(m, "about", p.meta.about);
+    insert_some!(m, "long_about", p.meta.long_about);
+    insert_some!(m, "before_help", p.meta.pre_help);
+    insert_some!(m, "after_help", p.meta.more_help);
+    insert_some!(m, "usage", p.meta.usage_str);
+    if !p.meta.usages.is_empty() {
+        m.insert("usages".to_owned(), strs_to_value(&p.meta.usages));
+    }
+    insert_some!(m, "help_template", p.meta.template);
+    insert_some!(m, "deprecated", p.meta.deprecated);
+    if p.meta.theme != Theme::default() {
+        let mut theme = Map::new();
+        for &(name, style) in p.meta.theme.parts().iter() {
+            theme.insert(name.to_owned(), Value::from(style.to_string()));
+        }
+        m.insert("theme".to_owned(), Value::Object(theme));
+    }
+    if !p.meta.examples.is_empty() {
+        let examples = p.meta
+            .examples
+            .iter()
+            .map(|&(cmd, desc)| {
+                let mut m = Map::new();
+                m.insert("command".to_owned(), Value::from(cmd));
+                m.insert("description".to_owned(), Value::from(desc));
+                Value::Object(m)
+            })
+            .collect();
+        m.insert("examples".to_owned(), Value::Array(examples));
+    }
+    insert_some!(m, "help_message", p.help_message);
+    insert_some!(m, "version_message", p.version_message);
+    if p.meta.disp_ord != 999 {
+        m.insert("display_order".to_owned(), Value::from(p.meta.disp_ord));
+    }
+    insert_some!(m, "aliases", p.meta.aliases.as_ref(), aliases_to_value);
+
+    let settings: Vec<_> = APP_SETTINGS.iter()
+        .filter(|&&s| p.is_set(s))
+        .map(|s| Value::from(format!("{:?}", s)))
+        .collect();
+    m.insert("settings".to_owned(), Value::Array(settings));
+    let g_settings: Vec<_> = APP_SETTINGS.iter()
+        .filter(|&&s| p.g_settings.is_set(s))
+        .map(|s| Value::from(format!("{:?}", s)))
+        .collect();
+    if !g_settings.is_empty() {
+        m.insert("global_settings".to_owned(), Value::Array(g_settings));
+    }
+
+    let mut args = vec![];
+    for f in &p.flags {
+        args.push(arg_to_value(p, "flag", &f.b, Some(&f.s), None));
+    }
+    for o in &p.opts {
+        args.push(arg_to_value(p, "option", &o.b, Some(&o.s), Some(&o.v)));
+    }
+    for pos in p.positionals.values() {
+        let mut a = arg_to_value(p, "positional", &pos.b, None, Some(&pos.v));
+        if let Value::Object(ref mut a) = a {
+            a.insert("index".to_owned(), Value::from(pos.index));
+        }
+        args.push(a);
+    }
+    m.insert("args".to_owned(), Value::Array(args));
+    if !p.groups.is_empty() {
+        m.insert("groups".to_owned(),
+                 Value::Array(p.groups.iter().map(group_to_value).collect()));
+    }
+    if !p.subcommands.is_empty() {
+        m.insert("subcommands".to_owned(),
+                 Value::Array(p.subcommands.iter().map(|sc| app_to_value(&sc.p)).collect()));
+    }
+    Value::Object(m)
+}
+
+fn arg_to_value(p: &Parser,
+                kind: &str,
+                b: &Base,
+                s: Option<&Switched>,
+                v: Option<&Valued>)
+                -> Value {
+    let mut m = Map::new();
+    m.insert("name".to_owned(), Value::from(b.name));
+    m.insert("kind".to_owned(), Value::from(kind));
+    if let Some(s) = s {
+        insert_some!(m, "short", s.short, |c: char| Value::from(c.to_string()));
+        insert_some!(m, "long", s.long);
+        insert_some!(m, "aliases", s.aliases.as_ref(), aliases_to_value);
+        if s.disp_ord != 999 {
+            m.insert("display_order".to_owned(), Value::from(s.disp_ord));
+        }
+    }
+    insert_some!(m, "help", b.help);
+    insert_some!(m, "long_help", b.long_help);
+    insert_some!(m, "help_heading", b.help_heading);
+    insert_some!(m, "deprecated", b.deprecated);
+
+    let settings: Vec<_> = ARG_SETTINGS.iter()
+        .filter(|&&s| b.is_set(s))
+        .map(|s| Value::from(format!("{:?}", s)))
+        .collect();
+    m.insert("settings".to_owned(), Value::Array(settings));
+
+    if let Some(v) = v {
+        insert_some!(m, "value_names", v.val_names.as_ref(), |names: &VecMap<&str>| {
+            strs_to_value(names.values())
+        });
+        insert_some!(m, "number_of_values", v.num_vals);
+        insert_some!(m, "min_values", v.min_vals);
+        insert_some!(m, "max_values", v.max_vals);
+        insert_some!(m, "value_delimiter", v.val_delim, |c: char| Value::from(c.to_string()));
+        insert_some!(m, "value_terminator", v.terminator);
+        insert_some!(m, "possible_values", v.possible_vals.as_ref(), strs_to_value);
+        if let Some(ref helps) = v.pv_help {
+            let mut h = Map::new();
+            for &(val, help) in helps {
+                h.insert(val.to_owned(), Value::from(help));
+            }
+            m.insert("possible_values_help".to_owned(), Value::Object(h));
+        }
+        insert_some!(m, "default_value", v.default_val, os_str_to_value);
+        insert_some!(m, "default_value_ifs", v.default_vals_ifs.as_ref(), |ifs: &VecMap<_>| {
+            Value::Array(ifs.values()
+                .map(|&(arg, val, default): &(&str, Option<&OsStr>, &OsStr)| {
+                    let mut m = Map::new();
+                    m.insert("arg".to_owned(), Value::from(arg));
+                    m.insert("value".to_owned(), val.map_or(Value::Null, os_str_to_value));
+                    m.insert("default".to_owned(), os_str_to_value(default));
+                    Value::Object(m)
+                })
+                .collect())
+        });
+        insert_some!(m, "value_hint", v.value_hint, |h| Value::from(format!("{:?}", h)));
+    }
+
+    insert_some!(m, "groups", b.groups.as_ref(), strs_to_value);
+    insert_some!(m, "requires", b.requires.as_ref(), |reqs: &Vec<(Option<&str>, &str)>| {
+        Value::Array(reqs.iter()
+            .map(|&(val, arg)| {
+                let mut m = Map::new();
+                m.insert("arg".to_owned(), Value::from(arg));
+                m.insert("if_value".to_owned(), val.map_or(Value::Null, Value::from));
+                Value::Object(m)
+            })
+            .collect())
+    });
+    // `Arg::required_if` is only kept by the Parser, keyed by the name of the required arg
+    let required_ifs: Vec<_> = p.r_ifs
+        .iter()
+        .filter(|&&(_, _, name)| name == b.name)
+        .map(|&(arg, val, _)| {
+            let mut m = Map::new();
+            m.insert("arg".to_owned(), Value::from(arg));
+            m.insert("value".to_owned(), Value::from(val));
+            Value::Object(m)
+        })
+        .collect();
+    if !required_ifs.is_empty() {
+        m.insert("required_if".to_owned(), Value::Array(required_ifs));
+    }
+    insert_some!(m, "conflicts_with", b.blacklist.as_ref(), strs_to_value);
+    insert_some!(m, "overrides_with", b.overrides.as_ref(), strs_to_value);
+    insert_some!(m, "required_unless", b.r_unless.as_ref(), strs_to_value);
+    Value::Object(m)
+}
+
+fn group_to_value(g: &ArgGroup) -> Value {
+    let mut m = Map::new();
+    m.insert("name".to_owned(), Value::from(g.name));
+    m.insert("args".to_owned(), strs_to_value(&g.args));
+    m.insert("required".to_owned(), Value::from(g.required));
+    m.insert("multiple".to_owned(), Value::from(g.multiple));
+    insert_some!(m, "requires", g.requires.as_ref(), strs_to_value);
+    insert_some!(m, "conflicts_with", g.conflicts.as_ref(), strs_to_value);
+    insert_some!(m, "help_heading", g.heading);
+    Value::Object(m)
+}
+
+fn aliases_to_value(aliases: &Vec<(&str, bool)>) -> Value {
+    Value::Array(aliases.iter()
+        .map(|&(name, visible)| {
+            let mut m = Map::new();
+            m.insert("name".to_owned(), Value::from(name));
+            m.insert("visible".to_owned(), Value::from(visible));
+            Value::Object(m)
+        })
+        .collect())
+}
+
+fn strs_to_value<'a, 'b, I: IntoIterator<Item = &'a &'b str>>(it: I) -> Value
+    where 'b: 'a
+{
+    Value::Array(it.into_iter().map(|&s| Value::from(s)).collect())
+}
+
+fn os_str_to_value(s: &OsStr) -> Value { Value::from(s.to_string_lossy().into_owned()) }
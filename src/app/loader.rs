@@ -0,0 +1,670 @@
+// Loads App definitions from data documents (YAML, JSON or TOML). The walker is generic over the
+// `Document` trait so each format only has to say how to look inside its values, and every error
+// carries the path of the offending key, i.e. `subcommands[2].args.verbose.multiple`
+
+// Std
+#[cfg(feature = "yaml")]
+use std::collections::BTreeMap;
+
+// Third Party
+#[cfg(feature = "yaml")]
+use yaml_rust::Yaml;
+#[cfg(feature = "serde")]
+use serde_json;
+#[cfg(feature = "toml")]
+use toml;
+
+// Internal
+use app::{App, AppSettings};
+use args::{Arg, ArgGroup, ArgSettings, ValueHint};
+use errors::{Error, Result as ClapResult};
+use fmt::{Style, Theme};
+
+pub trait Document {
+    fn as_str(&self) -> Option<&str>;
+    fn as_bool(&self) -> Option<bool>;
+    fn as_i64(&self) -> Option<i64>;
+    fn is_null(&self) -> bool;
+    fn as_list(&self) -> Option<Vec<&Self>>;
+    // The keys which aren't strings are `None`
+    fn as_map(&self) -> Option<Vec<(Option<&str>, &Self)>>;
+}
+
+#[cfg(feature = "yaml")]
+impl Document for Yaml {
+    fn as_str(&self) -> Option<&str> { Yaml::as_str(self) }
+    fn as_bool(&self) -> Option<bool> { Yaml::as_bool(self) }
+    fn as_i64(&self) -> Option<i64> { Yaml::as_i64(self) }
+    fn is_null(&self) -> bool { Yaml::is_null(self) }
+    fn as_list(&self) -> Option<Vec<&Self>> { self.as_vec().map(|v| v.iter().collect()) }
+    fn as_map(&self) -> Option<Vec<(Option<&str>, &Self)>> {
+        self.as_hash().map(|h| h.iter().map(|(k, v)| (k.as_str(), v)).collect())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Document for serde_json::Value {
+    fn as_str(&self) -> Option<&str> { serde_json::Value::as_str(self) }
+    fn as_bool(&self) -> Option<bool> { serde_json::Value::as_bool(self) }
+    fn as_i64(&self) -> Option<i64> { serde_json::Value::as_i64(self) }
+    fn is_null(&self) -> bool { serde_json::Value::is_null(self) }
+    fn as_list(&self) -> Option<Vec<&Self>> { self.as_array().map(|v| v.iter().collect()) }
+    fn as_map(&self) -> Option<Vec<(Option<&str>, &Self)>> {
+        self.as_object().map(|m| m.iter().map(|(k, v)| (Some(&**k), v)).collect())
+    }
+}
+
+#[cfg(feature = "toml")]
+impl Document for toml::Value {
+    fn as_str(&self) -> Option<&str> { toml::Value::as_str(self) }
+    fn as_bool(&self) -> Option<bool> { toml::Value::as_bool(self) }
+    fn as_i64(&self) -> Option<i64> { self.as_integer() }
+    // TOML has no null
+    fn is_null(&self) -> bool { false }
+    fn as_list(&self) -> Option<Vec<&Self>> { self.as_array().map(|v| v.iter().collect()) }
+    fn as_map(&self) -> Option<Vec<(Option<&str>, &Self)>> {
+        self.as_table().map(|m| m.iter().map(|(k, v)| (Some(&**k), v)).collect())
+    }
+}
+
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_owned()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+fn expect_str<'a, D: Document>(v: &'a D, path: &str) -> ClapResult<&'a str> {
+    v.as_str().ok_or_else(|| Error::invalid_definition(path, "expected string"))
+}
+
+fn expect_bool<D: Document>(v: &D, path: &str) -> ClapResult<bool> {
+    v.as_bool().ok_or_else(|| Error::invalid_definition(path, "expected bool"))
+}
+
+fn expect_u64<D: Document>(v: &D, path: &str) -> ClapResult<u64> {
+    match v.as_i64() {
+        Some(i) if i >= 0 => Ok(i as u64),
+        _ => Err(Error::invalid_definition(path, "expected non-negative integer")),
+    }
+}
+
+fn expect_list<'a, D: Document>(v: &'a D, path: &str) -> ClapResult<Vec<&'a D>> {
+    v.as_list().ok_or_else(|| Error::invalid_definition(path, "expected list"))
+}
+
+fn expect_map<'a, D: Document>(v: &'a D, path: &str) -> ClapResult<Vec<(&'a str, &'a D)>> {
+    let map = try!(v.as_map().ok_or_else(|| Error::invalid_definition(path, "expected map")));
+    let mut ret = Vec::with_capacity(map.len());
+    for (k, v) in map {
+        match k {
+            Some(k) => ret.push((k, v)),
+            None => return Err(Error::invalid_definition(path, "expected string keys")),
+        }
+    }
+    Ok(ret)
+}
+
+// Either a single string, or a list of them
+fn str_or_list<'a, D: Document>(v: &'a D, path: &str) -> ClapResult<Vec<&'a str>> {
+    if let Some(s) = v.as_str() {
+        return Ok(vec![s]);
+    }
+    let list = try!(v.as_list()
+        .ok_or_else(|| Error::invalid_definition(path, "expected string or list")));
+    let mut ret = Vec::with_capacity(list.len());
+    for (i, s) in list.into_iter().enumerate() {
+        ret.push(try!(expect_str(s, &format!("{}[{}]", path, i))));
+    }
+    Ok(ret)
+}
+
+// A list of `[first, second]` pairs, such as the conditions of `required_if`
+fn pairs<'a, D: Document>(v: &'a D, path: &str) -> ClapResult<Vec<(&'a str, &'a str)>> {
+    let mut ret = vec![];
+    for (i, pair) in try!(expect_list(v, path)).into_iter().enumerate() {
+        let path = format!("{}[{}]", path, i);
+        let pair = try!(expect_list(pair, &path));
+        if pair.len() != 2 {
+            return Err(Error::invalid_definition(&path, "expected list of 2 strings"));
+        }
+        ret.push((try!(expect_str(pair[0], &format!("{}[0]", path))),
+                  try!(expect_str(pair[1], &format!("{}[1]", path)))));
+    }
+    Ok(ret)
+}
+
+// A list of `[arg, value, default]` triples for `default_value_if`, where the value may be null,
+// or left out (i.e. `[arg, default]`) since TOML has no null
+fn triples<'a, D: Document>(v: &'a D,
+                            path: &str)
+                            -> ClapResult<Vec<(&'a str, Option<&'a str>, &'a str)>> {
+    let mut ret = vec![];
+    for (i, t) in try!(expect_list(v, path)).into_iter().enumerate() {
+        let path = format!("{}[{}]", path, i);
+        let t = try!(expect_list(t, &path));
+        let arg = match t.first() {
+            Some(arg) if t.len() == 2 || t.len() == 3 => {
+                try!(expect_str(*arg, &format!("{}[0]", path)))
+            }
+            _ => return Err(Error::invalid_definition(&path, "expected list of 2 or 3 values")),
+        };
+        let val = if t.len() == 3 && !t[1].is_null() {
+            Some(try!(expect_str(t[1], &format!("{}[1]", path))))
+        } else {
+            None
+        };
+        let last = t.len() - 1;
+        ret.push((arg, val, try!(expect_str(t[last], &format!("{}[{}]", path, last)))));
+    }
+    Ok(ret)
+}
+
+fn app_setting<D: Document>(v: &D, path: &str) -> ClapResult<AppSettings> {
+    let s = try!(expect_str(v, path));
+    s.parse().map_err(|_| Error::invalid_definition(path, &format!("unknown AppSetting '{}'", s)))
+}
+
+fn app_settings<D: Document>(v: &D, path: &str) -> ClapResult<Vec<AppSettings>> {
+    if v.as_str().is_some() {
+        return Ok(vec![try!(app_setting(v, path))]);
+    }
+    let list = try!(v.as_list()
+        .ok_or_else(|| Error::invalid_definition(path, "expected string or list")));
+    let mut ret = vec![];
+    for (i, s) in list.into_iter().enumerate() {
+        ret.push(try!(app_setting(s, &format!("{}[{}]", path, i))));
+    }
+    Ok(ret)
+}
+
+// A map of the parts of a theme to their styles, the other parts keeping their default style
+fn theme<D: Document>(v: &D, path: &str) -> ClapResult<Theme> {
+    let mut theme = Theme::default();
+    for (k, v) in try!(expect_map(v, path)) {
+        let p = join(path, k);
+        let style = try!(try!(expect_str(v, &p))
+            .parse::<Style>()
+            .map_err(|e| Error::invalid_definition(&p, &e)));
+        match theme.part_mut(k) {
+            Some(part) => *part = style,
+            None => return Err(Error::invalid_definition(&p, "unknown part of a theme")),
+        }
+    }
+    Ok(theme)
+}
+
+// Splits an entry of `args`, `groups` or `subcommands` (or a whole App) into its name and
+// settings, which are either written as `{name: {settings...}}`, or as `{name: "name",
+// settings...}`
+fn named_entry<'a, D: Document>(v: &'a D,
+                                path: &str)
+                                -> ClapResult<(&'a str, Vec<(&'a str, &'a D)>)> {
+    let map = try!(v.as_map().ok_or_else(|| Error::invalid_definition(path, "expected map")));
+    split_entry(map, path)
+}
+
+fn split_entry<'a, D: Document>(map: Vec<(Option<&'a str>, &'a D)>,
+                                path: &str)
+                                -> ClapResult<(&'a str, Vec<(&'a str, &'a D)>)> {
+    let mut entries = Vec::with_capacity(map.len());
+    for (k, v) in map {
+        match k {
+            Some(k) => entries.push((k, v)),
+            None => return Err(Error::invalid_definition(path, "expected string keys")),
+        }
+    }
+    if entries.len() == 1 && (entries[0].1.is_null() || entries[0].1.as_map().is_some()) {
+        let (name, settings) = entries[0];
+        let settings = if settings.is_null() {
+            vec![]
+        } else {
+            try!(expect_map(settings, &join(path, name)))
+        };
+        return Ok((name, settings));
+    }
+    match entries.iter().find(|&&(k, _)| k == "name") {
+        Some(&(_, name)) => {
+            let name = try!(expect_str(name, &join(path, "name")));
+            Ok((name, entries.into_iter().filter(|&(k, _)| k != "name").collect()))
+        }
+        None => Err(Error::invalid_definition(path, "missing 'name'")),
+    }
+}
+
+#[cfg(feature = "yaml")]
+pub fn arg_from_yaml<'a>(y: &'a BTreeMap<Yaml, Yaml>) -> ClapResult<Arg<'a, 'a>> {
+    let (name, settings) = try!(split_entry(y.iter().map(|(k, v)| (k.as_str(), v)).collect(),
+                                            ""));
+    arg_from_entries(name, settings, name)
+}
+
+#[cfg(feature = "yaml")]
+pub fn group_from_yaml<'a>(y: &'a BTreeMap<Yaml, Yaml>) -> ClapResult<ArgGroup<'a>> {
+    let (name, settings) = try!(split_entry(y.iter().map(|(k, v)| (k.as_str(), v)).collect(),
+                                            ""));
+    group_from_entries(name, settings, name)
+}
+
+// When `strict` isn't set, unknown keys of an App are ignored like `App::from_yaml` always did
+// Keeps `doc` for the rest of the program, for the `App`s which borrow their strings from a
+// document they were given as text
+#[cfg(any(feature = "serde", feature = "toml"))]
+pub fn keep<D: Document + 'static>(doc: D) -> &'static D { unsafe { &*Box::into_raw(Box::new(doc)) } }
+
+pub fn app_from_document<'a, D: Document>(v: &'a D, strict: bool) -> ClapResult<App<'a, 'a>> {
+    let (name, settings) = try!(named_entry(v, ""));
+    app_from_entries(name, settings, "", strict)
+}
+
+fn app_from_entries<'a, D: Document>(name: &'a str,
+                                     entries: Vec<(&'a str, &'a D)>,
+                                     path: &str,
+                                     strict: bool)
+                                     -> ClapResult<App<'a, 'a>> {
+    debugln!("loader::app_from_entries: name={}, path={}", name, path);
+    let mut a = App::new(name);
+    for (k, v) in entries {
+        let p = join(path, k);
+        a = match k {
+            "bin_name" => a.bin_name(try!(expect_str(v, &p))),
+            "version" => a.version(try!(expect_str(v, &p))),
+            "long_version" => a.long_version(try!(expect_str(v, &p))),
+            "author" => a.author(try!(expect_str(v, &p))),
+            "about" => a.about(try!(expect_str(v, &p))),
+            "long_about" => a.long_about(try!(expect_str(v, &p))),
+            "before_help" => a.before_help(try!(expect_str(v, &p))),
+            "after_help" => a.after_help(try!(expect_str(v, &p))),
+            "template" => a.template(try!(expect_str(v, &p))),
+            "theme" => a.theme(try!(theme(v, &p))),
+            "deprecated" => a.deprecated(try!(expect_str(v, &p))),
+            "example" | "examples" => {
+                for (cmd, desc) in try!(pairs(v, &p)) {
+                    a = a.example(cmd, desc);
+                }
+                a
+            }
+            "usage" => a.usage(try!(expect_str(v, &p))),
+            "usages" => a.usages(&*try!(str_or_list(v, &p))),
+            "help" => a.help(try!(expect_str(v, &p))),
+            "help_short" => a.help_short(try!(expect_str(v, &p))),
+            "version_short" => a.version_short(try!(expect_str(v, &p))),
+            "help_message" => a.help_message(try!(expect_str(v, &p))),
+            "version_message" => a.version_message(try!(expect_str(v, &p))),
+            "display_order" => a.display_order(try!(expect_u64(v, &p)) as usize),
+            "set_term_width" => a.set_term_width(try!(expect_u64(v, &p)) as usize),
+            "max_term_width" => a.max_term_width(try!(expect_u64(v, &p)) as usize),
+            "alias" | "aliases" => {
+                for s in try!(str_or_list(v, &p)) {
+                    a = a.alias(s);
+                }
+                a
+            }
+            "visible_alias" | "visible_aliases" => {
+                for s in try!(str_or_list(v, &p)) {
+                    a = a.visible_alias(s);
+                }
+                a
+            }
+            "setting" | "settings" => {
+                for s in try!(app_settings(v, &p)) {
+                    a = a.setting(s);
+                }
+                a
+            }
+            "global_setting" | "global_settings" => {
+                for s in try!(app_settings(v, &p)) {
+                    a = a.global_setting(s);
+                }
+                a
+            }
+            "unset_setting" | "unset_settings" => {
+                for s in try!(app_settings(v, &p)) {
+                    a = a.unset_setting(s);
+                }
+                a
+            }
+            "args" => {
+                for arg in try!(expect_list(v, &p)) {
+                    let (name, settings) = try!(named_entry(arg, &p));
+                    a = a.arg(try!(arg_from_entries(name, settings, &join(&p, name))));
+                }
+                a
+            }
+            "groups" => {
+                for g in try!(expect_list(v, &p)) {
+                    let (name, settings) = try!(named_entry(g, &p));
+                    a = a.group(try!(group_from_entries(name, settings, &join(&p, name))));
+                }
+                a
+            }
+            "subcommands" => {
+                for (i, sc) in try!(expect_list(v, &p)).into_iter().enumerate() {
+                    let sc_path = format!("{}[{}]", p, i);
+                    let (name, settings) = try!(named_entry(sc, &sc_path));
+                    a = a.subcommand(try!(app_from_entries(name, settings, &sc_path, strict)));
+                }
+                a
+            }
+            _ if strict => return Err(Error::invalid_definition(&p, "unknown App setting")),
+            _ => {
+                debugln!("loader::app_from_entries: ignoring unknown key {}", p);
+                a
+            }
+        };
+    }
+    Ok(a)
+}
+
+fn arg_from_entries<'a, D: Document>(name: &'a str,
+                                         entries: Vec<(&'a str, &'a D)>,
+                                         path: &str)
+                                         -> ClapResult<Arg<'a, 'a>> {
+    debugln!("loader::arg_from_entries: name={}, path={}", name, path);
+    let mut a = Arg::with_name(name);
+    for (k, v) in entries {
+        let p = join(path, k);
+        a = match k {
+            "short" => a.short(try!(expect_str(v, &p))),
+            "long" => a.long(try!(expect_str(v, &p))),
+            "help" => a.help(try!(expect_str(v, &p))),
+            "long_help" => a.long_help(try!(expect_str(v, &p))),
+            "help_heading" => a.help_heading(try!(expect_str(v, &p))),
+            "deprecated" => a.deprecated(try!(expect_str(v, &p))),
+            "required" => a.required(try!(expect_bool(v, &p))),
+            "takes_value" => a.takes_value(try!(expect_bool(v, &p))),
+            "index" => a.index(try!(expect_u64(v, &p))),
+            "global" => a.global(try!(expect_bool(v, &p))),
+            "multiple" => a.multiple(try!(expect_bool(v, &p))),
+            "hidden" => a.hidden(try!(expect_bool(v, &p))),
+            "next_line_help" => a.next_line_help(try!(expect_bool(v, &p))),
+            "empty_values" => a.empty_values(try!(expect_bool(v, &p))),
+            "number_of_values" => a.number_of_values(try!(expect_u64(v, &p))),
+            "max_values" => a.max_values(try!(expect_u64(v, &p))),
+            "min_values" => a.min_values(try!(expect_u64(v, &p))),
+            "value_name" => a.value_name(try!(expect_str(v, &p))),
+            "use_delimiter" => a.use_delimiter(try!(expect_bool(v, &p))),
+            "allow_hyphen_values" => a.allow_hyphen_values(try!(expect_bool(v, &p))),
+            "require_delimiter" => a.require_delimiter(try!(expect_bool(v, &p))),
+            "value_delimiter" => a.value_delimiter(try!(expect_str(v, &p))),
+            "display_order" => a.display_order(try!(expect_u64(v, &p)) as usize),
+            "default_value" => a.default_value(try!(expect_str(v, &p))),
+            "value_terminator" => a.value_terminator(try!(expect_str(v, &p))),
+            "last" => a.last(try!(expect_bool(v, &p))),
+            "require_equals" => a.require_equals(try!(expect_bool(v, &p))),
+            "hide_possible_values" => a.hide_possible_values(try!(expect_bool(v, &p))),
+            "hide_default_value" => a.hide_default_value(try!(expect_bool(v, &p))),
+            "value_hint" => {
+                let s = try!(expect_str(v, &p));
+                a.value_hint(try!(s.parse::<ValueHint>().map_err(|_| {
+                    Error::invalid_definition(&p, &format!("unknown ValueHint '{}'", s))
+                })))
+            }
+            "group" | "groups" => {
+                for s in try!(str_or_list(v, &p)) {
+                    a = a.group(s);
+                }
+                a
+            }
+            "alias" | "aliases" => {
+                for s in try!(str_or_list(v, &p)) {
+                    a = a.alias(s);
+                }
+                a
+            }
+            "visible_alias" | "visible_aliases" => {
+                for s in try!(str_or_list(v, &p)) {
+                    a = a.visible_alias(s);
+                }
+                a
+            }
+            "value_names" => {
+                for s in try!(str_or_list(v, &p)) {
+                    a = a.value_name(s);
+                }
+                a
+            }
+            "requires" | "requires_all" => {
+                for s in try!(str_or_list(v, &p)) {
+                    a = a.requires(s);
+                }
+                a
+            }
+            "conflicts_with" | "conflicts_with_all" => {
+                for s in try!(str_or_list(v, &p)) {
+                    a = a.conflicts_with(s);
+                }
+                a
+            }
+            "overrides_with" | "overrides_with_all" => {
+                for s in try!(str_or_list(v, &p)) {
+                    a = a.overrides_with(s);
+                }
+                a
+            }
+            "possible_value" | "possible_values" => {
+                for s in try!(str_or_list(v, &p)) {
+                    a = a.possible_value(s);
+                }
+                a
+            }
+            "possible_value_with_help" | "possible_values_with_help" => {
+                for (val, help) in try!(pairs(v, &p)) {
+                    a = a.possible_value_with_help(val, help);
+                }
+                a
+            }
+            "required_unless" | "required_unless_one" => {
+                for s in try!(str_or_list(v, &p)) {
+                    a = a.required_unless(s);
+                }
+                a
+            }
+            "required_unless_all" => {
+                for s in try!(str_or_list(v, &p)) {
+                    a = a.required_unless(s);
+                }
+                a.setb(ArgSettings::RequiredUnlessAll);
+                a
+            }
+            "required_if" | "required_ifs" => {
+                for (arg, val) in try!(pairs(v, &p)) {
+                    a = a.required_if(arg, val);
+                }
+                a
+            }
+            "requires_if" | "requires_ifs" => {
+                for (val, arg) in try!(pairs(v, &p)) {
+                    a = a.requires_if(val, arg);
+                }
+                a
+            }
+            "default_value_if" | "default_value_ifs" => {
+                for (arg, val, default) in try!(triples(v, &p)) {
+                    a = a.default_value_if(arg, val, default);
+                }
+                a
+            }
+            _ => return Err(Error::invalid_definition(&p, "unknown Arg setting")),
+        };
+    }
+    Ok(a)
+}
+
+fn group_from_entries<'a, D: Document>(name: &'a str,
+                                           entries: Vec<(&'a str, &'a D)>,
+                                           path: &str)
+                                           -> ClapResult<ArgGroup<'a>> {
+    let mut g = ArgGroup::with_name(name);
+    for (k, v) in entries {
+        let p = join(path, k);
+        g = match k {
+            "required" => g.required(try!(expect_bool(v, &p))),
+            "multiple" => g.multiple(try!(expect_bool(v, &p))),
+            "help_heading" => g.help_heading(try!(expect_str(v, &p))),
+            "arg" | "args" => {
+                for s in try!(str_or_list(v, &p)) {
+                    g = g.arg(s);
+                }
+                g
+            }
+            "requires" | "requires_all" => {
+                for s in try!(str_or_list(v, &p)) {
+                    g = g.requires(s);
+                }
+                g
+            }
+            "conflicts_with" | "conflicts_with_all" => {
+                for s in try!(str_or_list(v, &p)) {
+                    g = g.conflicts_with(s);
+                }
+                g
+            }
+            _ => return Err(Error::invalid_definition(&p, "unknown ArgGroup setting")),
+        };
+    }
+    Ok(g)
+}
+
+#[cfg(test)]
+mod test {
+    // Every builder method must be reachable from a data file. When adding one that can't (or
+    // shouldn't) be, list it here instead
+    const NO_KEY: [&'static str; 49] = [// constructors and the loaders themselves
+                                        "new",
+                                        "with_name",
+                                        "with_defaults",
+                                        "from_usage",
+                                        "from_yaml",
+                                        "try_from_yaml",
+                                        "from_json_str",
+                                        "from_toml_str",
+                                        "from_docopt",
+                                        "try_from_docopt",
+                                        // the key of an entry is its name
+                                        "name",
+                                        // only the plural forms hold a list
+                                        "arg",
+                                        "group",
+                                        "subcommand",
+                                        "subcommands",
+                                        "arg_from_usage",
+                                        "args_from_usage",
+                                        // closures and `OsStr`s can't be written in a document
+                                        "validator",
+                                        "validator_os",
+                                        "completer",
+                                        "default_value_os",
+                                        "default_value_if_os",
+                                        "default_value_ifs_os",
+                                        // raw settings, covered by their own methods
+                                        "is_set",
+                                        "set",
+                                        "unset",
+                                        "setb",
+                                        "unsetb",
+                                        // not builder methods
+                                        "get_name",
+                                        "get_bin_name",
+                                        "print_help",
+                                        "print_long_help",
+                                        "write_help",
+                                        "write_long_help",
+                                        "write_version",
+                                        "write_long_version",
+                                        "gen_completions",
+                                        "gen_completions_to",
+                                        "gen_dynamic_completions",
+                                        "gen_dynamic_completions_to",
+                                        "gen_fig_spec",
+                                        "gen_fig_spec_to",
+                                        "gen_manpage_to",
+                                        "gen_manpages",
+                                        "gen_markdown_to",
+                                        "to_json",
+                                        "to_yaml",
+                                        "verify_examples",
+                                        "get_matches"];
+
+    // The names of the `pub fn`s in the first block starting with `header`
+    fn methods(src: &'static str, header: &str) -> Vec<&'static str> {
+        let start = src.find(header).expect("impl block not found");
+        let block = &src[start..];
+        let end = block.find("\n}\n").unwrap_or(block.len());
+        block[..end]
+            .lines()
+            .filter_map(|l| {
+                if l.starts_with("    pub fn ") {
+                    l[11..].split(|c| c == '(' || c == '<').next()
+                } else {
+                    None
+                }
+            })
+            .filter(|m| !m.starts_with("get_matches") && !NO_KEY.contains(m))
+            .collect()
+    }
+
+    // The keys matched by the `"key" | "keys" =>` arms of the loader function `loader`
+    fn keys(loader: &str) -> Vec<&'static str> {
+        let src = include_str!("loader.rs");
+        let start = src.find(&format!("\nfn {}<", loader)).expect("loader function not found");
+        let body = &src[start + 1..];
+        let end = body.find("\n}\n").unwrap_or(body.len());
+        let mut keys = vec![];
+        for l in body[..end].lines() {
+            let l = l.trim_left();
+            if !l.starts_with('"') {
+                continue;
+            }
+            if let Some(arm) = l.find(" =>") {
+                keys.extend(l[..arm].split(" | ").map(|k| k.trim_matches('"')));
+            }
+        }
+        keys
+    }
+
+    fn assert_keys(src: &'static str, header: &str, loader: &str) {
+        let keys = keys(loader);
+        for m in methods(src, header) {
+            assert!(keys.contains(&m),
+                    "the builder method `{}` has no key in `{}`",
+                    m,
+                    loader);
+        }
+    }
+
+    #[test]
+    fn app_parity() {
+        assert_keys(include_str!("mod.rs"),
+                    "impl<'a, 'b> App<'a, 'b> {",
+                    "app_from_entries");
+    }
+
+    #[test]
+    fn arg_parity() {
+        assert_keys(include_str!("../args/arg.rs"),
+                    "impl<'a, 'b> Arg<'a, 'b> {",
+                    "arg_from_entries");
+    }
+
+    #[test]
+    fn setting_parity() {
+        use app::settings::{AppSettings, APP_SETTINGS};
+
+        // The `settings` keys name settings as `to_yaml` and `to_json` write them
+        for &s in APP_SETTINGS.iter() {
+            assert_eq!(format!("{:?}", s).parse::<AppSettings>(),
+                       Ok(s),
+                       "the setting `{:?}` can't be set from a data file",
+                       s);
+        }
+    }
+
+    #[test]
+    fn group_parity() {
+        assert_keys(include_str!("../args/group.rs"),
+                    "impl<'a> ArgGroup<'a> {",
+                    "group_from_entries");
+    }
+}
@@ -0,0 +1,223 @@
+// Std
+use std::io::{self, Write};
+
+// Internal
+use app::parser::Parser;
+use app::settings::AppSettings as AS;
+use app::usage;
+use args::{AnyArg, ArgSettings, DispOrder};
+
+/// `clap` man page writer, which renders a `Parser` as a roff document for section 1 of the
+/// manual.
+pub struct Man<'a> {
+    writer: &'a mut Write,
+    split: bool,
+}
+
+impl<'a> Man<'a> {
+    /// Create a new `Man` instance. When `split` is set, subcommands are expected to have a page
+    /// of their own, which the SUBCOMMANDS section refers to.
+    pub fn new(w: &'a mut Write, split: bool) -> Self { Man { writer: w, split: split } }
+
+    /// Writes the man page of `p`, which must already have its bin names built and its help and
+    /// version flags propagated.
+    pub fn write_page(&mut self, p: &Parser) -> io::Result<()> {
+        debugln!("Man::write_page: name={}", p.meta.name);
+        let name = page_name(p);
+        try!(write!(self.writer,
+                    ".TH {} 1 \"\" \"{}\"\n",
+                    escape(&name.to_uppercase()),
+                    escape(&version_line(p))));
+
+        try!(self.writer.write_all(b".SH NAME\n"));
+        try!(write!(self.writer, "{}", escape(&name)));
+        if let Some(about) = p.meta.about {
+            try!(write!(self.writer, " \\- {}", escape(about.lines().next().unwrap_or(""))));
+        }
+        try!(self.writer.write_all(b"\n"));
+
+        try!(self.writer.write_all(b".SH SYNOPSIS\n"));
+        try!(write!(self.writer,
+                    "{}\n",
+                    escape(&usage::create_usage_no_title(p, &[]))));
+
+        if let Some(about) = p.meta.long_about.or(p.meta.about) {
+            try!(self.writer.write_all(b".SH DESCRIPTION\n"));
+            try!(self.text(about));
+        }
+
+        try!(self.write_options(p));
+        try!(self.write_subcommands(p));
+
+        if !p.meta.examples.is_empty() {
+            try!(self.writer.write_all(b".SH EXAMPLES\n"));
+            for &(cmd, desc) in &p.meta.examples {
+                try!(write!(self.writer, ".TP\n\\fB{}\\fR\n", escape(cmd)));
+                try!(self.text(desc));
+            }
+        }
+
+        if let Some(author) = p.meta.author {
+            try!(self.writer.write_all(b".SH AUTHOR\n"));
+            try!(self.text(author));
+        }
+        if p.meta.version.is_some() || p.meta.long_version.is_some() {
+            try!(self.writer.write_all(b".SH VERSION\n"));
+            try!(self.text(&version_line(p)));
+        }
+        Ok(())
+    }
+
+    fn write_options(&mut self, p: &Parser) -> io::Result<()> {
+        let mut switched: Vec<(usize, &str, &AnyArg)> = vec![];
+        for f in p.flags().filter(|f| !f.is_set(ArgSettings::Hidden)) {
+            switched.push((f.disp_ord(), f.name(), f));
+        }
+        for o in p.opts().filter(|o| !o.is_set(ArgSettings::Hidden)) {
+            switched.push((o.disp_ord(), o.name(), o));
+        }
+        switched.sort_by(|a, b| (a.0, a.1).cmp(&(b.0, b.1)));
+        let positionals: Vec<_> = p.positionals()
+            .filter(|pos| !pos.is_set(ArgSettings::Hidden))
+            .collect();
+        if switched.is_empty() && positionals.is_empty() {
+            return Ok(());
+        }
+
+        try!(self.writer.write_all(b".SH OPTIONS\n"));
+        for &(_, _, a) in &switched {
+            let mut names = vec![];
+            if let Some(s) = a.short() {
+                names.push(format!("\\fB{}\\fR", escape(&format!("-{}", s))));
+            }
+            if let Some(l) = a.long() {
+                names.push(format!("\\fB{}\\fR", escape(&format!("--{}", l))));
+            }
+            try!(write!(self.writer, ".TP\n{}", names.join(", ")));
+            if a.takes_value() {
+                try!(write!(self.writer, "={}", value_names(a)));
+            }
+            try!(self.writer.write_all(b"\n"));
+            try!(self.arg_body(p, a));
+        }
+        for pos in positionals {
+            try!(write!(self.writer, ".TP\n{}\n", value_names(pos)));
+            try!(self.arg_body(p, pos));
+        }
+        Ok(())
+    }
+
+    fn write_subcommands(&mut self, p: &Parser) -> io::Result<()> {
+        let mut subcommands: Vec<_> = p.subcommands()
+            .filter(|sc| !sc.p.is_set(AS::Hidden))
+            .collect();
+        if subcommands.is_empty() {
+            return Ok(());
+        }
+        subcommands.sort_by(|a, b| {
+            (a.p.meta.disp_ord, &a.p.meta.name).cmp(&(b.p.meta.disp_ord, &b.p.meta.name))
+        });
+
+        try!(self.writer.write_all(b".SH SUBCOMMANDS\n"));
+        for sc in subcommands {
+            try!(write!(self.writer, ".TP\n\\fB{}\\fR\n", escape(&sc.p.meta.name)));
+            if let Some(about) = sc.p.meta.about {
+                try!(self.text(about));
+            }
+            if self.split {
+                try!(write!(self.writer,
+                            "See \\fB{}\\fR(1).\n",
+                            escape(&page_name(&sc.p))));
+            }
+        }
+        Ok(())
+    }
+
+    // Writes the help of an argument, followed by its default and possible values
+    fn arg_body<'b, 'c>(&mut self, p: &Parser, a: &AnyArg<'b, 'c>) -> io::Result<()> {
+        if let Some(h) = a.long_help().or_else(|| a.help()) {
+            try!(self.text(h));
+        }
+        let mut spec_vals = vec![];
+        if !a.is_set(ArgSettings::HideDefaultValue) {
+            if let Some(d) = a.default_val() {
+                spec_vals.push(format!("[default: {}]", d.to_string_lossy()));
+            }
+        }
+        if !p.is_set(AS::HidePossibleValuesInHelp) &&
+           !a.is_set(ArgSettings::HidePossibleValues) {
+            if let Some(pv) = a.possible_vals() {
+                spec_vals.push(format!("[possible values: {}]", pv.join(", ")));
+            }
+        }
+        if !spec_vals.is_empty() {
+            try!(self.writer.write_all(b".sp\n"));
+            try!(self.text(&spec_vals.join(" ")));
+        }
+        Ok(())
+    }
+
+    // Writes free form text, turning blank lines into paragraph breaks. Indented lines (i.e.
+    // example commands) are kept as they are, without filling
+    fn text(&mut self, s: &str) -> io::Result<()> {
+        let mut nofill = false;
+        for line in s.trim_right().lines().skip_while(|l| l.trim().is_empty()) {
+            let line = line.trim_right();
+            let indented = line.starts_with(|c: char| c.is_whitespace());
+            if nofill && !indented {
+                try!(self.writer.write_all(b".fi\n"));
+                nofill = false;
+            }
+            if line.is_empty() {
+                try!(self.writer.write_all(b".sp\n"));
+            } else {
+                if indented && !nofill {
+                    try!(self.writer.write_all(b".nf\n"));
+                    nofill = true;
+                }
+                try!(write!(self.writer, "{}\n", escape(line)));
+            }
+        }
+        if nofill {
+            try!(self.writer.write_all(b".fi\n"));
+        }
+        Ok(())
+    }
+}
+
+// The name of the page of `p`, which for subcommands is the bin name joined with dashes
+// (i.e. `git-remote-add`)
+pub fn page_name(p: &Parser) -> String {
+    p.meta.bin_name.as_ref().unwrap_or(&p.meta.name).replace(' ', "-")
+}
+
+fn version_line(p: &Parser) -> String {
+    let name = p.meta.bin_name.as_ref().unwrap_or(&p.meta.name);
+    match p.meta.long_version.or(p.meta.version) {
+        Some(v) => format!("{} {}", name, v),
+        None => name.clone(),
+    }
+}
+
+fn value_names<'b, 'c>(a: &AnyArg<'b, 'c>) -> String {
+    let names = match a.val_names() {
+        Some(names) => names.values().map(|n| format!("\\fI<{}>\\fR", escape(n))).collect(),
+        None => vec![format!("\\fI<{}>\\fR", escape(a.name()))],
+    };
+    let mut ret = names.join(" ");
+    if a.is_set(ArgSettings::Multiple) {
+        ret.push_str("...");
+    }
+    ret
+}
+
+// Escapes text for roff, so that backslashes and dashes are printed as is, and lines starting
+// with a control character aren't taken as requests
+fn escape(s: &str) -> String {
+    let s = s.replace('\\', "\\e").replace('-', "\\-");
+    if s.starts_with('.') || s.starts_with('\'') {
+        format!("\\&{}", s)
+    } else {
+        s
+    }
+}
@@ -0,0 +1,190 @@
+// Std
+use std::cmp;
+use std::io::{self, Write};
+
+// Internal
+use app::parser::Parser;
+use app::settings::AppSettings as AS;
+use app::usage;
+use args::{AnyArg, ArgSettings, DispOrder};
+use completions;
+
+/// `clap` Markdown writer, which renders a reference for a whole command tree with one section
+/// per subcommand path.
+pub struct Markdown<'a> {
+    writer: &'a mut Write,
+}
+
+impl<'a> Markdown<'a> {
+    /// Create a new `Markdown` instance.
+    pub fn new(w: &'a mut Write) -> Self { Markdown { writer: w } }
+
+    /// Writes the section of `p`, followed by those of its (visible) subcommands. `p` must already
+    /// have its bin names built and its help and version flags propagated.
+    pub fn write_reference(&mut self, p: &Parser) -> io::Result<()> {
+        try!(self.write_section(p, 1));
+
+        let mut paths = completions::get_all_subcommand_paths(p, true);
+        paths.sort();
+        paths.dedup();
+        // Sorting by the display order and name of each subcommand along the path puts every
+        // section right after its parent's, in the order of the parent's table
+        let mut sections = vec![];
+        'paths: for path in paths {
+            let mut sc = p;
+            let mut key = vec![];
+            for name in path.split("__").skip(1) {
+                sc = match sc.subcommands.iter().find(|s| s.p.meta.name == name) {
+                    Some(s) if !s.p.is_set(AS::Hidden) => &s.p,
+                    // The path of an alias, or of a hidden subcommand
+                    _ => continue 'paths,
+                };
+                key.push((sc.meta.disp_ord, &*sc.meta.name));
+            }
+            sections.push((key, sc));
+        }
+        sections.sort_by(|a, b| a.0.cmp(&b.0));
+        for (key, sc) in sections {
+            try!(self.write_section(sc, key.len() + 1));
+        }
+        Ok(())
+    }
+
+    fn write_section(&mut self, p: &Parser, depth: usize) -> io::Result<()> {
+        let name = p.meta.bin_name.as_ref().unwrap_or(&p.meta.name);
+        debugln!("Markdown::write_section: name={}", name);
+        if depth > 1 {
+            try!(self.writer.write_all(b"\n"));
+        }
+        for _ in 0..cmp::min(depth, 6) {
+            try!(self.writer.write_all(b"#"));
+        }
+        try!(write!(self.writer, " {}\n", name));
+
+        if let Some(about) = p.meta.long_about.or(p.meta.about) {
+            try!(write!(self.writer, "\n{}\n", about.trim()));
+        }
+        try!(write!(self.writer,
+                    "\n```text\n{}\n```\n",
+                    usage::create_usage_no_title(p, &[])));
+
+        if let Some(ref aliases) = p.meta.aliases {
+            let vis: Vec<_> = aliases.iter()
+                .filter(|&&(_, vis)| vis)
+                .map(|&(n, _)| format!("`{}`", n))
+                .collect();
+            if !vis.is_empty() {
+                try!(write!(self.writer, "\n**Aliases:** {}\n", vis.join(", ")));
+            }
+        }
+
+        try!(self.write_args(p));
+
+        if !p.meta.examples.is_empty() {
+            try!(self.writer.write_all(b"\n**Examples:**\n\n```text\n"));
+            for (i, &(cmd, desc)) in p.meta.examples.iter().enumerate() {
+                if i > 0 {
+                    try!(self.writer.write_all(b"\n"));
+                }
+                for line in desc.trim().lines() {
+                    try!(write!(self.writer, "# {}\n", line.trim()));
+                }
+                try!(write!(self.writer, "{}\n", cmd));
+            }
+            try!(self.writer.write_all(b"```\n"));
+        }
+
+        let subcommands = visible_subcommands(p);
+        if !subcommands.is_empty() {
+            try!(self.writer.write_all(b"\n| Subcommand | Description |\n|---|---|\n"));
+            for sc in &subcommands {
+                try!(write!(self.writer,
+                            "| `{}` | {} |\n",
+                            sc.meta.name,
+                            cell(sc.meta.about.unwrap_or(""))));
+            }
+        }
+        Ok(())
+    }
+
+    fn write_args(&mut self, p: &Parser) -> io::Result<()> {
+        let mut switched: Vec<(usize, &str, &AnyArg)> = vec![];
+        for f in p.flags().filter(|f| !f.is_set(ArgSettings::Hidden)) {
+            switched.push((f.disp_ord(), f.name(), f));
+        }
+        for o in p.opts().filter(|o| !o.is_set(ArgSettings::Hidden)) {
+            switched.push((o.disp_ord(), o.name(), o));
+        }
+        switched.sort_by(|a, b| (a.0, a.1).cmp(&(b.0, b.1)));
+        let mut args: Vec<&AnyArg> = switched.into_iter().map(|(_, _, a)| a).collect();
+        for pos in p.positionals().filter(|pos| !pos.is_set(ArgSettings::Hidden)) {
+            args.push(pos);
+        }
+        if args.is_empty() {
+            return Ok(());
+        }
+
+        try!(self.writer.write_all(b"\n| Argument | Value | Default | Possible values | Description |\n\
+                                     |---|---|---|---|---|\n"));
+        for a in args {
+            try!(write!(self.writer,
+                        "| {} | {} | {} | {} | {} |\n",
+                        switches(a),
+                        value_names(a),
+                        a.default_val().map_or(String::new(),
+                                               |d| format!("`{}`", d.to_string_lossy())),
+                        a.possible_vals().map_or(String::new(), |pv| {
+                            pv.iter().map(|v| format!("`{}`", v)).collect::<Vec<_>>().join(", ")
+                        }),
+                        cell(a.help().or_else(|| a.long_help()).unwrap_or(""))));
+        }
+        Ok(())
+    }
+}
+
+fn visible_subcommands<'a, 'b>(p: &'b Parser<'a, 'b>) -> Vec<&'b Parser<'a, 'b>> {
+    let mut ret: Vec<_> = p.subcommands
+        .iter()
+        .filter(|sc| !sc.p.is_set(AS::Hidden))
+        .map(|sc| &sc.p)
+        .collect();
+    ret.sort_by(|a, b| (a.meta.disp_ord, &a.meta.name).cmp(&(b.meta.disp_ord, &b.meta.name)));
+    ret
+}
+
+// The short, long and visible aliases of a flag or option, or the name of a positional argument
+// (whose value it describes)
+fn switches<'b, 'c>(a: &AnyArg<'b, 'c>) -> String {
+    if !a.has_switch() {
+        return format!("`<{}>{}`",
+                       a.name(),
+                       if a.is_set(ArgSettings::Multiple) { "..." } else { "" });
+    }
+    let mut ret = vec![];
+    if let Some(s) = a.short() {
+        ret.push(format!("`-{}`", s));
+    }
+    if let Some(l) = a.long() {
+        ret.push(format!("`--{}`", l));
+    }
+    if let Some(aliases) = a.aliases() {
+        ret.extend(aliases.into_iter().map(|n| format!("`--{}`", n)));
+    }
+    ret.join(", ")
+}
+
+fn value_names<'b, 'c>(a: &AnyArg<'b, 'c>) -> String {
+    if !a.has_switch() || !a.takes_value() {
+        return String::new();
+    }
+    let names = match a.val_names() {
+        Some(names) => names.values().map(|n| format!("<{}>", n)).collect::<Vec<_>>().join(" "),
+        None => format!("<{}>", a.name()),
+    };
+    format!("`{}{}`",
+            names,
+            if a.is_set(ArgSettings::Multiple) { "..." } else { "" })
+}
+
+// Makes free form text safe for use in a table cell
+fn cell(s: &str) -> String { s.trim().replace('|', "\\|").replace('\n', "<br>") }
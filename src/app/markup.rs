@@ -0,0 +1,226 @@
+// Renders the markup enabled by `AppSettings::HelpMarkup`: `**bold**`, `` `code` ``, bullets
+// starting with `- ` or `* `, and paragraphs separated by blank lines. The text is wrapped before
+// any style is applied, so the width is only ever computed on what the user will see
+
+// Internal
+use app::help::str_width;
+use fmt::Colorizer;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Style {
+    Plain,
+    Bold,
+    Code,
+}
+
+#[derive(Debug, PartialEq)]
+enum Block {
+    Paragraph(String),
+    Bullet(String),
+    Blank,
+}
+
+// A word is made of the styled pieces between two spaces, i.e. `**bold**,` is a bold `bold`
+// followed by a plain `,`
+type Word = Vec<(String, Style)>;
+
+/// Renders `text` wrapped at `width` columns, styling it with `cizer` if colors are on. `tail` is
+/// appended to the last paragraph or bullet as it is, without looking for any markup in it.
+pub fn render(text: &str, tail: &str, width: usize, cizer: Option<&Colorizer>) -> String {
+    debugln!("markup::render: width={}", width);
+    let tail = words(&[(tail.to_owned(), Style::Plain)]);
+    let blocks = blocks(text);
+    if blocks.is_empty() {
+        return wrap(&tail, width, "", "", cizer).join("\n");
+    }
+    let last = blocks.len() - 1;
+    let mut lines = vec![];
+    for (i, block) in blocks.into_iter().enumerate() {
+        let (text, first, rest) = match block {
+            Block::Paragraph(p) => (p, "", ""),
+            Block::Bullet(b) => (b, "- ", "  "),
+            Block::Blank => {
+                lines.push(String::new());
+                continue;
+            }
+        };
+        let mut words = words(&inline(&text));
+        if i == last {
+            words.extend(tail.iter().cloned());
+        }
+        lines.extend(wrap(&words, width, first, rest, cizer));
+    }
+    lines.join("\n")
+}
+
+fn blocks(text: &str) -> Vec<Block> {
+    let mut blocks = vec![];
+    for line in text.lines() {
+        let trimmed = line.trim_left();
+        if trimmed.is_empty() {
+            match blocks.last() {
+                None | Some(&Block::Blank) => (),
+                _ => blocks.push(Block::Blank),
+            }
+        } else if trimmed.starts_with("- ") || trimmed.starts_with("* ") {
+            blocks.push(Block::Bullet(trimmed[2..].trim().to_owned()));
+        } else {
+            // Indented lines continue a bullet, others continue a paragraph
+            match blocks.last_mut() {
+                Some(&mut Block::Bullet(ref mut b)) if trimmed.len() < line.len() => {
+                    b.push(' ');
+                    b.push_str(trimmed.trim_right());
+                    continue;
+                }
+                Some(&mut Block::Paragraph(ref mut p)) => {
+                    p.push(' ');
+                    p.push_str(trimmed.trim_right());
+                    continue;
+                }
+                _ => (),
+            }
+            blocks.push(Block::Paragraph(trimmed.trim_right().to_owned()));
+        }
+    }
+    if blocks.last() == Some(&Block::Blank) {
+        blocks.pop();
+    }
+    blocks
+}
+
+// Splits text into its styled pieces. Markers without a closing one are kept as is
+fn inline(s: &str) -> Vec<(String, Style)> {
+    let mut pieces = vec![];
+    let mut plain = String::new();
+    let mut rest = s;
+    while let Some(c) = rest.chars().next() {
+        let styled = if rest.starts_with("**") {
+            rest[2..].find("**").map(|end| (Style::Bold, 2, end))
+        } else if c == '`' {
+            rest[1..].find('`').map(|end| (Style::Code, 1, end))
+        } else {
+            None
+        };
+        if let Some((style, marker, end)) = styled {
+            if !plain.is_empty() {
+                pieces.push((plain, Style::Plain));
+                plain = String::new();
+            }
+            pieces.push((rest[marker..marker + end].to_owned(), style));
+            rest = &rest[end + 2 * marker..];
+        } else {
+            plain.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    if !plain.is_empty() {
+        pieces.push((plain, Style::Plain));
+    }
+    pieces
+}
+
+fn words(pieces: &[(String, Style)]) -> Vec<Word> {
+    let mut words = vec![];
+    let mut word = vec![];
+    for &(ref text, style) in pieces {
+        let mut piece = String::new();
+        for c in text.chars() {
+            if !c.is_whitespace() {
+                piece.push(c);
+                continue;
+            }
+            if !piece.is_empty() {
+                word.push((piece, style));
+                piece = String::new();
+            }
+            if !word.is_empty() {
+                words.push(word);
+                word = vec![];
+            }
+        }
+        if !piece.is_empty() {
+            word.push((piece, style));
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+// Fills lines of at most `width` columns with `words`, the first one starting with `first` and
+// the others with `rest`. A word longer than a line gets a line of its own
+fn wrap(words: &[Word],
+        width: usize,
+        first: &str,
+        rest: &str,
+        cizer: Option<&Colorizer>)
+        -> Vec<String> {
+    let mut lines = vec![];
+    let mut line = first.to_owned();
+    let mut line_w = str_width(first);
+    let mut empty = true;
+    for word in words {
+        let word_w = word.iter().map(|&(ref s, _)| str_width(&**s)).sum::<usize>();
+        if !empty && line_w + 1 + word_w > width {
+            lines.push(line);
+            line = rest.to_owned();
+            line_w = str_width(rest);
+            empty = true;
+        }
+        if !empty {
+            line.push(' ');
+            line_w += 1;
+        }
+        for &(ref s, style) in word {
+            line.push_str(&*styled(s, style, cizer));
+        }
+        line_w += word_w;
+        empty = false;
+    }
+    lines.push(line);
+    lines
+}
+
+fn styled(s: &str, style: Style, cizer: Option<&Colorizer>) -> String {
+    match (style, cizer) {
+        (Style::Bold, Some(c)) => c.bold(s).to_string(),
+        (Style::Code, Some(c)) => c.literal(s).to_string(),
+        _ => s.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::render;
+
+    #[test]
+    fn strips_markers() {
+        assert_eq!(render("Use **all** of `--foo`, or `*` and **", "", 80, None),
+                   "Use all of --foo, or * and **");
+    }
+
+    #[test]
+    fn paragraphs_and_bullets() {
+        assert_eq!(render("First\nparagraph.\n\n\n- one\n* two\n  continued\nafter", "", 80, None),
+                   "First paragraph.\n\n- one\n- two continued\nafter");
+    }
+
+    #[test]
+    fn wraps_bullets_with_hanging_indent() {
+        assert_eq!(render("- **aaa** bbb ccc `ddd`", "", 10, None), "- aaa bbb\n  ccc ddd");
+    }
+
+    #[test]
+    fn appends_tail_as_is() {
+        assert_eq!(render("**one**\n\n- two", " [default: **x**]", 80, None),
+                   "one\n\n- two [default: **x**]");
+        assert_eq!(render("", " [default: `x`]", 80, None), "[default: `x`]");
+    }
+
+    #[test]
+    fn colored_tail_width() {
+        let tail = " [default: \x1b[32mabc\x1b[0m]";
+        assert_eq!(render("one two", tail, 22, None), format!("one two{}", tail));
+    }
+}
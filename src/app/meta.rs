@@ -1,3 +1,6 @@
+// Internal
+use fmt::Theme;
+
 #[doc(hidden)]
 #[allow(missing_debug_implementations)]
 #[derive(Default, Clone)]
@@ -13,12 +16,16 @@ pub struct AppMeta<'b> {
     pub pre_help: Option<&'b str>,
     pub aliases: Option<Vec<(&'b str, bool)>>, // (name, visible)
     pub usage_str: Option<&'b str>,
+    pub usages: Vec<&'b str>,
     pub usage: Option<String>,
     pub help_str: Option<&'b str>,
     pub disp_ord: usize,
     pub term_w: Option<usize>,
     pub max_w: Option<usize>,
     pub template: Option<&'b str>,
+    pub examples: Vec<(&'b str, &'b str)>, // (command, description)
+    pub theme: Theme,
+    pub deprecated: Option<&'b str>,
 }
 
 impl<'b> AppMeta<'b> {
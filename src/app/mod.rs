@@ -4,8 +4,21 @@ mod macros;
 pub mod parser;
 mod meta;
 mod help;
+mod markup;
+mod examples;
+pub mod pager;
+mod search;
+mod man;
+mod markdown;
+#[cfg(feature = "serde")]
+mod json;
+#[cfg(any(feature = "yaml", feature = "serde", feature = "toml"))]
+pub mod loader;
+#[cfg(feature = "yaml")]
+mod yaml;
 mod validator;
 mod usage;
+mod docopt;
 
 // Std
 use std::env;
@@ -25,10 +38,14 @@ use yaml_rust::Yaml;
 // Internal
 use app::help::Help;
 use app::parser::Parser;
-use args::{AnyArg, Arg, ArgGroup, ArgMatcher, ArgMatches, ArgSettings};
+use args::{AnyArg, Arg, ArgGroup, ArgMatcher, ArgMatches, ArgSettings, ValueHint};
 use errors::Result as ClapResult;
+#[cfg(any(feature = "serde", feature = "toml"))]
+use errors::Error;
 pub use self::settings::AppSettings;
 use completions::Shell;
+use completions::dynamic::COMPLETE_SC;
+use fmt::Theme;
 
 /// Used to create a representation of a command line program and all possible command line
 /// arguments. Application settings are set using the "builder pattern" with the
@@ -128,6 +145,8 @@ impl<'a, 'b> App<'a, 'b> {
     /// ensure this doesn't happen is to run your program with the `--help` switch. If this passes
     /// without error, you needn't worry because the YAML is properly formatted.
     ///
+    /// Unknown keys of an `App` are ignored, use [`App::try_from_yaml`] to reject them.
+    ///
     /// # Examples
     ///
     /// The following example shows how to load a properly formatted YAML file to build an instance
@@ -145,12 +164,223 @@ impl<'a, 'b> App<'a, 'b> {
     /// # }
     /// ```
     /// [`App`]: ./struct.App.html
+    /// [`App::try_from_yaml`]: ./struct.App.html#method.try_from_yaml
     /// [`examples/17_yaml.rs`]: https://github.com/kbknapp/clap-rs/blob/master/examples/17_yaml.rs
     /// [`examples/17_yaml.yml`]: https://github.com/kbknapp/clap-rs/blob/master/examples/17_yaml.yml
     /// [`panic!`]: https://doc.rust-lang.org/std/macro.panic!.html
     #[cfg(feature = "yaml")]
     pub fn from_yaml(yaml: &'a Yaml) -> App<'a, 'a> { App::from(yaml) }
 
+    /// Like [`App::from_yaml`], but returns an [`Error`] instead of panicking when the YAML isn't
+    /// a valid definition, which includes having unknown keys. The error's [`ErrorKind`] is
+    /// [`ErrorKind::InvalidDefinition`], and its [`Error::info`] holds the path of the offending
+    /// key and what's wrong with it.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// # #[macro_use]
+    /// # extern crate clap;
+    /// # use clap::{App, ErrorKind};
+    /// # fn main() {
+    /// let yml = load_yaml!("app.yml");
+    /// match App::try_from_yaml(yml) {
+    ///     Ok(app) => { /* continued logic goes here */ }
+    ///     // i.e. "subcommands[2].args.verbose.multiple" and "expected bool"
+    ///     Err(e) => println!("{:?}", e.info),
+    /// }
+    /// # }
+    /// ```
+    /// [`App::from_yaml`]: ./struct.App.html#method.from_yaml
+    /// [`Error`]: ./struct.Error.html
+    /// [`ErrorKind`]: ./enum.ErrorKind.html
+    /// [`ErrorKind::InvalidDefinition`]: ./enum.ErrorKind.html#variant.InvalidDefinition
+    /// [`Error::info`]: ./struct.Error.html#structfield.info
+    #[cfg(feature = "yaml")]
+    pub fn try_from_yaml(yaml: &'a Yaml) -> ClapResult<App<'a, 'a>> {
+        loader::app_from_document(yaml, true)
+    }
+
+    /// Creates a fully defined `App` from the text of a JSON document, using the same keys as a
+    /// YAML definition (see [`App::from_yaml`]). Arguments, groups and subcommands may be written
+    /// either as `{"verbose": {...}}` or as `{"name": "verbose", ...}`.
+    ///
+    /// **NOTE:** The parsed document is kept for the rest of the program, since the `App` borrows
+    /// its strings from it. This is meant to be called once, when the program starts.
+    ///
+    /// **NOTE:** This requires the `serde` cargo feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`ErrorKind::InvalidDefinition`] error if the text isn't valid JSON, or if the
+    /// document isn't a valid definition, in which case [`Error::info`] holds the path of the
+    /// offending key and what's wrong with it. Unlike [`App::from_yaml`], unknown keys are errors
+    /// too.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::App;
+    /// let app = App::from_json_str(r#"{
+    ///     "name": "myprog",
+    ///     "args": [
+    ///         { "verbose": { "short": "v", "multiple": true } }
+    ///     ]
+    /// }"#).unwrap();
+    ///
+    /// let m = app.get_matches_from(vec!["myprog", "-vv"]);
+    /// assert_eq!(m.occurrences_of("verbose"), 2);
+    /// ```
+    /// [`App::from_yaml`]: ./struct.App.html#method.from_yaml
+    /// [`ErrorKind::InvalidDefinition`]: ./enum.ErrorKind.html#variant.InvalidDefinition
+    /// [`Error::info`]: ./struct.Error.html#structfield.info
+    #[cfg(feature = "serde")]
+    pub fn from_json_str(json: &str) -> ClapResult<App<'static, 'static>> {
+        let doc: ::serde_json::Value = match ::serde_json::from_str(json) {
+            Ok(doc) => doc,
+            Err(e) => return Err(Error::invalid_definition("", &*e.to_string())),
+        };
+        loader::app_from_document(loader::keep(doc), true)
+    }
+
+    /// Creates a fully defined `App` from the text of a TOML document, using the same keys as a
+    /// YAML definition (see [`App::from_yaml`]). Since TOML has no null, the conditions of
+    /// `default_value_if` which apply whenever the other argument is present are written as
+    /// `[arg, default]`.
+    ///
+    /// **NOTE:** The parsed document is kept for the rest of the program, since the `App` borrows
+    /// its strings from it. This is meant to be called once, when the program starts.
+    ///
+    /// **NOTE:** This requires the `toml` cargo feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`ErrorKind::InvalidDefinition`] error if the text isn't valid TOML, or if the
+    /// document isn't a valid definition, in which case [`Error::info`] holds the path of the
+    /// offending key and what's wrong with it. Unlike [`App::from_yaml`], unknown keys are errors
+    /// too.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::App;
+    /// let app = App::from_toml_str(r#"
+    ///     name = "myprog"
+    ///
+    ///     [[args]]
+    ///     name = "config"
+    ///     short = "c"
+    ///     takes_value = true
+    /// "#).unwrap();
+    ///
+    /// let m = app.get_matches_from(vec!["myprog", "-c", "file.toml"]);
+    /// assert_eq!(m.value_of("config"), Some("file.toml"));
+    /// ```
+    /// [`App::from_yaml`]: ./struct.App.html#method.from_yaml
+    /// [`ErrorKind::InvalidDefinition`]: ./enum.ErrorKind.html#variant.InvalidDefinition
+    /// [`Error::info`]: ./struct.Error.html#structfield.info
+    #[cfg(feature = "toml")]
+    pub fn from_toml_str(toml: &str) -> ClapResult<App<'static, 'static>> {
+        let doc: ::toml::Value = match toml.parse() {
+            Ok(doc) => doc,
+            Err(e) => return Err(Error::invalid_definition("", &*e.to_string())),
+        };
+        loader::app_from_document(loader::keep(doc), true)
+    }
+
+    /// Creates an `App` from a [docopt] usage document, so the help text of a docopt program can
+    /// remain the single source of truth for its arguments.
+    ///
+    /// The program name is the first word of the `Usage:` section, and anything before that
+    /// section becomes the [`App::about`] text. Every usage pattern is read as follows:
+    ///
+    ///  * Plain words are commands, which become (nested) [`SubCommand`]s
+    ///  * `<value>` or `VALUE` words are positional arguments, numbered in order of appearance
+    ///  * `-s` and `--long` are options, described by the lines starting with `-` in the sections
+    ///    whose header ends with `options:`, using the [`Arg::from_usage`] syntax and `[default:
+    ///    value]` in their description
+    ///  * `[options]` stands for all the options the pattern doesn't mention
+    ///  * `(a|b)` and `[a|b]` of single arguments become an [`ArgGroup`] named after their source
+    ///    text, required in the first case
+    ///  * `...` makes the previous argument [multiple]
+    ///  * `[--]` sets [`AppSettings::TrailingVarArg`], and `-` is a positional argument named `-`
+    ///
+    /// An argument is required when it's required in all the patterns of its command, or unless
+    /// the required arguments of the patterns it isn't part of are used. It conflicts with the
+    /// arguments it never appears with in the same pattern. When every pattern goes on to a
+    /// command, [`AppSettings::SubcommandRequiredElseHelp`] is set.
+    ///
+    /// `-h`, `--help` and `--version` are left to `clap`, so use [`App::version`] to set the
+    /// version. Only the first line of an option's description is used as its help.
+    ///
+    /// # Panics
+    ///
+    /// If the document has no `Usage:` section or usage patterns, or their brackets don't match.
+    /// See [`App::try_from_docopt`] to handle those as an [`Error`] instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::App;
+    /// let app = App::from_docopt("Naval Fate.
+    ///
+    /// Usage:
+    ///   naval_fate ship new <name>...
+    ///   naval_fate ship <name> move <x> <y> [--speed=<kn>]
+    ///   naval_fate mine (set|remove) <x> <y> [--moored|--drifting]
+    ///   naval_fate -h | --help
+    ///
+    /// Options:
+    ///   -h --help     Show this screen.
+    ///   --speed=<kn>  Speed in knots [default: 10].
+    ///   --moored      Moored (anchored) mine.
+    ///   --drifting    Drifting mine.
+    /// ");
+    ///
+    /// let m = app.get_matches_from(vec!["naval_fate", "ship", "Guardian", "move", "10", "50"]);
+    /// let ship = m.subcommand_matches("ship").unwrap();
+    /// assert_eq!(ship.value_of("name"), Some("Guardian"));
+    /// assert_eq!(ship.subcommand_matches("move").unwrap().value_of("speed"), Some("10"));
+    /// ```
+    /// [docopt]: http://docopt.org/
+    /// [`App::try_from_docopt`]: ./struct.App.html#method.try_from_docopt
+    /// [`Error`]: ./struct.Error.html
+    /// [`App::about`]: ./struct.App.html#method.about
+    /// [`App::version`]: ./struct.App.html#method.version
+    /// [`SubCommand`]: ./struct.SubCommand.html
+    /// [`Arg::from_usage`]: ./struct.Arg.html#method.from_usage
+    /// [`ArgGroup`]: ./struct.ArgGroup.html
+    /// [multiple]: ./struct.Arg.html#method.multiple
+    /// [`AppSettings::TrailingVarArg`]: ./enum.AppSettings.html#variant.TrailingVarArg
+    /// [`AppSettings::SubcommandRequiredElseHelp`]: ./enum.AppSettings.html#variant.SubcommandRequiredElseHelp
+    pub fn from_docopt(doc: &'a str) -> App<'a, 'a> {
+        match docopt::app_from_docopt(doc) {
+            Ok(app) => app,
+            Err(e) => panic!("{}", e.message),
+        }
+    }
+
+    /// Like [`App::from_docopt`], but returns an [`Error`] instead of panicking when the document
+    /// can't be read. The error's [`ErrorKind`] is [`ErrorKind::InvalidDefinition`], and its
+    /// [`Error::info`] holds the offending usage pattern and what's wrong with it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, ErrorKind};
+    /// let err = App::try_from_docopt("Usage:\n  prog (--fast | --slow").err().unwrap();
+    /// assert_eq!(err.kind, ErrorKind::InvalidDefinition);
+    /// assert_eq!(err.info.unwrap()[1], "unclosed '('");
+    /// ```
+    /// [`App::from_docopt`]: ./struct.App.html#method.from_docopt
+    /// [`Error`]: ./struct.Error.html
+    /// [`ErrorKind`]: ./enum.ErrorKind.html
+    /// [`ErrorKind::InvalidDefinition`]: ./enum.ErrorKind.html#variant.InvalidDefinition
+    /// [`Error::info`]: ./struct.Error.html#structfield.info
+    pub fn try_from_docopt(doc: &'a str) -> ClapResult<App<'a, 'a>> {
+        docopt::app_from_docopt(doc)
+    }
+
     /// Sets a string of author(s) that will be displayed to the user when they
     /// request the help information with `--help` or `-h`.
     ///
@@ -309,6 +539,35 @@ impl<'a, 'b> App<'a, 'b> {
         self
     }
 
+    /// Adds an example of how to invoke the program, which is displayed along with its
+    /// description in an `EXAMPLES` section of the help message, the man page and the Markdown
+    /// reference. Examples are displayed in the order they were added.
+    ///
+    /// The command is a full command line, starting with the name of the binary, even for the
+    /// examples of a [`SubCommand`]. This lets [`App::verify_examples`] check that each of them
+    /// parses, so they don't go stale.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, SubCommand};
+    /// let res = App::new("tool")
+    ///     .subcommand(SubCommand::with_name("deploy")
+    ///         .arg_from_usage("--env <env> 'The environment to deploy to'")
+    ///         .arg_from_usage("<config> 'The config of the app'")
+    ///         .example("tool deploy --env prod app.toml", "Deploy the app to production"))
+    ///     .get_matches_from_safe(vec!["tool", "deploy", "--help"]);
+    ///
+    /// let help = res.unwrap_err().message;
+    /// assert!(help.contains("EXAMPLES:\n    tool deploy --env prod app.toml\n        Deploy the app"));
+    /// ```
+    /// [`SubCommand`]: ./struct.SubCommand.html
+    /// [`App::verify_examples`]: ./struct.App.html#method.verify_examples
+    pub fn example<S: Into<&'b str>>(mut self, cmd: S, desc: S) -> Self {
+        self.p.meta.examples.push((cmd.into(), desc.into()));
+        self
+    }
+
     /// Sets a string of the version number to be displayed when displaying version or help
     /// information with `-V`. 
     ///
@@ -395,6 +654,34 @@ impl<'a, 'b> App<'a, 'b> {
         self
     }
 
+    /// Sets several usage strings to override the auto-generated usage string, one per line, for
+    /// the alternative ways of invoking the program. Like with [`App::usage`], each one starts
+    /// with the binary name.
+    ///
+    /// **CAUTION:** Using this setting disables `clap`s "context-aware" usage strings. After this
+    /// setting is set, these will be the only usage strings displayed to the user!
+    ///
+    /// **NOTE:** [`AppSettings::DeriveUsages`] derives such lines from the conflicting arguments
+    /// instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let res = App::new("tool")
+    ///     .usages(&["tool --list", "tool [OPTIONS] <FILE>..."])
+    ///     .get_matches_from_safe(vec!["tool", "--help"]);
+    ///
+    /// let help = res.unwrap_err().message;
+    /// assert!(help.contains("USAGE:\n    tool --list\n    tool [OPTIONS] <FILE>...\n"));
+    /// ```
+    /// [`App::usage`]: ./struct.App.html#method.usage
+    /// [`AppSettings::DeriveUsages`]: ./enum.AppSettings.html#variant.DeriveUsages
+    pub fn usages(mut self, usages: &[&'b str]) -> Self {
+        self.p.meta.usages.extend_from_slice(usages);
+        self
+    }
+
     /// Sets a custom help message and overrides the auto-generated one. This should only be used
     /// when the auto-generated message does not suffice.
     ///
@@ -544,6 +831,19 @@ impl<'a, 'b> App<'a, 'b> {
     ///   * `{subcommands}` - Help for subcommands.
     ///   * `{after-help}`  - Help from [`App::after_help`]
     ///   * `{before-help}`  - Help from [`App::before_help`]
+    ///   * `{long-about}`  - Long description (from [`App::long_about`], or [`App::about`] when
+    ///                       there is none)
+    ///   * `{examples}`    - Examples from [`App::example`]
+    ///   * `{heading:H}`   - Help for the arguments under the [`Arg::help_heading`] `H`. Such
+    ///                       arguments are left out of `{unified}`, `{flags}`, `{options}` and
+    ///                       `{positionals}`.
+    ///
+    /// Text between `{?tag}` and `{/tag}`, where `tag` is one of the above, is only written when
+    /// that tag has something to show, e.g. when there are visible subcommands for
+    /// `{?subcommands}`. Such blocks may be nested, and keep the headers of empty sections out of
+    /// the help message.
+    ///
+    /// To use the template for the subcommands as well, see [`AppSettings::GlobalTemplate`].
     ///
     /// # Examples
     ///
@@ -554,16 +854,85 @@ impl<'a, 'b> App<'a, 'b> {
     ///     .template("{bin} ({version}) - {usage}")
     /// # ;
     /// ```
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg};
+    /// App::new("myprog")
+    ///     .template("{bin}\n\n{usage}{?subcommands}\n\nCOMMANDS:\n{subcommands}{/subcommands}")
+    /// # ;
+    /// ```
     /// **NOTE:**The template system is, on purpose, very simple. Therefore the tags have to writen
     /// in the lowercase and without spacing.
     /// [`App::about`]: ./struct.App.html#method.about
+    /// [`App::long_about`]: ./struct.App.html#method.long_about
     /// [`App::after_help`]: ./struct.App.html#method.after_help
     /// [`App::before_help`]: ./struct.App.html#method.before_help
+    /// [`App::example`]: ./struct.App.html#method.example
+    /// [`Arg::help_heading`]: ./struct.Arg.html#method.help_heading
+    /// [`AppSettings::GlobalTemplate`]: ./enum.AppSettings.html#variant.GlobalTemplate
     pub fn template<S: Into<&'b str>>(mut self, s: S) -> Self {
         self.p.meta.template = Some(s.into());
         self
     }
 
+    /// Sets the styles of the help and error messages, when they are colored (see
+    /// [`AppSettings::ColoredHelp`] and [`AppSettings::ColorAuto`]). The theme applies to the
+    /// subcommands as well. By default the headers are yellow, the error labels bold red and the
+    /// names and values green or yellow.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use clap::{App, AppSettings, Color, Style, Theme};
+    /// App::new("myprog")
+    ///     .setting(AppSettings::ColoredHelp)
+    ///     .theme(Theme {
+    ///         header: Style::new().fg(Color::Blue).bold(),
+    ///         placeholder: Style::new().fg(Color::Fixed(244)).underline(),
+    ///         ..Theme::default()
+    ///     })
+    /// # ;
+    /// ```
+    /// [`AppSettings::ColoredHelp`]: ./enum.AppSettings.html#variant.ColoredHelp
+    /// [`AppSettings::ColorAuto`]: ./enum.AppSettings.html#variant.ColorAuto
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.p.meta.theme = theme;
+        self
+    }
+
+    /// Marks this [`SubCommand`] as deprecated, with a `note` telling users what to do instead.
+    /// The subcommand is still parsed as usual, but using it prints a warning to stderr, the help
+    /// message marks it `[deprecated]` and the completion scripts leave it out.
+    ///
+    /// **NOTE:** [`AppSettings::NoDeprecationWarnings`] turns the warnings off, and
+    /// [`AppSettings::HideDeprecated`] hides deprecated subcommands from the help message.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, SubCommand};
+    /// let m = App::new("myprog")
+    ///     .subcommand(SubCommand::with_name("rm")
+    ///         .deprecated("use 'remove' instead"))
+    ///     .subcommand(SubCommand::with_name("remove"))
+    ///     .get_matches_from(vec!["myprog", "rm"]);
+    ///
+    /// assert_eq!(m.subcommand_name(), Some("rm"));
+    /// ```
+    ///
+    /// The above example parses `rm` and prints
+    ///
+    /// ```notrust
+    /// warning: The subcommand 'rm' is deprecated: use 'remove' instead
+    /// ```
+    /// [`SubCommand`]: ./struct.SubCommand.html
+    /// [`AppSettings::NoDeprecationWarnings`]: ./enum.AppSettings.html#variant.NoDeprecationWarnings
+    /// [`AppSettings::HideDeprecated`]: ./enum.AppSettings.html#variant.HideDeprecated
+    pub fn deprecated<S: Into<&'b str>>(mut self, note: S) -> Self {
+        self.p.meta.deprecated = Some(note.into());
+        self
+    }
+
     /// Enables a single command, or [`SubCommand`], level settings.
     ///
     /// See [`AppSettings`] for a full list of possibilities and examples.
@@ -1376,7 +1745,8 @@ impl<'a, 'b> App<'a, 'b> {
     /// Assuming we compiled with debug mode, it would be somewhere similar to
     /// `<project>/target/debug/build/myapp-<hash>/out/myapp.bash-completion`.
     ///
-    /// Fish shell completions will use the file format `{bin_name}.fish`
+    /// Fish shell completions will use the file format `{bin_name}.fish`, and Elvish completions
+    /// will use `{bin_name}.elv`
     pub fn gen_completions<T: Into<OsString>, S: Into<String>>(&mut self,
                                                                bin_name: S,
                                                                for_shell: Shell,
@@ -1426,6 +1796,330 @@ impl<'a, 'b> App<'a, 'b> {
         self.p.gen_completions_to(for_shell, buf);
     }
 
+    /// Generate a small completions file for a specified shell at compile time, which computes
+    /// the candidates at runtime by calling back into the binary. Unlike the scripts from
+    /// [`App::gen_completions`], these don't embed the command tree, so they stay small and can
+    /// complete values only known at runtime via [`Arg::completer`].
+    ///
+    /// The scripts run `{bin_name} __complete -- <words...>`, which requires
+    /// [`AppSettings::DynamicCompletion`] to be set on the [`App`] the binary parses with.
+    ///
+    /// The file names used are the same as those of [`App::gen_completions`].
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// // build.rs
+    /// extern crate clap;
+    ///
+    /// use clap::Shell;
+    ///
+    /// include!("src/cli.rs");
+    ///
+    /// fn main() {
+    ///     let outdir = match env::var_os("OUT_DIR") {
+    ///         None => return,
+    ///         Some(outdir) => outdir,
+    ///     };
+    ///     let mut app = build_cli();
+    ///     app.gen_dynamic_completions("myapp", Shell::Bash, outdir);
+    /// }
+    /// ```
+    /// [`App::gen_completions`]: ./struct.App.html#method.gen_completions
+    /// [`Arg::completer`]: ./struct.Arg.html#method.completer
+    /// [`AppSettings::DynamicCompletion`]: ./enum.AppSettings.html#variant.DynamicCompletion
+    /// [`App`]: ./struct.App.html
+    pub fn gen_dynamic_completions<T: Into<OsString>, S: Into<String>>(&mut self,
+                                                                       bin_name: S,
+                                                                       for_shell: Shell,
+                                                                       out_dir: T) {
+        self.p.meta.bin_name = Some(bin_name.into());
+        self.p.gen_dynamic_completions(for_shell, out_dir.into());
+    }
+
+    /// Generate a dynamic completions file (see [`App::gen_dynamic_completions`]) for a specified
+    /// shell at runtime.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, AppSettings, Shell};
+    /// let mut app = App::new("myapp")
+    ///     .setting(AppSettings::DynamicCompletion);
+    /// let mut buf = vec![];
+    /// app.gen_dynamic_completions_to("myapp", Shell::Bash, &mut buf);
+    ///
+    /// assert!(String::from_utf8(buf).unwrap().contains("myapp __complete --"));
+    /// ```
+    /// [`App::gen_dynamic_completions`]: ./struct.App.html#method.gen_dynamic_completions
+    pub fn gen_dynamic_completions_to<W: Write, S: Into<String>>(&mut self,
+                                                                 bin_name: S,
+                                                                 for_shell: Shell,
+                                                                 buf: &mut W) {
+        self.p.meta.bin_name = Some(bin_name.into());
+        self.p.gen_dynamic_completions_to(for_shell, buf);
+    }
+
+    /// Generate a [Fig] style JSON completion spec at compile time, describing the subcommands,
+    /// options and positional arguments of the [`App`] along with their descriptions,
+    /// [`Arg::possible_values`], and whether they're required or may be used multiple times.
+    /// Hidden arguments and subcommands are left out.
+    ///
+    /// The spec is written to `{bin_name}.json` in `out_dir`.
+    ///
+    /// **NOTE:** This requires the `serde` cargo feature.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// // build.rs
+    /// extern crate clap;
+    ///
+    /// include!("src/cli.rs");
+    ///
+    /// fn main() {
+    ///     let outdir = match env::var_os("OUT_DIR") {
+    ///         None => return,
+    ///         Some(outdir) => outdir,
+    ///     };
+    ///     let mut app = build_cli();
+    ///     app.gen_fig_spec("myapp", outdir);
+    /// }
+    /// ```
+    /// [Fig]: https://fig.io/docs/reference/subcommand
+    /// [`App`]: ./struct.App.html
+    /// [`Arg::possible_values`]: ./struct.Arg.html#method.possible_values
+    #[cfg(feature = "serde")]
+    pub fn gen_fig_spec<T: Into<OsString>, S: Into<String>>(&mut self, bin_name: S, out_dir: T) {
+        self.p.meta.bin_name = Some(bin_name.into());
+        self.p.gen_fig_spec(out_dir.into());
+    }
+
+    /// Generate a Fig style JSON completion spec (see [`App::gen_fig_spec`]) at runtime.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let mut app = App::new("myapp")
+    ///     .arg(Arg::with_name("mode")
+    ///         .long("mode")
+    ///         .takes_value(true)
+    ///         .possible_values(&["fast", "slow"]));
+    /// let mut buf = vec![];
+    /// app.gen_fig_spec_to("myapp", &mut buf);
+    ///
+    /// assert!(String::from_utf8(buf).unwrap().contains(r#""name": "--mode""#));
+    /// ```
+    /// [`App::gen_fig_spec`]: ./struct.App.html#method.gen_fig_spec
+    #[cfg(feature = "serde")]
+    pub fn gen_fig_spec_to<W: Write, S: Into<String>>(&mut self, bin_name: S, buf: &mut W) {
+        self.p.meta.bin_name = Some(bin_name.into());
+        self.p.gen_fig_spec_to(buf);
+    }
+
+    /// Writes a man page (in roff format) for the [`App`] to a [`io::Write`] object, with the
+    /// sections NAME, SYNOPSIS, DESCRIPTION (from [`App::long_about`], or [`App::about`]),
+    /// OPTIONS (using [`Arg::long_help`], value names, defaults and possible values),
+    /// SUBCOMMANDS, AUTHOR and VERSION. Hidden arguments and subcommands are left out.
+    ///
+    /// To write a separate page for each subcommand instead, see [`App::gen_manpages`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let mut app = App::new("myapp")
+    ///     .version("1.0")
+    ///     .about("Does awesome things")
+    ///     .arg(Arg::with_name("config")
+    ///         .long("config")
+    ///         .takes_value(true)
+    ///         .help("Sets a custom config file"));
+    /// let mut buf = vec![];
+    /// app.gen_manpage_to(&mut buf).unwrap();
+    ///
+    /// let page = String::from_utf8(buf).unwrap();
+    /// assert!(page.contains("myapp \\- Does awesome things"));
+    /// ```
+    /// [`App`]: ./struct.App.html
+    /// [`io::Write`]: https://doc.rust-lang.org/std/io/trait.Write.html
+    /// [`App::long_about`]: ./struct.App.html#method.long_about
+    /// [`App::about`]: ./struct.App.html#method.about
+    /// [`Arg::long_help`]: ./struct.Arg.html#method.long_help
+    /// [`App::gen_manpages`]: ./struct.App.html#method.gen_manpages
+    pub fn gen_manpage_to<W: Write>(&mut self, w: &mut W) -> ClapResult<()> {
+        self.p.gen_manpage_to(w)
+    }
+
+    /// Writes one man page per command into `out_dir`, i.e. `{bin_name}.1` for the [`App`] and
+    /// `{bin_name}-{subcommand}.1` for each (visible) subcommand, recursively. The SUBCOMMANDS
+    /// section of each page refers to the pages of its subcommands.
+    ///
+    /// See [`App::gen_manpage_to`] for the sections of the pages.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// // build.rs
+    /// extern crate clap;
+    ///
+    /// include!("src/cli.rs");
+    ///
+    /// fn main() {
+    ///     let outdir = match env::var_os("OUT_DIR") {
+    ///         None => return,
+    ///         Some(outdir) => outdir,
+    ///     };
+    ///     build_cli().gen_manpages(outdir).expect("failed to write the man pages");
+    /// }
+    /// ```
+    /// [`App`]: ./struct.App.html
+    /// [`App::gen_manpage_to`]: ./struct.App.html#method.gen_manpage_to
+    pub fn gen_manpages<T: Into<OsString>>(&mut self, out_dir: T) -> ClapResult<()> {
+        self.p.gen_manpages(out_dir.into())
+    }
+
+    /// Writes a Markdown reference for the whole command tree of the [`App`] to a [`io::Write`]
+    /// object. There is one section per subcommand path (i.e. `## myapp remote add`), with the
+    /// about, usage, visible aliases, and a table of the arguments listing their switches, value
+    /// names, defaults, possible values and [`Arg::help`]. Hidden arguments and subcommands are
+    /// left out.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, SubCommand};
+    /// let mut app = App::new("myapp")
+    ///     .subcommand(SubCommand::with_name("test")
+    ///         .arg(Arg::with_name("case")
+    ///             .long("case")
+    ///             .takes_value(true)
+    ///             .help("the case to test")));
+    /// let mut buf = vec![];
+    /// app.gen_markdown_to(&mut buf).unwrap();
+    ///
+    /// let reference = String::from_utf8(buf).unwrap();
+    /// assert!(reference.contains("## myapp test"));
+    /// assert!(reference.contains("| `--case` | `<case>` |  |  | the case to test |"));
+    /// ```
+    /// [`App`]: ./struct.App.html
+    /// [`io::Write`]: https://doc.rust-lang.org/std/io/trait.Write.html
+    /// [`Arg::help`]: ./struct.Arg.html#method.help
+    pub fn gen_markdown_to<W: Write>(&mut self, w: &mut W) -> ClapResult<()> {
+        self.p.gen_markdown_to(w)
+    }
+
+    /// Checks that the command line of every example added with [`App::example`], on this [`App`]
+    /// and all of its subcommands, parses against this [`App`]. Calling it from a test keeps the
+    /// examples from going stale as arguments are renamed or removed.
+    ///
+    /// Command lines are split on whitespace, except inside single or double quotes, and their
+    /// first word (the name of the binary) is skipped. An example displaying the help or version
+    /// counts as parsing.
+    ///
+    /// **NOTE:** This must be called on the top level [`App`], since the command line of an
+    /// example starts with the name of the binary even when it's the example of a subcommand.
+    ///
+    /// # Errors
+    ///
+    /// The error of the first example which doesn't parse, with that example appended to its
+    /// message.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ErrorKind};
+    /// let app = App::new("tool")
+    ///     .arg(Arg::with_name("env").long("env").takes_value(true))
+    ///     .example("tool --env prod", "Use the production environment")
+    ///     .example("tool --environment prod", "Went stale when --environment was renamed");
+    ///
+    /// let err = app.verify_examples().unwrap_err();
+    /// assert_eq!(err.kind, ErrorKind::UnknownArgument);
+    /// assert!(err.message.ends_with("tool --environment prod"));
+    /// ```
+    /// [`App::example`]: ./struct.App.html#method.example
+    /// [`App`]: ./struct.App.html
+    pub fn verify_examples(&self) -> ClapResult<()> { examples::verify(self) }
+
+    /// Serializes the whole definition of the [`App`] into a JSON document, so that external
+    /// tooling (linters, documentation, GUIs, etc.) can introspect it. Every argument, group and
+    /// subcommand is included (hidden or not), along with their settings, aliases, defaults,
+    /// possible values and relationships (`requires`, `conflicts_with`, `overrides_with`,
+    /// `required_unless` and `required_if`).
+    ///
+    /// The document carries a `schema_version`, which is bumped whenever a key is renamed or
+    /// changes meaning. Keys which are unset are left out, and settings are listed by the names
+    /// of their [`AppSettings`] and [`ArgSettings`] variants.
+    ///
+    /// **NOTE:** This requires the `serde` cargo feature.
+    ///
+    /// **NOTE:** The auto-generated help and version flags, and arguments propagated with
+    /// [`Arg::global`], only exist once the [`App`] starts parsing, so they aren't included.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let app = App::new("myapp")
+    ///     .arg(Arg::with_name("config")
+    ///         .long("config")
+    ///         .takes_value(true)
+    ///         .conflicts_with("debug"))
+    ///     .arg(Arg::with_name("debug")
+    ///         .short("d"));
+    ///
+    /// let json = app.to_json();
+    /// assert!(json.contains(r#""schema_version": 1"#));
+    /// assert!(json.contains(r#""conflicts_with": ["#));
+    /// ```
+    /// [`App`]: ./struct.App.html
+    /// [`AppSettings`]: ./enum.AppSettings.html
+    /// [`ArgSettings`]: ./enum.ArgSettings.html
+    /// [`Arg::global`]: ./struct.Arg.html#method.global
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> String { json::to_json(&self.p) }
+
+    /// Writes the definition of this [`App`] as a YAML document in the format read by
+    /// [`App::from_yaml`], so that `App::from_yaml` of the document builds the same [`App`] again.
+    /// This is handy for moving a CLI built in code to a YAML definition.
+    ///
+    /// Only what differs from a new [`App`] or [`Arg`] is written. Validators, completers and
+    /// default values which aren't valid UTF-8 can't be written as YAML, so they're left out.
+    ///
+    /// **NOTE:** This requires the `yaml` cargo feature.
+    ///
+    /// **NOTE:** The auto-generated help and version flags, and arguments propagated with
+    /// [`Arg::global`], only exist once the [`App`] starts parsing, so they aren't included.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, YamlLoader};
+    /// let app = App::new("myapp")
+    ///     .arg(Arg::with_name("verbose")
+    ///         .short("v")
+    ///         .multiple(true));
+    ///
+    /// let yaml = app.to_yaml();
+    /// assert_eq!(yaml, "name: \"myapp\"\n\
+    ///                   args:\n  \
+    ///                     - verbose:\n      \
+    ///                         short: \"v\"\n      \
+    ///                         multiple: true\n");
+    ///
+    /// let docs = YamlLoader::load_from_str(&yaml).unwrap();
+    /// let m = App::from_yaml(&docs[0]).get_matches_from(vec!["myapp", "-vv"]);
+    /// assert_eq!(m.occurrences_of("verbose"), 2);
+    /// ```
+    /// [`App`]: ./struct.App.html
+    /// [`Arg`]: ./struct.Arg.html
+    /// [`App::from_yaml`]: ./struct.App.html#method.from_yaml
+    /// [`Arg::global`]: ./struct.Arg.html#method.global
+    #[cfg(feature = "yaml")]
+    pub fn to_yaml(&self) -> String { yaml::to_yaml(&self.p) }
+
     /// Starts the parsing process, upon a failed parse an error will be displayed to the user and
     /// the process will exit with the appropriate error code. By default this method gets all user
     /// provided arguments from [`env::args_os`] in order to allow for invalid UTF-8 code points,
@@ -1589,7 +2283,7 @@ impl<'a, 'b> App<'a, 'b> {
 
         let mut matcher = ArgMatcher::new();
 
-        let mut it = itr.into_iter();
+        let mut it = itr.into_iter().peekable();
         // Get the name of the program (argument 1 of env::args()) and determine the
         // actual file
         // that was used to execute the program. This is because a program called
@@ -1611,8 +2305,14 @@ impl<'a, 'b> App<'a, 'b> {
             }
         }
 
+        if self.p.is_set(AppSettings::DynamicCompletion) &&
+           it.peek().map_or(false, |a| a.clone().into() == *COMPLETE_SC) {
+            it.next();
+            return Err(self.p.complete_dynamic(&mut it));
+        }
+
         // do the real parsing
-        if let Err(e) = self.p.get_matches_with(&mut matcher, &mut it.peekable()) {
+        if let Err(e) = self.p.get_matches_with(&mut matcher, &mut it) {
             return Err(e);
         }
 
@@ -1628,133 +2328,12 @@ impl<'a, 'b> App<'a, 'b> {
 
 #[cfg(feature = "yaml")]
 impl<'a> From<&'a Yaml> for App<'a, 'a> {
-    fn from(mut yaml: &'a Yaml) -> Self {
-        use args::SubCommand;
-        // We WANT this to panic on error...so expect() is good.
-        let mut is_sc = None;
-        let mut a = if let Some(name) = yaml["name"].as_str() {
-            App::new(name)
-        } else {
-            let yaml_hash = yaml.as_hash().unwrap();
-            let sc_key = yaml_hash.keys().nth(0).unwrap();
-            is_sc = Some(yaml_hash.get(sc_key).unwrap());
-            App::new(sc_key.as_str().unwrap())
-        };
-        yaml = if let Some(sc) = is_sc { sc } else { yaml };
-
-        macro_rules! yaml_str {
-            ($a:ident, $y:ident, $i:ident) => {
-                if let Some(v) = $y[stringify!($i)].as_str() {
-                    $a = $a.$i(v);
-                } else if $y[stringify!($i)] != Yaml::BadValue {
-                    panic!("Failed to convert YAML value {:?} to a string", $y[stringify!($i)]);
-                }
-            };
-        }
-
-        yaml_str!(a, yaml, version);
-        yaml_str!(a, yaml, author);
-        yaml_str!(a, yaml, bin_name);
-        yaml_str!(a, yaml, about);
-        yaml_str!(a, yaml, before_help);
-        yaml_str!(a, yaml, after_help);
-        yaml_str!(a, yaml, template);
-        yaml_str!(a, yaml, usage);
-        yaml_str!(a, yaml, help);
-        yaml_str!(a, yaml, help_short);
-        yaml_str!(a, yaml, version_short);
-        yaml_str!(a, yaml, help_message);
-        yaml_str!(a, yaml, version_message);
-        yaml_str!(a, yaml, alias);
-        yaml_str!(a, yaml, visible_alias);
-
-        if let Some(v) = yaml["display_order"].as_i64() {
-            a = a.display_order(v as usize);
-        } else if yaml["display_order"] != Yaml::BadValue {
-            panic!("Failed to convert YAML value {:?} to a u64",
-                   yaml["display_order"]);
-        }
-        if let Some(v) = yaml["setting"].as_str() {
-            a = a.setting(v.parse().expect("unknown AppSetting found in YAML file"));
-        } else if yaml["setting"] != Yaml::BadValue {
-            panic!("Failed to convert YAML value {:?} to an AppSetting",
-                   yaml["setting"]);
-        }
-        if let Some(v) = yaml["settings"].as_vec() {
-            for ys in v {
-                if let Some(s) = ys.as_str() {
-                    a = a.setting(s.parse().expect("unknown AppSetting found in YAML file"));
-                }
-            }
-        } else if let Some(v) = yaml["settings"].as_str() {
-            a = a.setting(v.parse().expect("unknown AppSetting found in YAML file"));
-        } else if yaml["settings"] != Yaml::BadValue {
-            panic!("Failed to convert YAML value {:?} to a string",
-                   yaml["settings"]);
-        }
-        if let Some(v) = yaml["global_setting"].as_str() {
-            a = a.setting(v.parse().expect("unknown AppSetting found in YAML file"));
-        } else if yaml["global_setting"] != Yaml::BadValue {
-            panic!("Failed to convert YAML value {:?} to an AppSetting",
-                   yaml["setting"]);
-        }
-        if let Some(v) = yaml["global_settings"].as_vec() {
-            for ys in v {
-                if let Some(s) = ys.as_str() {
-                    a = a.global_setting(s.parse()
-                        .expect("unknown AppSetting found in YAML file"));
-                }
-            }
-        } else if let Some(v) = yaml["global_settings"].as_str() {
-            a = a.global_setting(v.parse().expect("unknown AppSetting found in YAML file"));
-        } else if yaml["global_settings"] != Yaml::BadValue {
-            panic!("Failed to convert YAML value {:?} to a string",
-                   yaml["global_settings"]);
-        }
-
-        macro_rules! vec_or_str {
-            ($a:ident, $y:ident, $as_vec:ident, $as_single:ident) => {{
-                    let maybe_vec = $y[stringify!($as_vec)].as_vec();
-                    if let Some(vec) = maybe_vec {
-                        for ys in vec {
-                            if let Some(s) = ys.as_str() {
-                                $a = $a.$as_single(s);
-                            } else {
-                                panic!("Failed to convert YAML value {:?} to a string", ys);
-                            }
-                        }
-                    } else {
-                        if let Some(s) = $y[stringify!($as_vec)].as_str() {
-                            $a = $a.$as_single(s);
-                        } else if $y[stringify!($as_vec)] != Yaml::BadValue {
-                            panic!("Failed to convert YAML value {:?} to either a vec or string", $y[stringify!($as_vec)]);
-                        }
-                    }
-                    $a
-                }
-            };
-        }
-
-        a = vec_or_str!(a, yaml, aliases, alias);
-        a = vec_or_str!(a, yaml, visible_aliases, visible_alias);
-
-        if let Some(v) = yaml["args"].as_vec() {
-            for arg_yaml in v {
-                a = a.arg(Arg::from_yaml(arg_yaml.as_hash().unwrap()));
-            }
+    fn from(yaml: &'a Yaml) -> Self {
+        // We WANT this to panic on error
+        match loader::app_from_document(yaml, false) {
+            Ok(app) => app,
+            Err(e) => panic!("{}", e.message),
         }
-        if let Some(v) = yaml["subcommands"].as_vec() {
-            for sc_yaml in v {
-                a = a.subcommand(SubCommand::from_yaml(sc_yaml));
-            }
-        }
-        if let Some(v) = yaml["groups"].as_vec() {
-            for ag_yaml in v {
-                a = a.group(ArgGroup::from(ag_yaml.as_hash().unwrap()));
-            }
-        }
-
-        a
     }
 }
 
@@ -1771,8 +2350,13 @@ impl<'n, 'e> AnyArg<'n, 'e> for App<'n, 'e> {
     fn blacklist(&self) -> Option<&[&'e str]> { None }
     fn required_unless(&self) -> Option<&[&'e str]> { None }
     fn val_names(&self) -> Option<&VecMap<&'e str>> { None }
-    fn is_set(&self, _: ArgSettings) -> bool { false }
+    fn is_set(&self, s: ArgSettings) -> bool {
+        // A hidden subcommand is only displayed by the help including the hidden ones
+        s == ArgSettings::Hidden && self.p.is_set(AppSettings::Hidden)
+    }
     fn val_terminator(&self) -> Option<&'e str> { None }
+    fn value_hint(&self) -> Option<ValueHint> { None }
+    fn completer(&self) -> Option<&Rc<Fn(&str) -> Vec<String>>> { None }
     fn set(&mut self, _: ArgSettings) {
         unreachable!("App struct does not support AnyArg::set, this is a bug!")
     }
@@ -1780,6 +2364,7 @@ impl<'n, 'e> AnyArg<'n, 'e> for App<'n, 'e> {
     fn max_vals(&self) -> Option<u64> { None }
     fn num_vals(&self) -> Option<u64> { None }
     fn possible_vals(&self) -> Option<&[&'e str]> { None }
+    fn possible_vals_help(&self) -> Option<&[(&'e str, &'e str)]> { None }
     fn validator(&self) -> Option<&Rc<Fn(String) -> StdResult<(), String>>> { None }
     fn validator_os(&self) -> Option<&Rc<Fn(&OsStr) -> StdResult<(), OsString>>> { None }
     fn min_vals(&self) -> Option<u64> { None }
@@ -1789,6 +2374,7 @@ impl<'n, 'e> AnyArg<'n, 'e> for App<'n, 'e> {
     fn takes_value(&self) -> bool { true }
     fn help(&self) -> Option<&'e str> { self.p.meta.about }
     fn long_help(&self) -> Option<&'e str> { self.p.meta.long_about }
+    fn deprecated(&self) -> Option<&'e str> { self.p.meta.deprecated }
     fn default_val(&self) -> Option<&'e OsStr> { None }
     fn default_vals_ifs(&self) -> Option<vec_map::Values<(&'n str, Option<&'e OsStr>, &'e OsStr)>> {
         None
@@ -1807,6 +2393,19 @@ impl<'n, 'e> AnyArg<'n, 'e> for App<'n, 'e> {
             None
         }
     }
+    fn hidden_aliases(&self) -> Option<Vec<&'e str>> {
+        if let Some(ref aliases) = self.p.meta.aliases {
+            let hidden: Vec<_> =
+                aliases.iter().filter_map(|&(n, v)| if v { None } else { Some(n) }).collect();
+            if hidden.is_empty() {
+                None
+            } else {
+                Some(hidden)
+            }
+        } else {
+            None
+        }
+    }
 }
 
 impl<'n, 'e> fmt::Display for App<'n, 'e> {
@@ -0,0 +1,88 @@
+// Sends the help message through a pager for `AppSettings::PagedHelp`
+
+// Std
+use std::env;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+// Third Party
+#[cfg(feature = "wrap_help")]
+use term_size;
+
+// Internal
+use fmt;
+
+#[cfg(not(feature = "wrap_help"))]
+mod term_size {
+    pub fn dimensions() -> Option<(usize, usize)> { None }
+}
+
+/// Pages `text` if `stdout` is a terminal too short to show all of it. Returns `false` when the
+/// text wasn't paged, in which case it still has to be printed.
+pub fn page(text: &str) -> bool {
+    debugln!("pager::page;");
+    if !fmt::is_a_tty(false) || !needs_paging(text, term_size::dimensions().map(|(_, h)| h)) {
+        return false;
+    }
+    let (prog, args) = pager_cmd(env::var("PAGER").ok());
+    debugln!("pager::page: pager={} {:?}", prog, args);
+    let mut cmd = Command::new(prog);
+    cmd.args(&args).stdin(Stdio::piped());
+    // Lets `less` show colors even when `$PAGER` doesn't ask for it
+    if env::var_os("LESS").is_none() {
+        cmd.env("LESS", "R");
+    }
+    let mut child = match cmd.spawn() {
+        Ok(c) => c,
+        Err(_) => return false,
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        // The user may quit before reading everything, which closes the pipe
+        let _ = writeln!(stdin, "{}", text);
+    }
+    child.wait().is_ok()
+}
+
+fn needs_paging(text: &str, height: Option<usize>) -> bool {
+    match height {
+        Some(h) => text.lines().count() >= h,
+        None => false,
+    }
+}
+
+// `$PAGER` may hold arguments, i.e. `less -FR`
+fn pager_cmd(pager: Option<String>) -> (String, Vec<String>) {
+    let pager = pager.unwrap_or_else(String::new);
+    let mut words = pager.split_whitespace().map(str::to_owned);
+    match words.next() {
+        Some(prog) => (prog, words.collect()),
+        None => ("less".to_owned(), vec!["-R".to_owned()]),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{needs_paging, pager_cmd};
+
+    #[test]
+    fn pager_from_env() {
+        assert_eq!(pager_cmd(Some("most".to_owned())), ("most".to_owned(), vec![]));
+        assert_eq!(pager_cmd(Some(" less  -F -R ".to_owned())),
+                   ("less".to_owned(), vec!["-F".to_owned(), "-R".to_owned()]));
+    }
+
+    #[test]
+    fn pager_default() {
+        let less = ("less".to_owned(), vec!["-R".to_owned()]);
+        assert_eq!(pager_cmd(None), less);
+        assert_eq!(pager_cmd(Some("".to_owned())), less);
+    }
+
+    #[test]
+    fn paging_only_when_too_tall() {
+        assert!(!needs_paging("a\nb\nc", Some(24)));
+        // The prompt takes a line once the help is printed
+        assert!(needs_paging("a\nb\nc", Some(3)));
+        assert!(!needs_paging("a\nb\nc", None));
+    }
+}
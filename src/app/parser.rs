@@ -5,7 +5,7 @@ use std::fs::File;
 use std::io::{self, BufWriter, Write};
 #[cfg(feature = "debug")]
 use std::os::unix::ffi::OsStrExt;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::slice::Iter;
 use std::iter::Peekable;
 
@@ -18,14 +18,17 @@ use INVALID_UTF8;
 use SubCommand;
 use app::App;
 use app::help::Help;
+use app::man::{self, Man};
+use app::markdown::Markdown;
 use app::meta::AppMeta;
+use app::search;
 use app::settings::AppFlags;
 use args::{AnyArg, ArgMatcher, Base, Switched, Arg, ArgGroup, FlagBuilder, OptBuilder, PosBuilder};
 use args::settings::ArgSettings;
-use completions::ComplGen;
+use completions::{self, ComplGen};
 use errors::{Error, ErrorKind};
 use errors::Result as ClapResult;
-use fmt::ColorWhen;
+use fmt::{Colorizer, ColorWhen};
 use osstringext::OsStrExt2;
 use completions::Shell;
 use suggestions;
@@ -64,8 +67,9 @@ pub struct Parser<'a, 'b>
     pub r_ifs: Vec<(&'a str, &'b str, &'a str)>,
     pub blacklist: Vec<&'b str>,
     pub overrides: Vec<&'b str>,
-    help_short: Option<char>,
-    version_short: Option<char>,
+    pub help_headings: Vec<&'b str>,
+    pub help_short: Option<char>,
+    pub version_short: Option<char>,
     cache: Option<&'a str>,
     pub help_message: Option<&'a str>,
     pub version_message: Option<&'a str>,
@@ -97,7 +101,7 @@ impl<'a, 'b> Parser<'a, 'b>
         self.version_short = Some(c);
     }
 
-    pub fn gen_completions_to<W: Write>(&mut self, for_shell: Shell, buf: &mut W) {
+    fn propogate_for_completions(&mut self) {
         if !self.is_set(AS::Propogated) {
             self.propogate_help_version();
             self.build_bin_names();
@@ -105,11 +109,89 @@ impl<'a, 'b> Parser<'a, 'b>
             self.propogate_settings();
             self.set(AS::Propogated);
         }
+    }
+
+    pub fn gen_completions_to<W: Write>(&mut self, for_shell: Shell, buf: &mut W) {
+        self.propogate_for_completions();
 
         ComplGen::new(self).generate(for_shell, buf)
     }
 
     pub fn gen_completions(&mut self, for_shell: Shell, od: OsString) {
+        let mut file = self.create_completions_file(for_shell, od);
+        self.gen_completions_to(for_shell, &mut file)
+    }
+
+    pub fn gen_dynamic_completions_to<W: Write>(&mut self, for_shell: Shell, buf: &mut W) {
+        self.propogate_for_completions();
+
+        ComplGen::new(self).generate_dynamic(for_shell, buf)
+    }
+
+    pub fn gen_dynamic_completions(&mut self, for_shell: Shell, od: OsString) {
+        let mut file = self.create_completions_file(for_shell, od);
+        self.gen_dynamic_completions_to(for_shell, &mut file)
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn gen_fig_spec_to<W: Write>(&mut self, buf: &mut W) {
+        self.propogate_for_completions();
+
+        ComplGen::new(self).generate_fig_spec(buf)
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn gen_fig_spec(&mut self, od: OsString) {
+        use std::error::Error;
+
+        let name = self.meta.bin_name.as_ref().unwrap().clone();
+        let mut file = match File::create(PathBuf::from(od).join(format!("{}.json", name))) {
+            Err(why) => panic!("couldn't create completion spec file: {}", why.description()),
+            Ok(file) => file,
+        };
+        self.gen_fig_spec_to(&mut file)
+    }
+
+    pub fn gen_manpage_to<W: Write>(&mut self, w: &mut W) -> ClapResult<()> {
+        if self.meta.bin_name.is_none() {
+            self.meta.bin_name = Some(self.meta.name.clone());
+        }
+        self.propogate_for_completions();
+
+        Ok(try!(Man::new(w, false).write_page(self)))
+    }
+
+    pub fn gen_manpages(&mut self, od: OsString) -> ClapResult<()> {
+        if self.meta.bin_name.is_none() {
+            self.meta.bin_name = Some(self.meta.name.clone());
+        }
+        self.propogate_for_completions();
+
+        self.write_manpages(&PathBuf::from(od))
+    }
+
+    // Writes the page of this parser, then those of its (visible) subcommands
+    fn write_manpages(&self, out_dir: &Path) -> ClapResult<()> {
+        let path = out_dir.join(format!("{}.1", man::page_name(self)));
+        debugln!("Parser::write_manpages: path={:?}", path);
+        let mut file = try!(File::create(path));
+        try!(Man::new(&mut file, true).write_page(self));
+        for sc in self.subcommands.iter().filter(|sc| !sc.p.is_set(AS::Hidden)) {
+            try!(sc.p.write_manpages(out_dir));
+        }
+        Ok(())
+    }
+
+    pub fn gen_markdown_to<W: Write>(&mut self, w: &mut W) -> ClapResult<()> {
+        if self.meta.bin_name.is_none() {
+            self.meta.bin_name = Some(self.meta.name.clone());
+        }
+        self.propogate_for_completions();
+
+        Ok(try!(Markdown::new(w).write_reference(self)))
+    }
+
+    fn create_completions_file(&self, for_shell: Shell, od: OsString) -> File {
         use std::error::Error;
 
         let out_dir = PathBuf::from(od);
@@ -119,13 +201,31 @@ impl<'a, 'b> Parser<'a, 'b>
             Shell::Fish => format!("{}.fish", name),
             Shell::Zsh => format!("_{}", name),
             Shell::PowerShell => format!("_{}.ps1", name),
+            Shell::Elvish => format!("{}.elv", name),
         };
 
-        let mut file = match File::create(out_dir.join(file_name)) {
+        match File::create(out_dir.join(file_name)) {
             Err(why) => panic!("couldn't create completion file: {}", why.description()),
             Ok(file) => file,
-        };
-        self.gen_completions_to(for_shell, &mut file)
+        }
+    }
+
+    // Handles the hidden `__complete` subcommand by computing the candidates for the words which
+    // follow it, and returning them as a CompletionsDisplayed "error"
+    pub fn complete_dynamic<I, T>(&mut self, it: &mut I) -> Error
+        where I: Iterator<Item = T>,
+              T: Into<OsString>
+    {
+        debugln!("Parser::complete_dynamic;");
+        self.propogate_for_completions();
+
+        let mut words: Vec<String> = it.map(|a| a.into().to_string_lossy().into_owned())
+            .collect();
+        if words.first().map_or(false, |w| w == "--") {
+            words.remove(0);
+        }
+        Error::with_message(completions::dynamic::complete(self, &words).join("\n"),
+                            ErrorKind::CompletionsDisplayed)
     }
 
     #[inline]
@@ -241,6 +341,9 @@ impl<'a, 'b> Parser<'a, 'b>
         self.add_conditional_reqs(&a);
         self.add_arg_groups(&a);
         self.add_reqs(&a);
+        if let Some(h) = a.b.help_heading {
+            self.add_help_heading(h);
+        }
         self.implied_settings(&a);
         if a.index.is_some() || (a.s.short.is_none() && a.s.long.is_none()) {
             let i = if a.index.is_none() {
@@ -266,6 +369,9 @@ impl<'a, 'b> Parser<'a, 'b>
         self.add_conditional_reqs(a);
         self.add_arg_groups(a);
         self.add_reqs(a);
+        if let Some(h) = a.b.help_heading {
+            self.add_help_heading(h);
+        }
         self.implied_settings(&a);
         if a.index.is_some() || (a.s.short.is_none() && a.s.long.is_none()) {
             let i = if a.index.is_none() {
@@ -289,7 +395,17 @@ impl<'a, 'b> Parser<'a, 'b>
         }
     }
 
+    // Headings are displayed in the order they were first used
+    fn add_help_heading(&mut self, h: &'b str) {
+        if !self.help_headings.contains(&h) {
+            self.help_headings.push(h);
+        }
+    }
+
     pub fn add_group(&mut self, group: ArgGroup<'a>) {
+        if let Some(h) = group.heading {
+            self.add_help_heading(h);
+        }
         if group.required {
             self.required.push(group.name);
             if let Some(ref reqs) = group.requires {
@@ -308,6 +424,9 @@ impl<'a, 'b> Parser<'a, 'b>
             grp.requires = group.requires.clone();
             grp.conflicts = group.conflicts.clone();
             grp.required = group.required;
+            if group.heading.is_some() {
+                grp.heading = group.heading;
+            }
         } else {
             self.groups.push(group);
         }
@@ -329,6 +448,9 @@ impl<'a, 'b> Parser<'a, 'b>
         debugln!("Parser::propogate_settings: self={}, g_settings={:#?}",
                  self.meta.name,
                  self.g_settings);
+        if self.is_set(AS::HideDeprecated) {
+            self.hide_deprecated();
+        }
         for sc in &mut self.subcommands {
             debugln!("Parser::propogate_settings: sc={}, settings={:#?}, g_settings={:#?}",
                      sc.p.meta.name,
@@ -339,6 +461,8 @@ impl<'a, 'b> Parser<'a, 'b>
             {
                 let vsc = self.settings.is_set(AS::VersionlessSubcommands);
                 let gv = self.settings.is_set(AS::GlobalVersion);
+                let gt = self.settings.is_set(AS::GlobalTemplate);
+                let ph = self.settings.is_set(AS::PagedHelp);
 
                 if vsc {
                     sc.p.set(AS::DisableVersion);
@@ -347,15 +471,40 @@ impl<'a, 'b> Parser<'a, 'b>
                     sc.p.set(AS::GlobalVersion);
                     sc.p.meta.version = Some(self.meta.version.unwrap());
                 }
+                if gt && sc.p.meta.template.is_none() && self.meta.template.is_some() {
+                    sc.p.set(AS::GlobalTemplate);
+                    sc.p.meta.template = self.meta.template;
+                }
+                if ph {
+                    sc.p.set(AS::PagedHelp);
+                }
                 sc.p.settings = sc.p.settings | self.g_settings;
                 sc.p.g_settings = sc.p.g_settings | self.g_settings;
                 sc.p.meta.term_w = self.meta.term_w;
                 sc.p.meta.max_w = self.meta.max_w;
+                sc.p.meta.theme = self.meta.theme;
             }
             sc.p.propogate_settings();
         }
     }
 
+    // Hides the deprecated arguments and subcommands from the help message
+    fn hide_deprecated(&mut self) {
+        debugln!("Parser::hide_deprecated;");
+        for f in self.flags.iter_mut().filter(|f| f.b.deprecated.is_some()) {
+            f.b.set(ArgSettings::Hidden);
+        }
+        for o in self.opts.iter_mut().filter(|o| o.b.deprecated.is_some()) {
+            o.b.set(ArgSettings::Hidden);
+        }
+        for p in self.positionals.values_mut().filter(|p| p.b.deprecated.is_some()) {
+            p.b.set(ArgSettings::Hidden);
+        }
+        for sc in self.subcommands.iter_mut().filter(|sc| sc.p.meta.deprecated.is_some()) {
+            sc.p.set(AS::Hidden);
+        }
+    }
+
     #[cfg_attr(feature = "lints", allow(needless_borrow))]
     pub fn derive_display_order(&mut self) {
         if self.is_set(AS::DeriveDisplayOrder) {
@@ -392,6 +541,24 @@ impl<'a, 'b> Parser<'a, 'b>
         !(self.flags.is_empty() && self.opts.is_empty() && self.positionals.is_empty())
     }
 
+    // The heading an arg is displayed under, if it or one of its groups has one
+    pub fn help_heading_of(&self, name: &str) -> Option<&'b str> {
+        let own = self.flags
+            .iter()
+            .find(|f| f.b.name == name)
+            .map(|f| &f.b)
+            .or_else(|| self.opts.iter().find(|o| o.b.name == name).map(|o| &o.b))
+            .or_else(|| self.positionals.values().find(|p| p.b.name == name).map(|p| &p.b))
+            .and_then(|b| b.help_heading);
+        own.or_else(|| {
+            self.groups
+                .iter()
+                .filter(|g| g.args.contains(&name))
+                .filter_map(|g| g.heading)
+                .next()
+        })
+    }
+
     #[inline]
     pub fn has_opts(&self) -> bool { !self.opts.is_empty() }
 
@@ -637,6 +804,16 @@ impl<'a, 'b> Parser<'a, 'b>
     {
         debugln!("Parser::parse_help_subcommand;");
         let cmds: Vec<OsString> = it.map(|c| c.into()).collect();
+        if self.is_set(AS::HelpSearch) {
+            if let Some(first) = cmds.first() {
+                if first == "--search" {
+                    return Err(self.search_help(&cmds[1..]));
+                } else if first.starts_with(b"--search=") {
+                    let term = first.split_at(b"--search=".len()).1.to_os_string();
+                    return Err(self.search_help(&[term]));
+                }
+            }
+        }
         let mut help_help = false;
         let mut bin_name = self.meta
             .bin_name
@@ -676,7 +853,7 @@ impl<'a, 'b> Parser<'a, 'b>
                                                                   .bin_name
                                                                   .as_ref()
                                                                   .unwrap_or(&self.meta.name),
-                                                              self.color()));
+                                                              self.colorizer()));
                 }
                 bin_name = format!("{} {}", bin_name, &*sc.meta.name);
             }
@@ -868,7 +1045,7 @@ impl<'a, 'b> Parser<'a, 'b>
                                     return Err(Error::unknown_argument(&*arg_os.to_string_lossy(),
                                         "",
                                         &*usage::create_error_usage(self, matcher, None),
-                                        self.color()));
+                                        self.colorizer()));
                                 }
                             }
                             // ParseResult::MaybeHyphenValue => (),
@@ -895,7 +1072,7 @@ impl<'a, 'b> Parser<'a, 'b>
                                                              &*usage::create_error_usage(self,
                                                                                          matcher,
                                                                                          None),
-                                                             self.color()));
+                                                             self.colorizer()));
                     }
                 }
             }
@@ -945,7 +1122,7 @@ impl<'a, 'b> Parser<'a, 'b>
                                                        &*usage::create_error_usage(self,
                                                                                    matcher,
                                                                                    None),
-                                                       self.color()));
+                                                       self.colorizer()));
                 }
                 parse_positional!(self, p, arg_os, pos_counter, matcher);
                 self.settings.set(AS::ValidArgFound);
@@ -958,7 +1135,7 @@ impl<'a, 'b> Parser<'a, 'b>
                             return Err(Error::invalid_utf8(&*usage::create_error_usage(self,
                                                                                        matcher,
                                                                                        None),
-                                                           self.color()));
+                                                           self.colorizer()));
                         }
                         arg_os.to_string_lossy().into_owned()
                     }
@@ -972,7 +1149,7 @@ impl<'a, 'b> Parser<'a, 'b>
                         return Err(Error::invalid_utf8(&*usage::create_error_usage(self,
                                                                                    matcher,
                                                                                    None),
-                                                       self.color()));
+                                                       self.colorizer()));
                     }
                     sc_m.add_val_to("", &a);
                 }
@@ -989,7 +1166,7 @@ impl<'a, 'b> Parser<'a, 'b>
                                                    &*usage::create_error_usage(self,
                                                                                matcher,
                                                                                None),
-                                                   self.color()));
+                                                   self.colorizer()));
             } else if !has_args || self.is_set(AS::InferSubcommands) && self.has_subcommands() {
                 if let Some(cdate) = suggestions::did_you_mean(&*arg_os.to_string_lossy(),
                                                                sc_names!(self)) {
@@ -1002,7 +1179,7 @@ impl<'a, 'b> Parser<'a, 'b>
                                                          &*usage::create_error_usage(self,
                                                                                      matcher,
                                                                                      None),
-                                                         self.color()));
+                                                         self.colorizer()));
                 } else {
                     return Err(Error::unrecognized_subcommand(arg_os
                                                                   .to_string_lossy()
@@ -1011,11 +1188,13 @@ impl<'a, 'b> Parser<'a, 'b>
                                                                   .bin_name
                                                                   .as_ref()
                                                                   .unwrap_or(&self.meta.name),
-                                                              self.color()));
+                                                              self.colorizer()));
                 }
             }
         }
 
+        self.warn_deprecated_args(matcher);
+
         if let Some(ref pos_sc_name) = subcmd_name {
             let sc_name = {
                 find_subcmd!(self, pos_sc_name)
@@ -1030,22 +1209,42 @@ impl<'a, 'b> Parser<'a, 'b>
             let bn = self.meta.bin_name.as_ref().unwrap_or(&self.meta.name);
             return Err(Error::missing_subcommand(bn,
                                                  &usage::create_error_usage(self, matcher, None),
-                                                 self.color()));
+                                                 self.colorizer()));
         } else if self.is_set(AS::SubcommandRequiredElseHelp) {
             debugln!("Parser::get_matches_with: SubcommandRequiredElseHelp=true");
             let mut out = vec![];
             try!(self.write_help_err(&mut out));
-            return Err(Error {
-                           message: String::from_utf8_lossy(&*out).into_owned(),
-                           kind: ErrorKind::MissingArgumentOrSubcommand,
-                           info: None,
-                       });
+            return Err(Error::with_message(String::from_utf8_lossy(&*out).into_owned(),
+                                           ErrorKind::MissingArgumentOrSubcommand));
         }
 
         Validator::new(self).validate(needs_val_of, subcmd_name, matcher)
     }
 
 
+    // Warns about the deprecated arguments used on the command line, which are the only ones in
+    // the matcher until the default values are added
+    fn warn_deprecated_args(&self, matcher: &ArgMatcher<'a>) {
+        if self.is_set(AS::NoDeprecationWarnings) {
+            return;
+        }
+        let c = self.colorizer();
+        let warn = |a: &AnyArg| if let Some(note) = a.deprecated() {
+            if matcher.contains(a.name()) {
+                warn_deprecated(&c, "argument", &*a.to_string(), note);
+            }
+        };
+        for f in &self.flags {
+            warn(f);
+        }
+        for o in &self.opts {
+            warn(o);
+        }
+        for p in self.positionals.values() {
+            warn(p);
+        }
+    }
+
     fn propogate_help_version(&mut self) {
         debugln!("Parser::propogate_help_version;");
         self.create_help_and_version();
@@ -1107,9 +1306,16 @@ impl<'a, 'b> Parser<'a, 'b>
             }
         }
         mid_string.push_str(" ");
+        let warn = !self.is_set(AS::NoDeprecationWarnings);
+        let c = self.colorizer();
         if let Some(ref mut sc) = self.subcommands
                .iter_mut()
                .find(|s| &s.p.meta.name == &sc_name) {
+            if let Some(note) = sc.p.meta.deprecated {
+                if warn {
+                    warn_deprecated(&c, "subcommand", &*sc.p.meta.name, note);
+                }
+            }
             let mut sc_matcher = ArgMatcher::new();
             // bin_name should be parent's bin_name + [<reqs>] + the sc's name separated by
             // a space
@@ -1237,6 +1443,23 @@ impl<'a, 'b> Parser<'a, 'b>
             };
             self.flags.push(arg);
         }
+        if self.is_set(AS::HelpAll) && !self.contains_long("help-all") {
+            debugln!("Parser::create_help_and_version: Building --help-all");
+            // name is "hclap_help_all" to be sorted right after "hclap_help"
+            let arg = FlagBuilder {
+                b: Base {
+                    name: "hclap_help_all",
+                    help: Some("Prints help information, including hidden arguments and \
+                                subcommands"),
+                    ..Default::default()
+                },
+                s: Switched {
+                    long: Some("help-all"),
+                    ..Default::default()
+                },
+            };
+            self.flags.push(arg);
+        }
         if !self.is_set(AS::DisableVersion) && !self.contains_long("version") {
             debugln!("Parser::create_help_and_version: Building --version");
             if self.version_short.is_none() && !self.contains_short('V') {
@@ -1261,9 +1484,15 @@ impl<'a, 'b> Parser<'a, 'b>
         if !self.subcommands.is_empty() && !self.is_set(AS::DisableHelpSubcommand) &&
            self.is_set(AS::NeedsSubcommandHelp) {
             debugln!("Parser::create_help_and_version: Building help");
-            self.subcommands
-                .push(App::new("help")
-                    .about("Prints this message or the help of the given subcommand(s)"));
+            let mut help = App::new("help")
+                .about("Prints this message or the help of the given subcommand(s)");
+            if self.is_set(AS::HelpSearch) {
+                help = help.arg(Arg::with_name("search")
+                                    .long("search")
+                                    .value_name("term")
+                                    .help("Searches all subcommands and arguments for a term"));
+            }
+            self.subcommands.push(help);
         }
     }
 
@@ -1281,6 +1510,10 @@ impl<'a, 'b> Parser<'a, 'b>
             sdebugln!("Version");
             return Err(self._version(true));
         }
+        if arg == "help-all" && self.flags.iter().any(|f| f.b.name == "hclap_help_all") {
+            sdebugln!("Help all");
+            return Err(self._help_all());
+        }
         sdebugln!("Neither");
 
         Ok(())
@@ -1308,10 +1541,10 @@ impl<'a, 'b> Parser<'a, 'b>
 
     fn use_long_help(&self) -> bool {
         let ul = self.flags.iter().any(|f| f.b.long_help.is_some()) ||
-                 self.opts.iter().any(|o| o.b.long_help.is_some()) ||
+                 self.opts.iter().any(|o| o.b.long_help.is_some() || o.v.pv_help.is_some()) ||
                  self.positionals
                      .values()
-                     .any(|p| p.b.long_help.is_some()) ||
+                     .any(|p| p.b.long_help.is_some() || p.v.pv_help.is_some()) ||
                  self.subcommands
                      .iter()
                      .any(|s| s.p.meta.long_about.is_some());
@@ -1327,11 +1560,48 @@ impl<'a, 'b> Parser<'a, 'b>
             Err(e) => return e,
             _ => (),
         }
-        Error {
-            message: unsafe { String::from_utf8_unchecked(buf) },
-            kind: ErrorKind::HelpDisplayed,
-            info: None,
+        Error::help_displayed(unsafe { String::from_utf8_unchecked(buf) },
+                              self.is_set(AS::PagedHelp))
+    }
+
+    // The results of `help --search <term>`
+    fn search_help(&self, terms: &[OsString]) -> Error {
+        debugln!("Parser::search_help;");
+        let term = terms.iter().map(|t| t.to_string_lossy()).collect::<Vec<_>>().join(" ");
+        if term.trim().is_empty() {
+            let mut search = OptBuilder::new("search");
+            search.s.long = Some("search");
+            let mut names = VecMap::new();
+            names.insert(0, "term");
+            search.v.val_names = Some(names);
+            return Error::empty_value(&search,
+                                      &*usage::create_usage_with_title(self, &[]),
+                                      self.colorizer());
+        }
+        let c = Colorizer {
+            use_stderr: false,
+            when: if self.is_set(AS::ColoredHelp) {
+                self.color()
+            } else {
+                ColorWhen::Never
+            },
+            theme: self.meta.theme,
+        };
+        let mut buf = vec![];
+        if let Err(e) = search::write_matches(&mut buf, &search::search(self, &term), &term, &c) {
+            return Error::from(e);
         }
+        Error::help_displayed(unsafe { String::from_utf8_unchecked(buf) }, false)
+    }
+
+    fn _help_all(&self) -> Error {
+        debugln!("Parser::_help_all;");
+        let mut buf = vec![];
+        if let Err(e) = Help::write_parser_help_all(&mut buf, self, self.use_long_help()) {
+            return e;
+        }
+        Error::help_displayed(unsafe { String::from_utf8_unchecked(buf) },
+                              self.is_set(AS::PagedHelp))
     }
 
     fn _version(&self, use_long: bool) -> Error {
@@ -1342,11 +1612,7 @@ impl<'a, 'b> Parser<'a, 'b>
             Err(e) => return e,
             _ => (),
         }
-        Error {
-            message: String::new(),
-            kind: ErrorKind::VersionDisplayed,
-            info: None,
-        }
+        Error::with_message(String::new(), ErrorKind::VersionDisplayed)
     }
 
     fn parse_long_arg(&mut self,
@@ -1486,7 +1752,7 @@ impl<'a, 'b> Parser<'a, 'b>
                                                    &*usage::create_error_usage(self,
                                                                                matcher,
                                                                                None),
-                                                   self.color()));
+                                                   self.colorizer()));
             }
         }
         Ok(ret)
@@ -1511,7 +1777,7 @@ impl<'a, 'b> Parser<'a, 'b>
                 sdebugln!("Found Empty - Error");
                 return Err(Error::empty_value(opt,
                                               &*usage::create_error_usage(self, matcher, None),
-                                              self.color()));
+                                              self.colorizer()));
             }
             sdebugln!("Found - {:?}, len: {}", v, v.len_());
             debugln!("Parser::parse_opt: {:?} contains '='...{:?}",
@@ -1522,7 +1788,7 @@ impl<'a, 'b> Parser<'a, 'b>
             sdebugln!("None, but requires equals...Error");
             return Err(Error::empty_value(opt,
                                           &*usage::create_error_usage(self, matcher, None),
-                                          self.color()));
+                                          self.colorizer()));
 
         } else {
             sdebugln!("None");
@@ -1630,7 +1896,8 @@ impl<'a, 'b> Parser<'a, 'b>
         let suffix =
             suggestions::did_you_mean_suffix(arg,
                                              longs!(self),
-                                             suggestions::DidYouMeanMessageStyle::LongFlag);
+                                             suggestions::DidYouMeanMessageStyle::LongFlag,
+                                             &self.colorizer());
 
         // Add the arg to the matches to build a proper usage string
         if let Some(name) = suffix.1 {
@@ -1649,7 +1916,7 @@ impl<'a, 'b> Parser<'a, 'b>
         Err(Error::unknown_argument(&*used_arg,
                                     &*suffix.0,
                                     &*usage::create_error_usage(self, matcher, None),
-                                    self.color()))
+                                    self.colorizer()))
     }
 
     // Prints the version to the user and exits if quit=true
@@ -1780,6 +2047,16 @@ impl<'a, 'b> Parser<'a, 'b>
         }
     }
 
+    // Colors the error messages according to the color setting and theme
+    #[doc(hidden)]
+    pub fn colorizer(&self) -> Colorizer {
+        Colorizer {
+            use_stderr: true,
+            when: self.color(),
+            theme: self.meta.theme,
+        }
+    }
+
     pub fn find_any_arg(&self, name: &str) -> Option<&AnyArg> {
         if let Some(f) = find_by_name!(self, &name, flags, iter) {
             return Some(f);
@@ -1826,3 +2103,12 @@ impl<'a, 'b> Parser<'a, 'b>
     #[inline]
     fn contains_short(&self, s: char) -> bool { shorts!(self).any(|arg_s| arg_s == &s) }
 }
+
+// Prints the warning for a deprecated argument or subcommand to stderr
+fn warn_deprecated(c: &Colorizer, kind: &str, name: &str, note: &str) {
+    wlnerr!("{} The {} '{}' is deprecated: {}",
+            c.warning("warning:"),
+            kind,
+            c.value(name),
+            note);
+}
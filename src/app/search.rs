@@ -0,0 +1,158 @@
+// The `help --search <term>` of `AppSettings::HelpSearch`
+
+// Std
+use std::cmp::{self, Ordering};
+use std::fmt::Display;
+use std::io::{self, Write};
+
+// Internal
+use app::parser::Parser;
+use app::settings::AppSettings as AS;
+use args::{AnyArg, ArgSettings};
+use fmt::Colorizer;
+use suggestions;
+
+// The least similarity for a fuzzy match, the same as for the "did you mean" suggestions
+const THRESHOLD: f64 = 0.8;
+
+/// A subcommand or argument matching a search term.
+pub struct Match {
+    /// The subcommand's path, followed by the argument for an argument
+    pub path: String,
+    /// The first line of its help
+    pub summary: String,
+    score: f64,
+}
+
+/// Searches the names, aliases and help of `p`'s subcommands and arguments, at every level, for
+/// `term`. The best matches come first, and otherwise they are in the order of the command tree.
+pub fn search(p: &Parser, term: &str) -> Vec<Match> {
+    debugln!("search::search: term={}", term);
+    let path = p.meta.bin_name.as_ref().unwrap_or(&p.meta.name).clone();
+    let mut ret = vec![];
+    walk(p, &path, &term.to_lowercase(), &mut ret);
+    ret.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(Ordering::Equal));
+    ret
+}
+
+fn walk(p: &Parser, path: &str, term: &str, ret: &mut Vec<Match>) {
+    debugln!("search::walk: path={}", path);
+    // The generated help and version flags would match in every subcommand
+    for f in p.flags().filter(|f| f.b.name != "help" && f.b.name != "version") {
+        push_arg(f, path, term, ret);
+    }
+    for o in p.opts() {
+        push_arg(o, path, term, ret);
+    }
+    for pos in p.positionals() {
+        push_arg(pos, path, term, ret);
+    }
+    for sc in p.subcommands
+            .iter()
+            .filter(|sc| !sc.p.is_set(AS::Hidden) && sc.p.meta.name != "help") {
+        let sc = &sc.p;
+        let sc_path = format!("{} {}", path, sc.meta.name);
+        let mut names = vec![&*sc.meta.name];
+        if let Some(ref aliases) = sc.meta.aliases {
+            names.extend(aliases.iter().map(|&(a, _)| a));
+        }
+        let about = sc.meta.about.or(sc.meta.long_about).unwrap_or("");
+        let score = score(term, &names, about);
+        if score > THRESHOLD {
+            ret.push(Match {
+                path: sc_path.clone(),
+                summary: summary(about),
+                score: score,
+            });
+        }
+        walk(sc, &sc_path, term, ret);
+    }
+}
+
+fn push_arg<'b, 'c, A>(a: &A, path: &str, term: &str, ret: &mut Vec<Match>)
+    where A: AnyArg<'b, 'c> + Display
+{
+    if a.is_set(ArgSettings::Hidden) {
+        return;
+    }
+    let short = a.short().map(|s| s.to_string());
+    let mut names = vec![a.name()];
+    names.extend(short.as_ref().map(|s| &**s));
+    names.extend(a.long());
+    names.extend(a.aliases().unwrap_or(vec![]));
+    let help = a.help().or_else(|| a.long_help()).unwrap_or("");
+    let score = score(term, &names, help);
+    if score > THRESHOLD {
+        ret.push(Match {
+            path: format!("{} {}", path, a),
+            summary: summary(help),
+            score: score,
+        });
+    }
+}
+
+// How well `term` matches the names or help of a subcommand or argument: containing it counts
+// more than being similar to it, and the names count more than the help
+fn score(term: &str, names: &[&str], help: &str) -> f64 {
+    let mut best = 0.0f64;
+    for name in names.iter().map(|n| n.to_lowercase()) {
+        if name.contains(term) {
+            return 1.0;
+        }
+        best = best.max(suggestions::similarity(term, &name));
+    }
+    let help = help.to_lowercase();
+    if help.contains(term) {
+        return best.max(0.9);
+    }
+    for word in help.split(|c: char| !c.is_alphanumeric() && c != '-' && c != '_') {
+        best = best.max(0.9 * suggestions::similarity(term, word));
+    }
+    best
+}
+
+fn summary(help: &str) -> String {
+    help.trim().lines().next().unwrap_or("").replace("{n}", " ")
+}
+
+/// Writes the matches one per line, each path followed by its summary.
+pub fn write_matches(w: &mut Write,
+                     matches: &[Match],
+                     term: &str,
+                     c: &Colorizer)
+                     -> io::Result<()> {
+    debugln!("search::write_matches;");
+    if matches.is_empty() {
+        return write!(w, "No subcommands or arguments match '{}'\n", term);
+    }
+    let longest = matches.iter().fold(0, |l, m| cmp::max(l, m.path.len()));
+    for m in matches {
+        try!(write!(w, "{}", c.literal(&*m.path)));
+        if m.summary.is_empty() {
+            try!(w.write_all(b"\n"));
+        } else {
+            write_nspaces!(w, longest - m.path.len() + 4);
+            try!(write!(w, "{}\n", m.summary));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::score;
+
+    #[test]
+    fn score_order() {
+        assert_eq!(score("net", &["network"], ""), 1.0);
+        assert_eq!(score("net", &["iface"], "The network to use"), 0.9);
+        assert!(score("net", &["iface"], "An interface") < 0.8);
+    }
+
+    #[cfg(feature = "suggestions")]
+    #[test]
+    fn score_fuzzy() {
+        assert!(score("netwrk", &["network"], "") > 0.8);
+        assert!(score("netwrk", &["iface"], "The network to use") > 0.8);
+    }
+}
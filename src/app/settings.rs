@@ -45,9 +45,62 @@ bitflags! {
         const VALID_ARG_FOUND      = 1 << 37,
         const INFER_SUBCOMMANDS    = 1 << 38,
         const CONTAINS_LAST        = 1 << 39,
+        const DYNAMIC_COMPLETION   = 1 << 40,
+        const HELP_MARKUP          = 1 << 41,
+        const PAGED_HELP           = 1 << 42,
+        const GLOBAL_TEMPLATE      = 1 << 43,
+        const HELP_SEARCH          = 1 << 44,
+        const HELP_ALL             = 1 << 45,
+        const HIDE_DEPRECATED      = 1 << 46,
+        const NO_DEPRECATION_WARN  = 1 << 47,
+        const DERIVE_USAGES        = 1 << 48,
     }
 }
 
+// The settings which describe an App, as opposed to those clap uses to keep track of parsing
+#[cfg(any(feature = "yaml", feature = "serde"))]
+pub const APP_SETTINGS: [AppSettings; 41] = [AppSettings::ArgRequiredElseHelp,
+                                             AppSettings::ArgsNegateSubcommands,
+                                             AppSettings::AllowExternalSubcommands,
+                                             AppSettings::AllowInvalidUtf8,
+                                             AppSettings::AllowLeadingHyphen,
+                                             AppSettings::AllowNegativeNumbers,
+                                             AppSettings::AllowMissingPositional,
+                                             AppSettings::ColoredHelp,
+                                             AppSettings::ColorAlways,
+                                             AppSettings::ColorAuto,
+                                             AppSettings::ColorNever,
+                                             AppSettings::DontDelimitTrailingValues,
+                                             AppSettings::DontCollapseArgsInUsage,
+                                             AppSettings::DeriveDisplayOrder,
+                                             AppSettings::DeriveUsages,
+                                             AppSettings::DisableHelpSubcommand,
+                                             AppSettings::DisableVersion,
+                                             AppSettings::DynamicCompletion,
+                                             AppSettings::GlobalTemplate,
+                                             AppSettings::GlobalVersion,
+                                             AppSettings::HelpAll,
+                                             AppSettings::HelpMarkup,
+                                             AppSettings::HelpSearch,
+                                             AppSettings::HideDeprecated,
+                                             AppSettings::HidePossibleValuesInHelp,
+                                             AppSettings::Hidden,
+                                             AppSettings::InferSubcommands,
+                                             AppSettings::LowIndexMultiplePositional,
+                                             AppSettings::NextLineHelp,
+                                             AppSettings::NoBinaryName,
+                                             AppSettings::NoDeprecationWarnings,
+                                             AppSettings::PagedHelp,
+                                             AppSettings::PropagateGlobalValuesDown,
+                                             AppSettings::StrictUtf8,
+                                             AppSettings::SubcommandsNegateReqs,
+                                             AppSettings::SubcommandRequired,
+                                             AppSettings::SubcommandRequiredElseHelp,
+                                             AppSettings::TrailingVarArg,
+                                             AppSettings::UnifiedHelpMessage,
+                                             AppSettings::VersionlessSubcommands,
+                                             AppSettings::WaitOnError];
+
 #[doc(hidden)]
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub struct AppFlags(Flags);
@@ -81,9 +134,12 @@ impl AppFlags {
         DontDelimitTrailingValues => DONT_DELIM_TRAIL,
         DontCollapseArgsInUsage => DONT_COLLAPSE_ARGS,
         DeriveDisplayOrder => DERIVE_DISP_ORDER,
+        DeriveUsages => DERIVE_USAGES,
         DisableHelpSubcommand => DISABLE_HELP_SC,
         DisableVersion => DISABLE_VERSION,
+        GlobalTemplate => GLOBAL_TEMPLATE,
         GlobalVersion => GLOBAL_VERSION,
+        HideDeprecated => HIDE_DEPRECATED,
         HidePossibleValuesInHelp => NO_POS_VALUES,
         Hidden => HIDDEN,
         LowIndexMultiplePositional => LOW_INDEX_MUL_POS,
@@ -91,6 +147,7 @@ impl AppFlags {
         NeedsLongVersion => NEEDS_LONG_VERSION,
         NeedsSubcommandHelp => NEEDS_SC_HELP,
         NoBinaryName => NO_BIN_NAME,
+        NoDeprecationWarnings => NO_DEPRECATION_WARN,
         PropagateGlobalValuesDown=> PROPAGATE_VALS_DOWN,
         StrictUtf8 => UTF8_STRICT,
         SubcommandsNegateReqs => SC_NEGATE_REQS,
@@ -106,7 +163,12 @@ impl AppFlags {
         Propogated => PROPOGATED,
         ValidArgFound => VALID_ARG_FOUND,
         InferSubcommands => INFER_SUBCOMMANDS,
-        ContainsLast => CONTAINS_LAST
+        ContainsLast => CONTAINS_LAST,
+        DynamicCompletion => DYNAMIC_COMPLETION,
+        HelpAll => HELP_ALL,
+        HelpMarkup => HELP_MARKUP,
+        HelpSearch => HELP_SEARCH,
+        PagedHelp => PAGED_HELP
     }
 }
 
@@ -489,6 +551,92 @@ pub enum AppSettings {
     /// [`SubCommand`]: ./struct.SubCommand.html
     DeriveDisplayOrder,
 
+    /// Writes one usage line per way of invoking the program, instead of a single line mixing
+    /// them. An optional argument which conflicts with a required one gets a line of its own,
+    /// without the arguments it conflicts with, and so does each argument of a required
+    /// [`ArgGroup`] which doesn't allow [`ArgGroup::multiple`] arguments. The usual line then
+    /// leaves those arguments out.
+    ///
+    /// **NOTE:** This only changes the usage of the help message, and of the errors which don't
+    /// come from specific arguments. [`App::usages`] sets the lines explicitly instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, AppSettings};
+    /// let res = App::new("tool")
+    ///     .setting(AppSettings::DeriveUsages)
+    ///     .arg(Arg::with_name("list")
+    ///         .long("list")
+    ///         .conflicts_with("FILE"))
+    ///     .arg(Arg::with_name("level")
+    ///         .long("level")
+    ///         .takes_value(true))
+    ///     .arg(Arg::with_name("FILE")
+    ///         .required(true)
+    ///         .multiple(true))
+    ///     .get_matches_from_safe(vec!["tool", "--help"]);
+    ///
+    /// let help = res.unwrap_err().message;
+    /// assert!(help.contains("USAGE:\n    tool [OPTIONS] --list\n    tool [OPTIONS] <FILE>...\n"));
+    /// ```
+    /// [`ArgGroup`]: ./struct.ArgGroup.html
+    /// [`ArgGroup::multiple`]: ./struct.ArgGroup.html#method.multiple
+    /// [`App::usages`]: ./struct.App.html#method.usages
+    DeriveUsages,
+
+    /// Enables the hidden `__complete` subcommand which the scripts generated by
+    /// [`App::gen_dynamic_completions`] call back into. Instead of parsing the arguments, the
+    /// words following `__complete --` are treated as a partially typed command line and the
+    /// completion candidates for the last word are computed from the live [`App`], including any
+    /// closures registered with [`Arg::completer`].
+    ///
+    /// The candidates are returned as an [`ErrorKind::CompletionsDisplayed`] error, one per line,
+    /// which [`Error::exit`] prints to `stdout`. When the value being completed has neither
+    /// possible values nor a completer, but an [`Arg::value_hint`] of a file or directory path,
+    /// the last line is `:files` or `:dirs` and the scripts let the shell complete the path.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, SubCommand, AppSettings, ErrorKind};
+    /// let res = App::new("myprog")
+    ///     .setting(AppSettings::DynamicCompletion)
+    ///     .subcommand(SubCommand::with_name("test"))
+    ///     .subcommand(SubCommand::with_name("temp"))
+    ///     .get_matches_from_safe(vec!["myprog", "__complete", "--", "te"]);
+    ///
+    /// let err = res.unwrap_err();
+    /// assert_eq!(err.kind, ErrorKind::CompletionsDisplayed);
+    /// assert_eq!(err.message, "test\ntemp");
+    /// ```
+    /// [`App::gen_dynamic_completions`]: ./struct.App.html#method.gen_dynamic_completions
+    /// [`App`]: ./struct.App.html
+    /// [`Arg::completer`]: ./struct.Arg.html#method.completer
+    /// [`Arg::value_hint`]: ./struct.Arg.html#method.value_hint
+    /// [`ErrorKind::CompletionsDisplayed`]: ./enum.ErrorKind.html#variant.CompletionsDisplayed
+    /// [`Error::exit`]: ./struct.Error.html#method.exit
+    DynamicCompletion,
+
+    /// Specifies to use the help template of the current command for all child [`SubCommand`]s
+    /// which don't set one of their own with [`App::template`]. (Defaults to `false`; subcommands
+    /// use the default help format unless given a template.)
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg, SubCommand, AppSettings};
+    /// App::new("myprog")
+    ///     .template("{bin}\n{usage}{?all-args}\n\n{all-args}{/all-args}")
+    ///     .setting(AppSettings::GlobalTemplate)
+    ///     .subcommand(SubCommand::with_name("test"))
+    ///     .get_matches();
+    /// // running `$ myprog test --help` will use the same template
+    /// ```
+    /// [`SubCommand`]: ./struct.SubCommand.html
+    /// [`App::template`]: ./struct.App.html#method.template
+    GlobalTemplate,
+
     /// Specifies to use the version of the current command for all child [`SubCommand`]s.
     /// (Defaults to `false`; subcommands have independant version strings from their parents.)
     ///
@@ -510,6 +658,101 @@ pub enum AppSettings {
     /// [`SubCommand`]: ./struct.SubCommand.html
     GlobalVersion,
 
+    /// Generates a `--help-all` flag, which prints the help message including the arguments with
+    /// [`ArgSettings::Hidden`], the subcommands with [`AppSettings::Hidden`] and the hidden
+    /// aliases, each marked as hidden. This lets the users who need them discover the diagnostic
+    /// or experimental arguments which are kept out of the usual help message.
+    ///
+    /// **NOTE:** This setting only applies to the current command, use [`App::global_setting`] to
+    /// add the flag to every subcommand as well.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, AppSettings, ErrorKind};
+    /// let res = App::new("myprog")
+    ///     .setting(AppSettings::HelpAll)
+    ///     .arg(Arg::with_name("trace")
+    ///         .long("trace")
+    ///         .hidden(true)
+    ///         .help("Traces every request"))
+    ///     .get_matches_from_safe(vec!["myprog", "--help-all"]);
+    ///
+    /// let err = res.unwrap_err();
+    /// assert_eq!(err.kind, ErrorKind::HelpDisplayed);
+    /// assert!(err.message.contains("Traces every request [hidden]"));
+    /// ```
+    /// [`ArgSettings::Hidden`]: ./enum.ArgSettings.html#variant.Hidden
+    /// [`AppSettings::Hidden`]: ./enum.AppSettings.html#variant.Hidden
+    /// [`App::global_setting`]: ./struct.App.html#method.global_setting
+    HelpAll,
+
+    /// Renders a small markup in the [`App::about`], [`App::before_help`], [`App::after_help`],
+    /// [`Arg::help`] and [`Arg::long_help`] strings: `**bold**`, `` `code` ``, bullet lists whose
+    /// items start with `- ` or `* `, and paragraphs separated by blank lines. The lines of a
+    /// paragraph or bullet are joined and wrapped to the width of the terminal, with the
+    /// continuation lines of a bullet indented under its text.
+    ///
+    /// With [`AppSettings::ColoredHelp`] the bold text is written in bold and the code in the same
+    /// color as the arguments, otherwise the markers are simply removed. This should be preferred
+    /// to writing ANSI escapes in help strings, which are written even when colors are off and
+    /// throw off the wrapping.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, AppSettings};
+    /// let mut app = App::new("myprog")
+    ///     .setting(AppSettings::HelpMarkup)
+    ///     .about("Copies **every** file.\n\n- skips `.git`\n- keeps permissions");
+    ///
+    /// let mut help = Vec::new();
+    /// app.write_help(&mut help).unwrap();
+    /// let help = String::from_utf8(help).unwrap();
+    /// assert!(help.contains("Copies every file.\n\n- skips .git\n- keeps permissions\n"));
+    /// ```
+    /// [`App::about`]: ./struct.App.html#method.about
+    /// [`App::before_help`]: ./struct.App.html#method.before_help
+    /// [`App::after_help`]: ./struct.App.html#method.after_help
+    /// [`Arg::help`]: ./struct.Arg.html#method.help
+    /// [`Arg::long_help`]: ./struct.Arg.html#method.long_help
+    /// [`AppSettings::ColoredHelp`]: ./enum.AppSettings.html#variant.ColoredHelp
+    HelpMarkup,
+
+    /// Adds a `--search <term>` option to the generated `help` subcommand, which searches the
+    /// names, aliases and help of all the subcommands and arguments, at every level, for `term`.
+    /// The matching subcommands are printed with their path and the first line of their
+    /// [`App::about`], and the matching arguments after the path of their subcommand, best matches
+    /// first. Besides the words containing `term`, similar words match as well with the
+    /// `suggestions` feature, e.g. `netwrk` finds `--network`.
+    ///
+    /// Like the help message, the results are displayed with an [`ErrorKind::HelpDisplayed`]
+    /// error.
+    ///
+    /// **NOTE:** Only the `help` subcommand of the command with this setting gets the option,
+    /// searching from that command down.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, AppSettings, ErrorKind, SubCommand};
+    /// let res = App::new("myprog")
+    ///     .setting(AppSettings::HelpSearch)
+    ///     .subcommand(SubCommand::with_name("remote")
+    ///         .subcommand(SubCommand::with_name("add")
+    ///             .arg(Arg::with_name("tags")
+    ///                 .long("tags")
+    ///                 .help("Imports every tag"))))
+    ///     .get_matches_from_safe(vec!["myprog", "help", "--search", "tag"]);
+    ///
+    /// let err = res.unwrap_err();
+    /// assert_eq!(err.kind, ErrorKind::HelpDisplayed);
+    /// assert_eq!(err.message, "myprog remote add --tags    Imports every tag\n");
+    /// ```
+    /// [`App::about`]: ./struct.App.html#method.about
+    /// [`ErrorKind::HelpDisplayed`]: ./enum.ErrorKind.html#variant.HelpDisplayed
+    HelpSearch,
+
     /// Specifies that this [`SubCommand`] should be hidden from help messages
     ///
     /// # Examples
@@ -524,6 +767,32 @@ pub enum AppSettings {
     /// [`SubCommand`]: ./struct.SubCommand.html
     Hidden,
 
+    /// Hides the arguments and subcommands marked with [`Arg::deprecated`] or
+    /// [`App::deprecated`] from the help message, instead of marking them `[deprecated]`. They
+    /// are still parsed, and shown by `--help-all` (see [`AppSettings::HelpAll`]).
+    ///
+    /// **NOTE:** This setting only applies to the current command, use [`App::global_setting`] to
+    /// hide the deprecated arguments of every subcommand as well.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, AppSettings};
+    /// let res = App::new("myprog")
+    ///     .setting(AppSettings::HideDeprecated)
+    ///     .arg(Arg::with_name("out")
+    ///         .long("out")
+    ///         .deprecated("use --output instead"))
+    ///     .get_matches_from_safe(vec!["myprog", "--help"]);
+    ///
+    /// assert!(!res.unwrap_err().message.contains("--out"));
+    /// ```
+    /// [`Arg::deprecated`]: ./struct.Arg.html#method.deprecated
+    /// [`App::deprecated`]: ./struct.App.html#method.deprecated
+    /// [`AppSettings::HelpAll`]: ./enum.AppSettings.html#variant.HelpAll
+    /// [`App::global_setting`]: ./struct.App.html#method.global_setting
+    HideDeprecated,
+
     /// Tells `clap` *not* to print possible values when displaying help information.
     /// This can be useful if there are many values, or they are explained elsewhere.
     HidePossibleValuesInHelp,
@@ -576,8 +845,35 @@ pub enum AppSettings {
     /// ```
     NoBinaryName,
 
+    /// Turns off the warnings printed to stderr when an argument or subcommand marked with
+    /// [`Arg::deprecated`] or [`App::deprecated`] is used.
+    ///
+    /// **NOTE:** This setting only applies to the current command, use [`App::global_setting`] to
+    /// turn the warnings off for every subcommand as well.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, AppSettings};
+    /// let m = App::new("myprog")
+    ///     .global_setting(AppSettings::NoDeprecationWarnings)
+    ///     .arg(Arg::with_name("out")
+    ///         .long("out")
+    ///         .deprecated("use --output instead"))
+    ///     .get_matches_from(vec!["myprog", "--out"]);
+    ///
+    /// assert!(m.is_present("out"));
+    /// ```
+    /// [`Arg::deprecated`]: ./struct.Arg.html#method.deprecated
+    /// [`App::deprecated`]: ./struct.App.html#method.deprecated
+    /// [`App::global_setting`]: ./struct.App.html#method.global_setting
+    NoDeprecationWarnings,
+
     /// Places the help string for all arguments on the line after the argument.
     ///
+    /// **NOTE:** Without this setting, a section of the help is written this way by itself when
+    /// the terminal is too narrow for its two columns.
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -588,6 +884,33 @@ pub enum AppSettings {
     /// ```
     NextLineHelp,
 
+    /// Sends the help message through a pager when it's taller than the terminal, so that a long
+    /// help message doesn't scroll past the user. The pager is the one named by the `$PAGER`
+    /// environment variable, or `less -R` when it isn't set. Colors are preserved.
+    ///
+    /// The pager is only used when `stdout` is a terminal and the help message doesn't fit in it,
+    /// otherwise the help message is printed as usual. When the pager can't be started the help
+    /// message is printed as well. Paging is done by [`Error::exit`], so the
+    /// [`ErrorKind::HelpDisplayed`] error returned by methods such as
+    /// [`App::get_matches_from_safe`] still holds the whole help message. The help messages of
+    /// child subcommands are paged as well.
+    ///
+    /// **NOTE:** Knowing whether `stdout` is a terminal and how tall it is requires the `color` and
+    /// `wrap_help` features (both are on by default).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use clap::{App, Arg, AppSettings};
+    /// App::new("myprog")
+    ///     .setting(AppSettings::PagedHelp)
+    ///     .get_matches();
+    /// ```
+    /// [`Error::exit`]: ./struct.Error.html#method.exit
+    /// [`ErrorKind::HelpDisplayed`]: ./enum.ErrorKind.html#variant.HelpDisplayed
+    /// [`App::get_matches_from_safe`]: ./struct.App.html#method.get_matches_from_safe
+    PagedHelp,
+
     /// Specifies that the parser should propagate global arg's values down through any *used* child
     /// subcommands. Meaning, if a subcommand wasn't used, the values won't be propagated down to
     /// said subcommand.
@@ -875,22 +1198,33 @@ impl FromStr for AppSettings {
             "allowleadinghyphen" => Ok(AppSettings::AllowLeadingHyphen),
             "allowexternalsubcommands" => Ok(AppSettings::AllowExternalSubcommands),
             "allownegativenumbers" => Ok(AppSettings::AllowNegativeNumbers),
+            "allowmissingpositional" => Ok(AppSettings::AllowMissingPositional),
             "colorauto" => Ok(AppSettings::ColorAuto),
             "coloralways" => Ok(AppSettings::ColorAlways),
             "colornever" => Ok(AppSettings::ColorNever),
             "coloredhelp" => Ok(AppSettings::ColoredHelp),
             "derivedisplayorder" => Ok(AppSettings::DeriveDisplayOrder),
+            "deriveusages" => Ok(AppSettings::DeriveUsages),
             "dontcollapseargsinusage" => Ok(AppSettings::DontCollapseArgsInUsage),
             "dontdelimittrailingvalues" => Ok(AppSettings::DontDelimitTrailingValues),
+            "dynamiccompletion" => Ok(AppSettings::DynamicCompletion),
             "disablehelpsubcommand" => Ok(AppSettings::DisableHelpSubcommand),
             "disableversion" => Ok(AppSettings::DisableVersion),
+            "globaltemplate" => Ok(AppSettings::GlobalTemplate),
             "globalversion" => Ok(AppSettings::GlobalVersion),
+            "helpall" => Ok(AppSettings::HelpAll),
+            "helpmarkup" => Ok(AppSettings::HelpMarkup),
+            "helpsearch" => Ok(AppSettings::HelpSearch),
             "hidden" => Ok(AppSettings::Hidden),
+            "hidedeprecated" => Ok(AppSettings::HideDeprecated),
             "hidepossiblevaluesinhelp" => Ok(AppSettings::HidePossibleValuesInHelp),
             "infersubcommands" => Ok(AppSettings::InferSubcommands),
             "lowindexmultiplepositional" => Ok(AppSettings::LowIndexMultiplePositional),
             "nobinaryname" => Ok(AppSettings::NoBinaryName),
+            "nodeprecationwarnings" => Ok(AppSettings::NoDeprecationWarnings),
             "nextlinehelp" => Ok(AppSettings::NextLineHelp),
+            "pagedhelp" => Ok(AppSettings::PagedHelp),
+            "propagateglobalvaluesdown" => Ok(AppSettings::PropagateGlobalValuesDown),
             "strictutf8" => Ok(AppSettings::StrictUtf8),
             "subcommandsnegatereqs" => Ok(AppSettings::SubcommandsNegateReqs),
             "subcommandrequired" => Ok(AppSettings::SubcommandRequired),
@@ -944,18 +1278,36 @@ mod test {
                    AppSettings::DontDelimitTrailingValues);
         assert_eq!("derivedisplayorder".parse::<AppSettings>().unwrap(),
                    AppSettings::DeriveDisplayOrder);
+        assert_eq!("deriveusages".parse::<AppSettings>().unwrap(),
+                   AppSettings::DeriveUsages);
+        assert_eq!("dynamiccompletion".parse::<AppSettings>().unwrap(),
+                   AppSettings::DynamicCompletion);
+        assert_eq!("globaltemplate".parse::<AppSettings>().unwrap(),
+                   AppSettings::GlobalTemplate);
         assert_eq!("globalversion".parse::<AppSettings>().unwrap(),
                    AppSettings::GlobalVersion);
+        assert_eq!("helpall".parse::<AppSettings>().unwrap(),
+                   AppSettings::HelpAll);
+        assert_eq!("helpmarkup".parse::<AppSettings>().unwrap(),
+                   AppSettings::HelpMarkup);
+        assert_eq!("helpsearch".parse::<AppSettings>().unwrap(),
+                   AppSettings::HelpSearch);
         assert_eq!("hidden".parse::<AppSettings>().unwrap(),
                    AppSettings::Hidden);
+        assert_eq!("hidedeprecated".parse::<AppSettings>().unwrap(),
+                   AppSettings::HideDeprecated);
         assert_eq!("hidepossiblevaluesinhelp".parse::<AppSettings>().unwrap(),
                    AppSettings::HidePossibleValuesInHelp);
         assert_eq!("lowindexmultiplePositional".parse::<AppSettings>().unwrap(),
                    AppSettings::LowIndexMultiplePositional);
         assert_eq!("nobinaryname".parse::<AppSettings>().unwrap(),
                    AppSettings::NoBinaryName);
+        assert_eq!("nodeprecationwarnings".parse::<AppSettings>().unwrap(),
+                   AppSettings::NoDeprecationWarnings);
         assert_eq!("nextlinehelp".parse::<AppSettings>().unwrap(),
                    AppSettings::NextLineHelp);
+        assert_eq!("pagedhelp".parse::<AppSettings>().unwrap(),
+                   AppSettings::PagedHelp);
         assert_eq!("subcommandsnegatereqs".parse::<AppSettings>().unwrap(),
                    AppSettings::SubcommandsNegateReqs);
         assert_eq!("subcommandrequired".parse::<AppSettings>().unwrap(),
@@ -47,6 +47,10 @@ pub fn create_usage_no_title(p: &Parser, used: &[&str]) -> String {
     debugln!("usage::create_usage_no_title;");
     if let Some(u) = p.meta.usage_str {
         String::from(&*u)
+    } else if !p.meta.usages.is_empty() {
+        p.meta.usages.join("\n    ")
+    } else if used.is_empty() && p.is_set(AS::DeriveUsages) {
+        derive_usages(p).join("\n    ")
     } else if used.is_empty() {
         create_help_usage(p, true)
     } else {
@@ -153,6 +157,125 @@ pub fn create_help_usage(p: &Parser, incl_reqs: bool) -> String {
     usage
 }
 
+// Creates the usage lines of AppSettings::DeriveUsages, one per way of invoking the program: one for
+// each optional arg which conflicts with a required one, the usual one without them, and each of
+// these split once more per arg of the required groups which only allow one
+fn derive_usages(p: &Parser) -> Vec<String> {
+    debugln!("usage::derive_usages;");
+    let modes: Vec<&str> = p.flags
+        .iter()
+        .map(|f| &f.b)
+        .chain(p.opts.iter().map(|o| &o.b))
+        .chain(p.positionals.values().map(|pos| &pos.b))
+        .filter(|b| !b.is_set(ArgSettings::Required) && !b.is_set(ArgSettings::Hidden))
+        .map(|b| b.name)
+        .filter(|a| p.required.iter().any(|r| in_conflict(p, a, r)))
+        .collect();
+    debugln!("usage::derive_usages: modes={:?}", modes);
+    let mut parsers = vec![];
+    for &m in &modes {
+        let mut q = restricted(p, |n| n == m || !modes.contains(&n) && !in_conflict(p, m, n));
+        require(&mut q, m);
+        parsers.push(q);
+    }
+    parsers.push(restricted(p, |n| !modes.contains(&n)));
+
+    let mut lines: Vec<String> = vec![];
+    for q in parsers.into_iter().flat_map(split_groups) {
+        let line = create_help_usage(&q, true);
+        if !lines.contains(&line) {
+            lines.push(line);
+        }
+    }
+    lines
+}
+
+// Splits `p` into one copy per arg of its first required group which only allows one, recursively
+fn split_groups<'a, 'b>(p: Parser<'a, 'b>) -> Vec<Parser<'a, 'b>> {
+    let (name, args) = match p.groups.iter().find(|g| g.required && !g.multiple && g.args.len() > 1) {
+        Some(g) => (g.name, g.args.clone()),
+        None => return vec![p],
+    };
+    debugln!("usage::split_groups: group={}", name);
+    let mut ret = vec![];
+    for &a in &args {
+        let mut q = restricted(&p, |n| n != name && (n == a || !args.contains(&n)));
+        require(&mut q, a);
+        ret.extend(split_groups(q));
+    }
+    ret
+}
+
+// Whether the args or groups `a` and `b` conflict with each other, or through their groups
+fn in_conflict(p: &Parser, a: &str, b: &str) -> bool {
+    let blacklists = |x: &str, y: &str| if let Some(arg) = p.find_any_arg(x) {
+        arg.blacklist().map_or(false, |bl| bl.contains(&y))
+    } else {
+        p.groups
+            .iter()
+            .find(|g| g.name == x)
+            .and_then(|g| g.conflicts.as_ref())
+            .map_or(false, |bl| bl.contains(&y))
+    };
+    let with_groups = |x| {
+        let mut names: Vec<&str> = p.groups_for_arg(x).unwrap_or_else(Vec::new);
+        names.push(x);
+        names
+    };
+    let b_names = with_groups(b);
+    with_groups(a)
+        .iter()
+        .any(|x| b_names.iter().any(|y| blacklists(x, y) || blacklists(y, x)))
+}
+
+// A copy of `p` with only the args and groups for which `keep` is true
+fn restricted<'a, 'b, F>(p: &Parser<'a, 'b>, keep: F) -> Parser<'a, 'b>
+    where F: Fn(&str) -> bool
+{
+    let mut q = p.clone();
+    q.flags.retain(|f| keep(f.b.name));
+    q.opts.retain(|o| keep(o.b.name));
+    let gone: Vec<_> = q.positionals
+        .iter()
+        .filter(|&(_, pos)| !keep(pos.b.name))
+        .map(|(i, _)| i)
+        .collect();
+    for i in gone {
+        q.positionals.remove(i);
+    }
+    for g in &mut q.groups {
+        g.args.retain(|a| keep(a));
+    }
+    q.groups.retain(|g| keep(g.name) && !g.args.is_empty());
+    let groups: Vec<&str> = q.groups.iter().map(|g| g.name).collect();
+    q.required.retain(|r| keep(r) && (groups.contains(r) || !p.groups.iter().any(|g| &g.name == r)));
+    {
+        let reqs = q.flags
+            .iter_mut()
+            .map(|f| &mut f.b.requires)
+            .chain(q.opts.iter_mut().map(|o| &mut o.b.requires))
+            .chain(q.positionals.values_mut().map(|pos| &mut pos.b.requires));
+        for r in reqs.filter_map(|r| r.as_mut()) {
+            r.retain(|&(_, name)| keep(name));
+        }
+    }
+    q
+}
+
+// Makes the arg `name` of `p` required, for it to be part of the usage line
+fn require<'a, 'b>(p: &mut Parser<'a, 'b>, name: &'a str) {
+    if let Some(f) = p.flags.iter_mut().find(|f| f.b.name == name) {
+        f.b.set(ArgSettings::Required);
+    }
+    if let Some(o) = p.opts.iter_mut().find(|o| o.b.name == name) {
+        o.b.set(ArgSettings::Required);
+    }
+    if let Some(pos) = p.positionals.values_mut().find(|pos| pos.b.name == name) {
+        pos.b.set(ArgSettings::Required);
+    }
+    p.required.push(name);
+}
+
 // Creates a context aware usage string, or "smart usage" from currently used
 // args, and requirements
 fn create_smart_usage(p: &Parser, used: &[&str]) -> String {
@@ -287,6 +410,11 @@ fn needs_flags_tag(p: &Parser) -> bool {
         if f.is_set(ArgSettings::Hidden) {
             continue;
         }
+        // The usage lines of AppSettings::DeriveUsages write out the flags they require, so these
+        // don't count towards `[FLAGS]` there
+        if p.is_set(AS::DeriveUsages) && f.is_set(ArgSettings::Required) {
+            continue;
+        }
         debugln!("usage::needs_flags_tag:iter: [FLAGS] required");
         return true;
     }
@@ -11,7 +11,6 @@ use errors::Result as ClapResult;
 use osstringext::OsStrExt2;
 use app::settings::AppSettings as AS;
 use app::parser::{Parser, ParseResult};
-use fmt::Colorizer;
 use app::usage;
 
 pub struct Validator<'a, 'b, 'z>(&'z mut Parser<'a, 'b>)
@@ -46,7 +45,7 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
             if should_err {
                 return Err(Error::empty_value(o,
                                               &*usage::create_error_usage(self.0, matcher, None),
-                                              self.0.color()));
+                                              self.0.colorizer()));
             }
         }
 
@@ -54,11 +53,8 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
            self.0.is_set(AS::ArgRequiredElseHelp) {
             let mut out = vec![];
             try!(self.0.write_help_err(&mut out));
-            return Err(Error {
-                           message: String::from_utf8_lossy(&*out).into_owned(),
-                           kind: ErrorKind::MissingArgumentOrSubcommand,
-                           info: None,
-                       });
+            return Err(Error::with_message(String::from_utf8_lossy(&*out).into_owned(),
+                                           ErrorKind::MissingArgumentOrSubcommand));
         }
         try!(self.validate_blacklist(matcher));
         if !(self.0.is_set(AS::SubcommandsNegateReqs) && subcmd_name.is_some()) && !reqs_validated {
@@ -83,7 +79,7 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
                 debugln!("Validator::validate_values: invalid UTF-8 found in val {:?}",
                          val);
                 return Err(Error::invalid_utf8(&*usage::create_error_usage(self.0, matcher, None),
-                                               self.0.color()));
+                                               self.0.colorizer()));
             }
             if let Some(p_vals) = arg.possible_vals() {
                 debugln!("Validator::validate_values: possible_vals={:?}", p_vals);
@@ -95,7 +91,7 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
                                                     &*usage::create_error_usage(self.0,
                                                                                 matcher,
                                                                                 None),
-                                                    self.0.color()));
+                                                    self.0.colorizer()));
                 }
             }
             if !arg.is_set(ArgSettings::EmptyValues) && val.is_empty_() &&
@@ -103,13 +99,13 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
                 debugln!("Validator::validate_values: illegal empty val found");
                 return Err(Error::empty_value(arg,
                                               &*usage::create_error_usage(self.0, matcher, None),
-                                              self.0.color()));
+                                              self.0.colorizer()));
             }
             if let Some(vtor) = arg.validator() {
                 debug!("Validator::validate_values: checking validator...");
                 if let Err(e) = vtor(val.to_string_lossy().into_owned()) {
                     sdebugln!("error");
-                    return Err(Error::value_validation(Some(arg), e, self.0.color()));
+                    return Err(Error::value_validation(Some(arg), e, self.0.colorizer()));
                 } else {
                     sdebugln!("good");
                 }
@@ -120,7 +116,7 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
                     sdebugln!("error");
                     return Err(Error::value_validation(Some(arg),
                                                        (*e).to_string_lossy().to_string(),
-                                                       self.0.color()));
+                                                       self.0.colorizer()));
                 } else {
                     sdebugln!("good");
                 }
@@ -148,15 +144,15 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
                 let usg = usage::create_error_usage($p, $matcher, None);
                 if let Some(f) = find_by_name!($p, $name, flags, iter) {
                     debugln!("build_err!: It was a flag...");
-                    Error::argument_conflict(f, c_with, &*usg, self.0.color())
+                    Error::argument_conflict(f, c_with, &*usg, self.0.colorizer())
                 } else if let Some(o) = find_by_name!($p, $name, opts, iter) {
                    debugln!("build_err!: It was an option...");
-                    Error::argument_conflict(o, c_with, &*usg, self.0.color())
+                    Error::argument_conflict(o, c_with, &*usg, self.0.colorizer())
                 } else {
                     match find_by_name!($p, $name, positionals, values) {
                         Some(p) => {
                             debugln!("build_err!: It was a positional...");
-                            Error::argument_conflict(p, c_with, &*usg, self.0.color())
+                            Error::argument_conflict(p, c_with, &*usg, self.0.colorizer())
                         },
                         None    => panic!(INTERNAL_ERROR_MSG)
                     }
@@ -234,7 +230,7 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
                                                         &*usage::create_error_usage(self.0,
                                                                                     matcher,
                                                                                     None),
-                                                        self.0.color()));
+                                                        self.0.colorizer()));
         }
         Ok(())
     }
@@ -274,7 +270,7 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
                                                          &*usage::create_error_usage(self.0,
                                                                                      matcher,
                                                                                      None),
-                                                         self.0.color()));
+                                                         self.0.colorizer()));
             }
         }
         if let Some(num) = a.max_vals() {
@@ -291,7 +287,7 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
                                                   &*usage::create_error_usage(self.0,
                                                                               matcher,
                                                                               None),
-                                                  self.0.color()));
+                                                  self.0.colorizer()));
             }
         }
         if let Some(num) = a.min_vals() {
@@ -304,14 +300,14 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
                                                  &*usage::create_error_usage(self.0,
                                                                              matcher,
                                                                              None),
-                                                 self.0.color()));
+                                                 self.0.colorizer()));
             }
         }
         // Issue 665 (https://github.com/kbknapp/clap-rs/issues/665)
         if a.takes_value() && !a.is_set(ArgSettings::EmptyValues) && ma.vals.is_empty() {
             return Err(Error::empty_value(a,
                                           &*usage::create_error_usage(self.0, matcher, None),
-                                          self.0.color()));
+                                          self.0.colorizer()));
         }
         Ok(())
     }
@@ -417,10 +413,7 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
 
     fn missing_required_error(&self, matcher: &ArgMatcher, extra: Option<&str>) -> ClapResult<()> {
         debugln!("Validator::missing_required_error: extra={:?}", extra);
-        let c = Colorizer {
-            use_stderr: true,
-            when: self.0.color(),
-        };
+        let c = self.0.colorizer();
         let mut reqs = self.0
             .required
             .iter()
@@ -441,7 +434,7 @@ impl<'a, 'b, 'z> Validator<'a, 'b, 'z> {
                  req_args);
         Err(Error::missing_required_argument(&*req_args,
                                              &*usage::create_error_usage(self.0, matcher, extra),
-                                             self.0.color()))
+                                             self.0.colorizer()))
     }
 
     #[inline]
@@ -0,0 +1,446 @@
+// Std
+use std::ffi::OsStr;
+
+// Internal
+use app::parser::Parser;
+use app::settings::{AppFlags, APP_SETTINGS};
+use args::{ArgGroup, ArgSettings, Base, Switched, Valued};
+use fmt::Theme;
+
+// Just enough of YAML to write what `App::from_yaml` reads
+enum Node {
+    Str(String),
+    Bool(bool),
+    Int(u64),
+    Null,
+    List(Vec<Node>),
+    Map(Vec<(String, Node)>),
+}
+
+impl Node {
+    fn str<S: Into<String>>(s: S) -> Self { Node::Str(s.into()) }
+
+    fn strs<'a, 'b, I: IntoIterator<Item = &'a &'b str>>(it: I) -> Self
+        where 'b: 'a
+    {
+        Node::List(it.into_iter().map(|&s| Node::str(s)).collect())
+    }
+
+    fn is_block(&self) -> bool {
+        match *self {
+            Node::List(ref l) => l.iter().any(|n| n.is_block()),
+            Node::Map(ref m) => !m.is_empty(),
+            _ => false,
+        }
+    }
+}
+
+// Pushes `$k: $v` when `$v` is `Some`, converting it with `$f`
+macro_rules! push_some {
+    ($m:ident, $k:expr, $v:expr) => {
+        push_some!($m, $k, $v, Node::str)
+    };
+    ($m:ident, $k:expr, $v:expr, $f:expr) => {
+        if let Some(v) = $v {
+            $m.push(($k.to_owned(), $f(v)));
+        }
+    };
+}
+
+pub fn to_yaml(p: &Parser) -> String {
+    let mut out = String::new();
+    if let Node::Map(ref m) = app_to_node(p) {
+        write_map(&mut out, m, 0, false);
+    }
+    out
+}
+
+fn app_to_node(p: &Parser) -> Node {
+    debugln!("yaml::app_to_node: name={}", p.meta.name);
+    let mut m = vec![("name".to_owned(), Node::str(&*p.meta.name))];
+    push_some!(m, "bin_name", p.meta.bin_name.as_ref().map(|s| &**s));
+    push_some!(m, "version", p.meta.version);
+    push_some!(m, "long_version", p.meta.long_version);
+    push_some!(m, "author", p.meta.author);
+    push_some!(m, "about", p.meta.about);
+    push_some!(m, "long_about", p.meta.long_about);
+    push_some!(m, "before_help", p.meta.pre_help);
+    push_some!(m, "after_help", p.meta.more_help);
+    push_some!(m, "usage", p.meta.usage_str);
+    if !p.meta.usages.is_empty() {
+        m.push(("usages".to_owned(), Node::strs(&p.meta.usages)));
+    }
+    push_some!(m, "help", p.meta.help_str);
+    push_some!(m, "template", p.meta.template);
+    push_some!(m, "deprecated", p.meta.deprecated);
+    let theme: Vec<_> = p.meta
+        .theme
+        .parts()
+        .iter()
+        .zip(Theme::default().parts().iter())
+        .filter(|&(part, default)| part.1 != default.1)
+        .map(|(&(name, style), _)| (name.to_owned(), Node::str(style.to_string())))
+        .collect();
+    if !theme.is_empty() {
+        m.push(("theme".to_owned(), Node::Map(theme)));
+    }
+    if !p.meta.examples.is_empty() {
+        let examples = p.meta
+            .examples
+            .iter()
+            .map(|&(cmd, desc)| Node::List(vec![Node::str(cmd), Node::str(desc)]))
+            .collect();
+        m.push(("examples".to_owned(), Node::List(examples)));
+    }
+    push_some!(m, "help_short", p.help_short, |c: char| Node::str(c.to_string()));
+    push_some!(m, "version_short", p.version_short, |c: char| Node::str(c.to_string()));
+    push_some!(m, "help_message", p.help_message);
+    push_some!(m, "version_message", p.version_message);
+    if p.meta.disp_ord != 999 {
+        m.push(("display_order".to_owned(), Node::Int(p.meta.disp_ord as u64)));
+    }
+    push_some!(m, "set_term_width", p.meta.term_w, |w| Node::Int(w as u64));
+    push_some!(m, "max_term_width", p.meta.max_w, |w| Node::Int(w as u64));
+    if let Some(ref aliases) = p.meta.aliases {
+        push_aliases(&mut m, aliases);
+    }
+
+    // Only what differs from a new App, with the global settings under their own key
+    let defaults = AppFlags::new();
+    let mut settings = vec![];
+    let mut unset = vec![];
+    let mut global = vec![];
+    for &s in APP_SETTINGS.iter() {
+        let name = Node::str(format!("{:?}", s));
+        if p.g_settings.is_set(s) && !defaults.is_set(s) {
+            global.push(name);
+        } else if p.is_set(s) && !defaults.is_set(s) {
+            settings.push(name);
+        } else if !p.is_set(s) && defaults.is_set(s) {
+            unset.push(name);
+        }
+    }
+    for (k, v) in vec![("settings", settings), ("unset_settings", unset), ("global_settings", global)] {
+        if !v.is_empty() {
+            m.push((k.to_owned(), Node::List(v)));
+        }
+    }
+
+    let mut args = vec![];
+    for f in &p.flags {
+        args.push(arg_to_node(p, &f.b, Some(&f.s), None));
+    }
+    for o in &p.opts {
+        let mut a = arg_to_node(p, &o.b, Some(&o.s), Some(&o.v));
+        if let Node::Map(ref mut a) = a {
+            if let Some(&mut (_, Node::Map(ref mut s))) = a.first_mut() {
+                s.insert(0, ("takes_value".to_owned(), Node::Bool(true)));
+            }
+        }
+        args.push(a);
+    }
+    for pos in p.positionals.values() {
+        let mut a = arg_to_node(p, &pos.b, None, Some(&pos.v));
+        if let Node::Map(ref mut a) = a {
+            if let Some(&mut (_, Node::Map(ref mut s))) = a.first_mut() {
+                s.insert(0, ("index".to_owned(), Node::Int(pos.index)));
+            }
+        }
+        args.push(a);
+    }
+    if !args.is_empty() {
+        m.push(("args".to_owned(), Node::List(args)));
+    }
+    if !p.groups.is_empty() {
+        m.push(("groups".to_owned(), Node::List(p.groups.iter().map(group_to_node).collect())));
+    }
+    if !p.subcommands.is_empty() {
+        m.push(("subcommands".to_owned(),
+                Node::List(p.subcommands
+                    .iter()
+                    .map(|sc| match app_to_node(&sc.p) {
+                        // Subcommands are keyed by their name
+                        Node::Map(mut m) => {
+                            let (_, name) = m.remove(0);
+                            match name {
+                                Node::Str(name) => Node::Map(vec![(name, Node::Map(m))]),
+                                _ => unreachable!(),
+                            }
+                        }
+                        _ => unreachable!(),
+                    })
+                    .collect())));
+    }
+    Node::Map(m)
+}
+
+// An arg is written as `{name: {settings...}}`. The groups it's in are left to the groups
+// themselves, which already list all of their args
+fn arg_to_node(p: &Parser, b: &Base, s: Option<&Switched>, v: Option<&Valued>) -> Node {
+    let mut m = vec![];
+    if let Some(s) = s {
+        push_some!(m, "short", s.short, |c: char| Node::str(c.to_string()));
+        push_some!(m, "long", s.long);
+        if let Some(ref aliases) = s.aliases {
+            push_aliases(&mut m, aliases);
+        }
+        if s.disp_ord != 999 {
+            m.push(("display_order".to_owned(), Node::Int(s.disp_ord as u64)));
+        }
+    }
+    push_some!(m, "help", b.help);
+    push_some!(m, "long_help", b.long_help);
+    push_some!(m, "help_heading", b.help_heading);
+    push_some!(m, "deprecated", b.deprecated);
+
+    for &(setting, key) in &[(ArgSettings::Required, "required"),
+                             (ArgSettings::Multiple, "multiple"),
+                             (ArgSettings::Global, "global"),
+                             (ArgSettings::Hidden, "hidden"),
+                             (ArgSettings::NextLineHelp, "next_line_help"),
+                             (ArgSettings::AllowLeadingHyphen, "allow_hyphen_values"),
+                             (ArgSettings::RequireDelimiter, "require_delimiter"),
+                             (ArgSettings::HidePossibleValues, "hide_possible_values"),
+                             (ArgSettings::HideDefaultValue, "hide_default_value"),
+                             (ArgSettings::RequireEquals, "require_equals"),
+                             (ArgSettings::Last, "last")] {
+        if b.is_set(setting) {
+            m.push((key.to_owned(), Node::Bool(true)));
+        }
+    }
+    // `require_equals` already implies it
+    if !b.is_set(ArgSettings::EmptyValues) && !b.is_set(ArgSettings::RequireEquals) {
+        m.push(("empty_values".to_owned(), Node::Bool(false)));
+    }
+
+    if let Some(v) = v {
+        // Without a delimiter, it was only implied by `value_names` which will imply it again
+        if b.is_set(ArgSettings::UseValueDelimiter) {
+            match v.val_delim {
+                Some(',') => m.push(("use_delimiter".to_owned(), Node::Bool(true))),
+                Some(d) => m.push(("value_delimiter".to_owned(), Node::str(d.to_string()))),
+                None => (),
+            }
+        }
+        push_some!(m, "value_names", v.val_names.as_ref(), |n: &::vec_map::VecMap<&str>| {
+            Node::strs(n.values())
+        });
+        push_some!(m, "number_of_values", v.num_vals, Node::Int);
+        push_some!(m, "min_values", v.min_vals, Node::Int);
+        push_some!(m, "max_values", v.max_vals, Node::Int);
+        push_some!(m, "value_terminator", v.terminator);
+        match (v.possible_vals.as_ref(), v.pv_help.as_ref()) {
+            // Keeps the values in order, those without a description getting an empty one
+            (Some(pvs), Some(helps)) => {
+                let pvs = pvs.iter()
+                    .map(|&pv| {
+                        let h = helps.iter().find(|&&(v, _)| v == pv).map_or("", |&(_, h)| h);
+                        Node::List(vec![Node::str(pv), Node::str(h)])
+                    })
+                    .collect();
+                m.push(("possible_values_with_help".to_owned(), Node::List(pvs)));
+            }
+            (pvs, _) => push_some!(m, "possible_values", pvs, Node::strs),
+        }
+        push_some!(m, "default_value", v.default_val, os_str_to_node);
+        if let Some(ref ifs) = v.default_vals_ifs {
+            m.push(("default_value_ifs".to_owned(),
+                    Node::List(ifs.values()
+                        .map(|&(arg, val, default)| {
+                            Node::List(vec![Node::str(arg),
+                                            val.map_or(Node::Null, os_str_to_node),
+                                            os_str_to_node(default)])
+                        })
+                        .collect())));
+        }
+        push_some!(m, "value_hint", v.value_hint, |h| Node::str(format!("{:?}", h)));
+    }
+
+    if let Some(ref reqs) = b.requires {
+        let requires: Vec<_> = reqs.iter().filter(|r| r.0.is_none()).map(|r| &r.1).collect();
+        if !requires.is_empty() {
+            m.push(("requires".to_owned(), Node::strs(requires)));
+        }
+        let requires_ifs: Vec<_> = reqs.iter()
+            .filter_map(|&(val, arg)| val.map(|val| Node::List(vec![Node::str(val), Node::str(arg)])))
+            .collect();
+        if !requires_ifs.is_empty() {
+            m.push(("requires_ifs".to_owned(), Node::List(requires_ifs)));
+        }
+    }
+    // `Arg::required_if` is only kept by the Parser, keyed by the name of the required arg
+    let required_ifs: Vec<_> = p.r_ifs
+        .iter()
+        .filter(|&&(_, _, name)| name == b.name)
+        .map(|&(arg, val, _)| Node::List(vec![Node::str(arg), Node::str(val)]))
+        .collect();
+    if !required_ifs.is_empty() {
+        m.push(("required_ifs".to_owned(), Node::List(required_ifs)));
+    }
+    push_some!(m, "conflicts_with", b.blacklist.as_ref(), Node::strs);
+    push_some!(m, "overrides_with", b.overrides.as_ref(), Node::strs);
+    if let Some(ref r_unless) = b.r_unless {
+        let key = if b.is_set(ArgSettings::RequiredUnlessAll) {
+            "required_unless_all"
+        } else {
+            "required_unless_one"
+        };
+        m.push((key.to_owned(), Node::strs(r_unless)));
+    }
+    Node::Map(vec![(b.name.to_owned(), Node::Map(m))])
+}
+
+fn group_to_node(g: &ArgGroup) -> Node {
+    let mut m = vec![("args".to_owned(), Node::strs(&g.args))];
+    if g.required {
+        m.push(("required".to_owned(), Node::Bool(true)));
+    }
+    if g.multiple {
+        m.push(("multiple".to_owned(), Node::Bool(true)));
+    }
+    push_some!(m, "requires", g.requires.as_ref(), Node::strs);
+    push_some!(m, "conflicts_with", g.conflicts.as_ref(), Node::strs);
+    push_some!(m, "help_heading", g.heading);
+    Node::Map(vec![(g.name.to_owned(), Node::Map(m))])
+}
+
+fn push_aliases(m: &mut Vec<(String, Node)>, aliases: &[(&str, bool)]) {
+    let hidden: Vec<_> = aliases.iter().filter(|a| !a.1).map(|a| &a.0).collect();
+    let visible: Vec<_> = aliases.iter().filter(|a| a.1).map(|a| &a.0).collect();
+    if !hidden.is_empty() {
+        m.push(("aliases".to_owned(), Node::strs(hidden)));
+    }
+    if !visible.is_empty() {
+        m.push(("visible_aliases".to_owned(), Node::strs(visible)));
+    }
+}
+
+fn os_str_to_node(s: &OsStr) -> Node { Node::str(s.to_string_lossy().into_owned()) }
+
+// Block style for maps, and for lists holding maps. Everything else is written in flow style,
+// i.e. `["a", "b"]`. The first key of a map in a list follows the dash of its item
+fn write_map(out: &mut String, m: &[(String, Node)], indent: usize, inline: bool) {
+    for (i, &(ref k, ref v)) in m.iter().enumerate() {
+        if i > 0 || !inline {
+            push_indent(out, indent);
+        }
+        write_key(out, k);
+        out.push(':');
+        match *v {
+            Node::Map(ref m) if !m.is_empty() => {
+                out.push('\n');
+                write_map(out, m, indent + 2, false);
+            }
+            Node::List(ref l) if v.is_block() => {
+                out.push('\n');
+                for item in l {
+                    push_indent(out, indent + 2);
+                    out.push_str("- ");
+                    match *item {
+                        Node::Map(ref m) if !m.is_empty() => write_map(out, m, indent + 4, true),
+                        _ => {
+                            write_flow(out, item);
+                            out.push('\n');
+                        }
+                    }
+                }
+            }
+            _ => {
+                out.push(' ');
+                write_flow(out, v);
+                out.push('\n');
+            }
+        }
+    }
+}
+
+fn write_flow(out: &mut String, n: &Node) {
+    match *n {
+        Node::Str(ref s) => write_str(out, s),
+        Node::Bool(b) => out.push_str(if b { "true" } else { "false" }),
+        Node::Int(i) => out.push_str(&i.to_string()),
+        Node::Null => out.push('~'),
+        Node::List(ref l) => {
+            out.push('[');
+            for (i, n) in l.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                write_flow(out, n);
+            }
+            out.push(']');
+        }
+        Node::Map(ref m) => {
+            out.push('{');
+            for (i, &(ref k, ref v)) in m.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                write_key(out, k);
+                out.push_str(": ");
+                write_flow(out, v);
+            }
+            out.push('}');
+        }
+    }
+}
+
+// Keys are written plainly unless they could be read as something other than a string
+fn write_key(out: &mut String, k: &str) {
+    let alpha = |c: char| (c >= 'a' && c <= 'z') || (c >= 'A' && c <= 'Z') || c == '_';
+    let plain = k.chars().next().map_or(false, &alpha) &&
+                k.chars().all(|c| alpha(c) || (c >= '0' && c <= '9') || c == '-') &&
+                !["true", "false", "null", "yes", "no", "on", "off", "y", "n"]
+        .contains(&&*k.to_lowercase());
+    if plain {
+        out.push_str(k);
+    } else {
+        write_str(out, k);
+    }
+}
+
+fn write_str(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            '\r' => out.push_str("\\r"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+fn push_indent(out: &mut String, indent: usize) {
+    for _ in 0..indent {
+        out.push(' ');
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use yaml_rust::YamlLoader;
+
+    use app::App;
+    use app::settings::{AppFlags, APP_SETTINGS};
+
+    #[test]
+    fn settings_round_trip() {
+        let defaults = AppFlags::new();
+        for &s in APP_SETTINGS.iter() {
+            let app = if defaults.is_set(s) {
+                App::new("t").unset_setting(s)
+            } else {
+                App::new("t").setting(s)
+            };
+            let docs = YamlLoader::load_from_str(&*app.to_yaml()).unwrap();
+            let loaded = App::try_from_yaml(&docs[0])
+                .unwrap_or_else(|e| panic!("{:?}: {}", s, e.message));
+            assert_eq!(loaded.p.is_set(s), !defaults.is_set(s), "{:?}", s);
+        }
+    }
+}
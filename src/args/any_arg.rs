@@ -8,12 +8,14 @@ use vec_map::{self, VecMap};
 
 // Internal
 use args::settings::ArgSettings;
+use args::ValueHint;
 
 #[doc(hidden)]
 pub trait AnyArg<'n, 'e>: std_fmt::Display {
     fn name(&self) -> &'n str;
     fn overrides(&self) -> Option<&[&'e str]>;
     fn aliases(&self) -> Option<Vec<&'e str>>;
+    fn hidden_aliases(&self) -> Option<Vec<&'e str>>;
     fn requires(&self) -> Option<&[(Option<&'e str>, &'n str)]>;
     fn blacklist(&self) -> Option<&[&'e str]>;
     fn required_unless(&self) -> Option<&[&'e str]>;
@@ -24,6 +26,7 @@ pub trait AnyArg<'n, 'e>: std_fmt::Display {
     fn min_vals(&self) -> Option<u64>;
     fn num_vals(&self) -> Option<u64>;
     fn possible_vals(&self) -> Option<&[&'e str]>;
+    fn possible_vals_help(&self) -> Option<&[(&'e str, &'e str)]>;
     fn validator(&self) -> Option<&Rc<Fn(String) -> Result<(), String>>>;
     fn validator_os(&self) -> Option<&Rc<Fn(&OsStr) -> Result<(), OsString>>>;
     fn short(&self) -> Option<char>;
@@ -33,10 +36,13 @@ pub trait AnyArg<'n, 'e>: std_fmt::Display {
     fn val_names(&self) -> Option<&VecMap<&'e str>>;
     fn help(&self) -> Option<&'e str>;
     fn long_help(&self) -> Option<&'e str>;
+    fn deprecated(&self) -> Option<&'e str>;
     fn default_val(&self) -> Option<&'e OsStr>;
     fn default_vals_ifs(&self) -> Option<vec_map::Values<(&'n str, Option<&'e OsStr>, &'e OsStr)>>;
     fn longest_filter(&self) -> bool;
     fn val_terminator(&self) -> Option<&'e str>;
+    fn value_hint(&self) -> Option<ValueHint>;
+    fn completer(&self) -> Option<&Rc<Fn(&str) -> Vec<String>>>;
 }
 
 pub trait DispOrder {
@@ -14,7 +14,10 @@ use vec_map::VecMap;
 
 use usage_parser::UsageParser;
 use args::settings::ArgSettings;
+use args::ValueHint;
 use args::arg_builder::{Base, Valued, Switched};
+#[cfg(feature = "yaml")]
+use app::loader;
 
 /// The abstract representation of a command line argument. Used to set all the options and
 /// relationships that define a valid argument for the program.
@@ -91,66 +94,11 @@ impl<'a, 'b> Arg<'a, 'b> {
     /// [`Arg`]: ./struct.Arg.html
     #[cfg(feature = "yaml")]
     pub fn from_yaml(y: &BTreeMap<Yaml, Yaml>) -> Arg {
-        // We WANT this to panic on error...so expect() is good.
-        let name_yml = y.keys().nth(0).unwrap();
-        let name_str = name_yml.as_str().unwrap();
-        let mut a = Arg::with_name(name_str);
-        let arg_settings = y.get(name_yml).unwrap().as_hash().unwrap();
-
-        for (k, v) in arg_settings.iter() {
-            a = match k.as_str().unwrap() {
-                "short" => yaml_to_str!(a, v, short),
-                "long" => yaml_to_str!(a, v, long),
-                "aliases" => yaml_vec_or_str!(v, a, alias),
-                "help" => yaml_to_str!(a, v, help),
-                "long_help" => yaml_to_str!(a, v, long_help),
-                "required" => yaml_to_bool!(a, v, required),
-                "required_if" => yaml_tuple2!(a, v, required_if),
-                "required_ifs" => yaml_tuple2!(a, v, required_if),
-                "takes_value" => yaml_to_bool!(a, v, takes_value),
-                "index" => yaml_to_u64!(a, v, index),
-                "global" => yaml_to_bool!(a, v, global),
-                "multiple" => yaml_to_bool!(a, v, multiple),
-                "hidden" => yaml_to_bool!(a, v, hidden),
-                "next_line_help" => yaml_to_bool!(a, v, next_line_help),
-                "empty_values" => yaml_to_bool!(a, v, empty_values),
-                "group" => yaml_to_str!(a, v, group),
-                "number_of_values" => yaml_to_u64!(a, v, number_of_values),
-                "max_values" => yaml_to_u64!(a, v, max_values),
-                "min_values" => yaml_to_u64!(a, v, min_values),
-                "value_name" => yaml_to_str!(a, v, value_name),
-                "use_delimiter" => yaml_to_bool!(a, v, use_delimiter),
-                "allow_hyphen_values" => yaml_to_bool!(a, v, allow_hyphen_values),
-                "require_delimiter" => yaml_to_bool!(a, v, require_delimiter),
-                "value_delimiter" => yaml_to_str!(a, v, value_delimiter),
-                "required_unless" => yaml_to_str!(a, v, required_unless),
-                "display_order" => yaml_to_usize!(a, v, display_order),
-                "default_value" => yaml_to_str!(a, v, default_value),
-                "default_value_if" => yaml_tuple3!(a, v, default_value_if),
-                "default_value_ifs" => yaml_tuple3!(a, v, default_value_if),
-                "value_names" => yaml_vec_or_str!(v, a, value_name),
-                "groups" => yaml_vec_or_str!(v, a, group),
-                "requires" => yaml_vec_or_str!(v, a, requires),
-                "requires_if" => yaml_tuple2!(a, v, requires_if),
-                "requires_ifs" => yaml_tuple2!(a, v, requires_if),
-                "conflicts_with" => yaml_vec_or_str!(v, a, conflicts_with),
-                "overrides_with" => yaml_vec_or_str!(v, a, overrides_with),
-                "possible_values" => yaml_vec_or_str!(v, a, possible_value),
-                "required_unless_one" => yaml_vec_or_str!(v, a, required_unless),
-                "required_unless_all" => {
-                    a = yaml_vec_or_str!(v, a, required_unless);
-                    a.setb(ArgSettings::RequiredUnlessAll);
-                    a
-                }
-                s => {
-                    panic!("Unknown Arg setting '{}' in YAML file for arg '{}'",
-                           s,
-                           name_str)
-                }
-            }
+        // We WANT this to panic on error
+        match loader::arg_from_yaml(y) {
+            Ok(a) => a,
+            Err(e) => panic!("{}", e.message),
         }
-
-        a
     }
 
     /// Creates a new instance of [`Arg`] from a usage string. Allows creation of basic settings
@@ -167,7 +115,7 @@ impl<'a, 'b> Arg<'a, 'b> {
     /// Usage strings typically following the form:
     ///
     /// ```notrust
-    /// [explicit name] [short] [long] [value names] [help string]
+    /// [explicit name] [short] [long] [value names] [possible values] [help string] [attributes]
     /// ```
     ///
     /// This is not a hard rule as the attributes can appear in other orders. There are also
@@ -251,10 +199,22 @@ impl<'a, 'b> Arg<'a, 'b> {
     /// --rapid=<SPEED>... 'some required multiple option'
     /// ```
     ///
+    /// ### Possible Values
+    ///
+    /// This is set by placing a comma separated list of values between `{}`, and works the same
+    /// as [`Arg::possible_values`].
+    ///
+    /// Example possible values are as follows:
+    ///
+    /// ```notrust
+    /// <mode> {fast,slow} 'how to run'
+    /// --color=[WHEN] {auto, always, never} 'when to use colors'
+    /// ```
+    ///
     /// ### Help String
     ///
     /// The help string is denoted between a pair of single quotes `''` and may contain any
-    /// characters.
+    /// characters. It ends at the last single quote of the usage string.
     ///
     /// Example help strings are as follows:
     ///
@@ -263,6 +223,24 @@ impl<'a, 'b> Arg<'a, 'b> {
     /// --rapid=<SPEED>... 'some required multiple option'
     /// ```
     ///
+    /// ### Attributes
+    ///
+    /// Attributes are written as `key:value` with no spaces, and may appear anywhere outside of
+    /// the help string. Lists of names are comma separated, and attributes may be repeated. The
+    /// following keys are supported:
+    ///
+    ///  * `default:VALUE` sets the [default value]
+    ///  * `group:NAME` adds the argument to one or more [groups]
+    ///  * `requires:NAME` makes the argument [require] other arguments or groups
+    ///  * `conflicts:NAME` makes the argument [conflict] with other arguments or groups
+    ///
+    /// Example attributes are as follows:
+    ///
+    /// ```notrust
+    /// --jobs=[N] 'jobs to run' default:4
+    /// --add [NAME] 'adds a remote' group:action requires:url conflicts:quiet,offline
+    /// ```
+    ///
     /// ### Additional Sigils
     ///
     /// Multiple notation `...` (three consecutive dots/periods) specifies that this argument may
@@ -278,12 +256,18 @@ impl<'a, 'b> Arg<'a, 'b> {
     ///     .args(&[
     ///         Arg::from_usage("--config <FILE> 'a required file for the configuration and no short'"),
     ///         Arg::from_usage("-d, --debug... 'turns on debugging information and allows multiples'"),
-    ///         Arg::from_usage("[input] 'an optional input file to use'")
+    ///         Arg::from_usage("[input] 'an optional input file to use'"),
+    ///         Arg::from_usage("--mode=[MODE] {fast,slow} 'how to run' default:fast group:tuning")
     /// ])
     /// # ;
     /// ```
     /// [`Arg`]: ./struct.Arg.html
     /// [`Arg::from_usage`]: ./struct.Arg.html#method.from_usage
+    /// [`Arg::possible_values`]: ./struct.Arg.html#method.possible_values
+    /// [default value]: ./struct.Arg.html#method.default_value
+    /// [groups]: ./struct.Arg.html#method.groups
+    /// [require]: ./struct.Arg.html#method.requires_all
+    /// [conflict]: ./struct.Arg.html#method.conflicts_with_all
     pub fn from_usage(u: &'a str) -> Self {
         let parser = UsageParser::from_usage(u);
         parser.parse()
@@ -616,6 +600,95 @@ impl<'a, 'b> Arg<'a, 'b> {
         self
     }
 
+    /// Displays this argument under its own section of the help message, titled `heading`,
+    /// instead of the usual `FLAGS`, `OPTIONS` or `ARGS` sections. Arguments sharing a heading are
+    /// listed together, and the custom sections are displayed after the usual ones in the order
+    /// their headings were first used.
+    ///
+    /// **NOTE:** A heading set on the argument takes precedence over the one of an
+    /// [`ArgGroup::help_heading`] it belongs to.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("prog")
+    ///     .arg(Arg::with_name("port")
+    ///         .long("port")
+    ///         .takes_value(true)
+    ///         .help_heading("NETWORK OPTIONS")
+    ///         .help("The port to listen on"))
+    ///     .arg(Arg::with_name("out")
+    ///         .long("out")
+    ///         .takes_value(true)
+    ///         .help("Where to write the output"))
+    ///     .get_matches_from(vec![
+    ///         "prog", "--help"
+    ///     ]);
+    /// ```
+    ///
+    /// The above example displays
+    ///
+    /// ```notrust
+    /// prog
+    ///
+    /// USAGE:
+    ///     prog [FLAGS] [OPTIONS]
+    ///
+    /// FLAGS:
+    ///     -h, --help       Prints help information
+    ///     -V, --version    Prints version information
+    ///
+    /// OPTIONS:
+    ///         --out <out>    Where to write the output
+    ///
+    /// NETWORK OPTIONS:
+    ///         --port <port>    The port to listen on
+    /// ```
+    /// [`ArgGroup::help_heading`]: ./struct.ArgGroup.html#method.help_heading
+    pub fn help_heading(mut self, heading: &'b str) -> Self {
+        self.b.help_heading = Some(heading);
+        self
+    }
+
+    /// Marks this argument as deprecated, with a `note` telling users what to do instead. The
+    /// argument is still parsed as usual, but using it prints a warning to stderr, the help
+    /// message marks it `[deprecated]` and the completion scripts leave it out.
+    ///
+    /// **NOTE:** [`AppSettings::NoDeprecationWarnings`] turns the warnings off, and
+    /// [`AppSettings::HideDeprecated`] hides deprecated arguments from the help message.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let m = App::new("prog")
+    ///     .arg(Arg::with_name("out")
+    ///         .long("out")
+    ///         .takes_value(true)
+    ///         .deprecated("use --output instead"))
+    ///     .arg(Arg::with_name("output")
+    ///         .long("output")
+    ///         .takes_value(true))
+    ///     .get_matches_from(vec![
+    ///         "prog", "--out", "notes.txt"
+    ///     ]);
+    ///
+    /// assert_eq!(m.value_of("out"), Some("notes.txt"));
+    /// ```
+    ///
+    /// The above example parses `--out` and prints
+    ///
+    /// ```notrust
+    /// warning: The argument '--out <out>' is deprecated: use --output instead
+    /// ```
+    /// [`AppSettings::NoDeprecationWarnings`]: ./enum.AppSettings.html#variant.NoDeprecationWarnings
+    /// [`AppSettings::HideDeprecated`]: ./enum.AppSettings.html#variant.HideDeprecated
+    pub fn deprecated(mut self, note: &'b str) -> Self {
+        self.b.deprecated = Some(note);
+        self
+    }
+
     /// Specifies that this arg is the last, or final, positional argument (i.e. has the highest
     /// index) and is *only* able to be accessed via the `--` syntax (i.e. `$ prog args --
     /// last_arg`). Even, if no other arguments are left to parse, if the user omits the `--` syntax
@@ -2353,6 +2426,88 @@ impl<'a, 'b> Arg<'a, 'b> {
         self
     }
 
+    /// Specifies a possible value for this argument along with a description of what it means,
+    /// which is otherwise the same as [`Arg::possible_value`].
+    ///
+    /// The long help message (`--help`) lists each possible value with its description below
+    /// the help of the argument, instead of the `[values: ...]` of the short help message. The zsh
+    /// and fish completions display the descriptions as well.
+    ///
+    /// **NOTE:** This setting only applies to [options] and [positional arguments]
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg};
+    /// let res = App::new("prog")
+    ///     .arg(Arg::with_name("mode")
+    ///         .long("mode")
+    ///         .takes_value(true)
+    ///         .help("How to run")
+    ///         .possible_value_with_help("fast", "Skips checks")
+    ///         .possible_value_with_help("slow", "Runs every check"))
+    ///     .get_matches_from_safe(vec![
+    ///         "prog", "--help"
+    ///     ]);
+    ///
+    /// let help = res.unwrap_err().message;
+    /// assert!(help.contains("
+    ///             Possible values:
+    ///                 fast    Skips checks
+    ///                 slow    Runs every check"));
+    /// ```
+    /// [`Arg::possible_value`]: ./struct.Arg.html#method.possible_value
+    /// [options]: ./struct.Arg.html#method.takes_value
+    /// [positional arguments]: ./struct.Arg.html#method.index
+    pub fn possible_value_with_help(mut self, name: &'b str, help: &'b str) -> Self {
+        if let Some(ref mut vec) = self.v.pv_help {
+            vec.push((name, help));
+        } else {
+            self.v.pv_help = Some(vec![(name, help)]);
+        }
+        self.possible_value(name)
+    }
+
+    /// Provides a hint about what kind of value this argument expects, which the shell
+    /// completion generators use to offer native completions for the value (i.e. file paths for
+    /// [`ValueHint::FilePath`], or host names for [`ValueHint::Hostname`]).
+    ///
+    /// **NOTE:** [`Arg::possible_values`] take precedence over a value hint when generating
+    /// completions.
+    ///
+    /// **NOTE:** This setting implies [`Arg::takes_value(true)`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ValueHint};
+    /// Arg::with_name("output")
+    ///     .long("output")
+    ///     .value_hint(ValueHint::FilePath)
+    /// # ;
+    /// ```
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, Shell, ValueHint};
+    /// let mut app = App::new("prog")
+    ///     .arg(Arg::with_name("dir")
+    ///         .long("dir")
+    ///         .value_hint(ValueHint::DirPath));
+    /// let mut buf = vec![];
+    /// app.gen_completions_to("prog", Shell::Bash, &mut buf);
+    ///
+    /// assert!(String::from_utf8(buf).unwrap().contains("compgen -d"));
+    /// ```
+    /// [`ValueHint::FilePath`]: ./enum.ValueHint.html#variant.FilePath
+    /// [`ValueHint::Hostname`]: ./enum.ValueHint.html#variant.Hostname
+    /// [`Arg::possible_values`]: ./struct.Arg.html#method.possible_values
+    /// [`Arg::takes_value(true)`]: ./struct.Arg.html#method.takes_value
+    pub fn value_hint(mut self, hint: ValueHint) -> Self {
+        self.setb(ArgSettings::TakesValue);
+        self.v.value_hint = Some(hint);
+        self
+    }
+
     /// Specifies the name of the [`ArgGroup`] the argument belongs to.
     ///
     /// # Examples
@@ -2519,6 +2674,55 @@ impl<'a, 'b> Arg<'a, 'b> {
         self
     }
 
+    /// Registers a closure which provides completion candidates for this argument's value at
+    /// runtime. The closure receives the partially typed value and returns the candidates to
+    /// offer, which makes it possible to complete values that are only known at runtime, such as
+    /// branch names, remote hosts, or configured profiles.
+    ///
+    /// Candidates returned by the closure are offered as-is, so it is up to the closure to filter
+    /// them by the partial value if desired.
+    ///
+    /// **NOTE:** Completers are only consulted by the scripts generated with
+    /// [`App::gen_dynamic_completions`] and require [`AppSettings::DynamicCompletion`]. If the
+    /// argument also has [`Arg::possible_values`], those are used instead.
+    ///
+    /// **NOTE:** This setting implies [`Arg::takes_value(true)`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, AppSettings, ErrorKind};
+    /// let res = App::new("prog")
+    ///     .setting(AppSettings::DynamicCompletion)
+    ///     .arg(Arg::with_name("branch")
+    ///         .long("branch")
+    ///         .completer(|partial| {
+    ///             ["master", "develop", "feature"]
+    ///                 .iter()
+    ///                 .filter(|b| b.starts_with(partial))
+    ///                 .map(|b| b.to_string())
+    ///                 .collect()
+    ///         }))
+    ///     .get_matches_from_safe(vec![
+    ///         "prog", "__complete", "--", "--branch", "d"
+    ///     ]);
+    ///
+    /// let err = res.unwrap_err();
+    /// assert_eq!(err.kind, ErrorKind::CompletionsDisplayed);
+    /// assert_eq!(err.message, "develop");
+    /// ```
+    /// [`App::gen_dynamic_completions`]: ./struct.App.html#method.gen_dynamic_completions
+    /// [`AppSettings::DynamicCompletion`]: ./enum.AppSettings.html#variant.DynamicCompletion
+    /// [`Arg::possible_values`]: ./struct.Arg.html#method.possible_values
+    /// [`Arg::takes_value(true)`]: ./struct.Arg.html#method.takes_value
+    pub fn completer<F>(mut self, f: F) -> Self
+        where F: Fn(&str) -> Vec<String> + 'static
+    {
+        self.setb(ArgSettings::TakesValue);
+        self.v.completer = Some(Rc::new(f));
+        self
+    }
+
     /// Works identically to Validator but is intended to be used with values that could 
     /// contain non UTF-8 formatted strings.
     ///
@@ -11,7 +11,7 @@ use vec_map::{self, VecMap};
 
 // Internal
 use Arg;
-use args::{ArgSettings, Base, Switched, AnyArg, DispOrder};
+use args::{ArgSettings, Base, Switched, AnyArg, DispOrder, ValueHint};
 
 #[derive(Default, Clone, Debug)]
 #[doc(hidden)]
@@ -72,6 +72,7 @@ impl<'n, 'e> AnyArg<'n, 'e> for FlagBuilder<'n, 'e> {
     fn val_names(&self) -> Option<&VecMap<&'e str>> { None }
     fn num_vals(&self) -> Option<u64> { None }
     fn possible_vals(&self) -> Option<&[&'e str]> { None }
+    fn possible_vals_help(&self) -> Option<&[(&'e str, &'e str)]> { None }
     fn validator(&self) -> Option<&Rc<Fn(String) -> StdResult<(), String>>> { None }
     fn validator_os(&self) -> Option<&Rc<Fn(&OsStr) -> StdResult<(), OsString>>> { None }
     fn min_vals(&self) -> Option<u64> { None }
@@ -80,7 +81,10 @@ impl<'n, 'e> AnyArg<'n, 'e> for FlagBuilder<'n, 'e> {
     fn val_delim(&self) -> Option<char> { None }
     fn help(&self) -> Option<&'e str> { self.b.help }
     fn long_help(&self) -> Option<&'e str> { self.b.long_help }
+    fn deprecated(&self) -> Option<&'e str> { self.b.deprecated }
     fn val_terminator(&self) -> Option<&'e str> { None }
+    fn value_hint(&self) -> Option<ValueHint> { None }
+    fn completer(&self) -> Option<&Rc<Fn(&str) -> Vec<String>>> { None }
     fn default_val(&self) -> Option<&'e OsStr> { None }
     fn default_vals_ifs(&self) -> Option<vec_map::Values<(&'n str, Option<&'e OsStr>, &'e OsStr)>> {
         None
@@ -100,6 +104,20 @@ impl<'n, 'e> AnyArg<'n, 'e> for FlagBuilder<'n, 'e> {
             None
         }
     }
+    fn hidden_aliases(&self) -> Option<Vec<&'e str>> {
+        if let Some(ref aliases) = self.s.aliases {
+            let hidden: Vec<_> = aliases.iter()
+                .filter_map(|&(n, v)| if v { None } else { Some(n) })
+                .collect();
+            if hidden.is_empty() {
+                None
+            } else {
+                Some(hidden)
+            }
+        } else {
+            None
+        }
+    }
 }
 
 impl<'n, 'e> DispOrder for FlagBuilder<'n, 'e> {
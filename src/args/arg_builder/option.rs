@@ -9,7 +9,7 @@ use std::mem;
 use vec_map::{self, VecMap};
 
 // Internal
-use args::{ArgSettings, AnyArg, Base, Switched, Valued, Arg, DispOrder};
+use args::{ArgSettings, AnyArg, Base, Switched, Valued, Arg, DispOrder, ValueHint};
 
 #[allow(missing_debug_implementations)]
 #[doc(hidden)]
@@ -115,8 +115,13 @@ impl<'n, 'e> AnyArg<'n, 'e> for OptBuilder<'n, 'e> {
     fn set(&mut self, s: ArgSettings) { self.b.settings.set(s) }
     fn max_vals(&self) -> Option<u64> { self.v.max_vals }
     fn val_terminator(&self) -> Option<&'e str> { self.v.terminator }
+    fn value_hint(&self) -> Option<ValueHint> { self.v.value_hint }
+    fn completer(&self) -> Option<&Rc<Fn(&str) -> Vec<String>>> { self.v.completer.as_ref() }
     fn num_vals(&self) -> Option<u64> { self.v.num_vals }
     fn possible_vals(&self) -> Option<&[&'e str]> { self.v.possible_vals.as_ref().map(|o| &o[..]) }
+    fn possible_vals_help(&self) -> Option<&[(&'e str, &'e str)]> {
+        self.v.pv_help.as_ref().map(|o| &o[..])
+    }
     fn validator(&self) -> Option<&Rc<Fn(String) -> StdResult<(), String>>> {
         self.v.validator.as_ref()
     }
@@ -130,6 +135,7 @@ impl<'n, 'e> AnyArg<'n, 'e> for OptBuilder<'n, 'e> {
     fn takes_value(&self) -> bool { true }
     fn help(&self) -> Option<&'e str> { self.b.help }
     fn long_help(&self) -> Option<&'e str> { self.b.long_help }
+    fn deprecated(&self) -> Option<&'e str> { self.b.deprecated }
     fn default_val(&self) -> Option<&'e OsStr> { self.v.default_val }
     fn default_vals_ifs(&self) -> Option<vec_map::Values<(&'n str, Option<&'e OsStr>, &'e OsStr)>> {
         self.v.default_vals_ifs.as_ref().map(|vm| vm.values())
@@ -149,6 +155,20 @@ impl<'n, 'e> AnyArg<'n, 'e> for OptBuilder<'n, 'e> {
             None
         }
     }
+    fn hidden_aliases(&self) -> Option<Vec<&'e str>> {
+        if let Some(ref aliases) = self.s.aliases {
+            let hidden: Vec<_> = aliases.iter()
+                .filter_map(|&(n, v)| if v { None } else { Some(n) })
+                .collect();
+            if hidden.is_empty() {
+                None
+            } else {
+                Some(hidden)
+            }
+        } else {
+            None
+        }
+    }
 }
 
 impl<'n, 'e> DispOrder for OptBuilder<'n, 'e> {
@@ -11,7 +11,7 @@ use vec_map::{self, VecMap};
 
 // Internal
 use Arg;
-use args::{ArgSettings, Base, Valued, AnyArg, DispOrder};
+use args::{ArgSettings, Base, Valued, AnyArg, DispOrder, ValueHint};
 use INTERNAL_ERROR_MSG;
 
 #[allow(missing_debug_implementations)]
@@ -126,8 +126,13 @@ impl<'n, 'e> AnyArg<'n, 'e> for PosBuilder<'n, 'e> {
     fn has_switch(&self) -> bool { false }
     fn max_vals(&self) -> Option<u64> { self.v.max_vals }
     fn val_terminator(&self) -> Option<&'e str> { self.v.terminator }
+    fn value_hint(&self) -> Option<ValueHint> { self.v.value_hint }
+    fn completer(&self) -> Option<&Rc<Fn(&str) -> Vec<String>>> { self.v.completer.as_ref() }
     fn num_vals(&self) -> Option<u64> { self.v.num_vals }
     fn possible_vals(&self) -> Option<&[&'e str]> { self.v.possible_vals.as_ref().map(|o| &o[..]) }
+    fn possible_vals_help(&self) -> Option<&[(&'e str, &'e str)]> {
+        self.v.pv_help.as_ref().map(|o| &o[..])
+    }
     fn validator(&self) -> Option<&Rc<Fn(String) -> StdResult<(), String>>> {
         self.v.validator.as_ref()
     }
@@ -141,12 +146,14 @@ impl<'n, 'e> AnyArg<'n, 'e> for PosBuilder<'n, 'e> {
     fn takes_value(&self) -> bool { true }
     fn help(&self) -> Option<&'e str> { self.b.help }
     fn long_help(&self) -> Option<&'e str> { self.b.long_help }
+    fn deprecated(&self) -> Option<&'e str> { self.b.deprecated }
     fn default_vals_ifs(&self) -> Option<vec_map::Values<(&'n str, Option<&'e OsStr>, &'e OsStr)>> {
         self.v.default_vals_ifs.as_ref().map(|vm| vm.values())
     }
     fn default_val(&self) -> Option<&'e OsStr> { self.v.default_val }
     fn longest_filter(&self) -> bool { true }
     fn aliases(&self) -> Option<Vec<&'e str>> { None }
+    fn hidden_aliases(&self) -> Option<Vec<&'e str>> { None }
 }
 
 impl<'n, 'e> DispOrder for PosBuilder<'n, 'e> {
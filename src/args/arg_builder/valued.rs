@@ -4,6 +4,7 @@ use std::ffi::{OsStr, OsString};
 use vec_map::VecMap;
 
 use Arg;
+use args::ValueHint;
 
 #[allow(missing_debug_implementations)]
 #[derive(Clone)]
@@ -11,6 +12,7 @@ pub struct Valued<'a, 'b>
     where 'a: 'b
 {
     pub possible_vals: Option<Vec<&'b str>>,
+    pub pv_help: Option<Vec<(&'b str, &'b str)>>, // (value, help)
     pub val_names: Option<VecMap<&'b str>>,
     pub num_vals: Option<u64>,
     pub max_vals: Option<u64>,
@@ -21,12 +23,15 @@ pub struct Valued<'a, 'b>
     pub default_val: Option<&'b OsStr>,
     pub default_vals_ifs: Option<VecMap<(&'a str, Option<&'b OsStr>, &'b OsStr)>>,
     pub terminator: Option<&'b str>,
+    pub value_hint: Option<ValueHint>,
+    pub completer: Option<Rc<Fn(&str) -> Vec<String>>>,
 }
 
 impl<'n, 'e> Default for Valued<'n, 'e> {
     fn default() -> Self {
         Valued {
             possible_vals: None,
+            pv_help: None,
             num_vals: None,
             min_vals: None,
             max_vals: None,
@@ -37,6 +42,8 @@ impl<'n, 'e> Default for Valued<'n, 'e> {
             default_val: None,
             default_vals_ifs: None,
             terminator: None,
+            value_hint: None,
+            completer: None,
         }
     }
 }
@@ -5,6 +5,9 @@ use std::fmt::{Debug, Formatter, Result};
 #[cfg(feature = "yaml")]
 use yaml_rust::Yaml;
 
+#[cfg(feature = "yaml")]
+use app::loader;
+
 /// `ArgGroup`s are a family of related [arguments] and way for you to express, "Any of these
 /// arguments". By placing arguments in a logical group, you can create easier requirement and
 /// exclusion rules instead of having to list each argument individually, or when you want a rule
@@ -91,6 +94,8 @@ pub struct ArgGroup<'a> {
     pub conflicts: Option<Vec<&'a str>>,
     #[doc(hidden)]
     pub multiple: bool,
+    #[doc(hidden)]
+    pub heading: Option<&'a str>,
 }
 
 impl<'a> ArgGroup<'a> {
@@ -112,6 +117,7 @@ impl<'a> ArgGroup<'a> {
             requires: None,
             conflicts: None,
             multiple: false,
+            heading: None,
         }
     }
 
@@ -422,6 +428,53 @@ impl<'a> ArgGroup<'a> {
         }
         self
     }
+
+    /// Displays the [`Arg`]s of this group under their own section of the help message, titled
+    /// `heading`, instead of the usual `FLAGS`, `OPTIONS` or `ARGS` sections.
+    ///
+    /// **NOTE:** An [`Arg::help_heading`] set on an argument takes precedence over the heading of
+    /// its group. When an argument is in more than one group with a heading, the group defined
+    /// first wins.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, ArgGroup};
+    /// let m = App::new("myprog")
+    ///     .arg(Arg::with_name("json")
+    ///         .long("json")
+    ///         .help("Prints JSON"))
+    ///     .arg(Arg::with_name("yaml")
+    ///         .long("yaml")
+    ///         .help("Prints YAML"))
+    ///     .group(ArgGroup::with_name("format")
+    ///         .args(&["json", "yaml"])
+    ///         .help_heading("OUTPUT FORMATS"))
+    ///     .get_matches_from(vec!["myprog", "--help"]);
+    /// ```
+    ///
+    /// The above example displays
+    ///
+    /// ```notrust
+    /// myprog
+    ///
+    /// USAGE:
+    ///     myprog [FLAGS]
+    ///
+    /// FLAGS:
+    ///     -h, --help       Prints help information
+    ///     -V, --version    Prints version information
+    ///
+    /// OUTPUT FORMATS:
+    ///         --json    Prints JSON
+    ///         --yaml    Prints YAML
+    /// ```
+    /// [`Arg`]: ./struct.Arg.html
+    /// [`Arg::help_heading`]: ./struct.Arg.html#method.help_heading
+    pub fn help_heading(mut self, heading: &'a str) -> Self {
+        self.heading = Some(heading);
+        self
+    }
 }
 
 impl<'a> Debug for ArgGroup<'a> {
@@ -451,6 +504,7 @@ impl<'a, 'z> From<&'z ArgGroup<'a>> for ArgGroup<'a> {
             requires: g.requires.clone(),
             conflicts: g.conflicts.clone(),
             multiple: g.multiple,
+            heading: g.heading,
         }
     }
 }
@@ -458,49 +512,11 @@ impl<'a, 'z> From<&'z ArgGroup<'a>> for ArgGroup<'a> {
 #[cfg(feature = "yaml")]
 impl<'a> From<&'a BTreeMap<Yaml, Yaml>> for ArgGroup<'a> {
     fn from(b: &'a BTreeMap<Yaml, Yaml>) -> Self {
-        // We WANT this to panic on error...so expect() is good.
-        let mut a = ArgGroup::default();
-        let group_settings = if b.len() == 1 {
-            let name_yml = b.keys().nth(0).expect("failed to get name");
-            let name_str = name_yml.as_str().expect("failed to convert arg YAML name to str");
-            a.name = name_str;
-            b.get(name_yml)
-                .expect("failed to get name_str")
-                .as_hash()
-                .expect("failed to convert to a hash")
-        } else {
-            b
-        };
-
-        for (k, v) in group_settings.iter() {
-            a = match k.as_str().unwrap() {
-                "required" => a.required(v.as_bool().unwrap()),
-                "multiple" => a.multiple(v.as_bool().unwrap()),
-                "args" => yaml_vec_or_str!(v, a, arg),
-                "arg" => {
-                    if let Some(ys) = v.as_str() {
-                        a = a.arg(ys);
-                    }
-                    a
-                }
-                "requires" => yaml_vec_or_str!(v, a, requires),
-                "conflicts_with" => yaml_vec_or_str!(v, a, conflicts_with),
-                "name" => {
-                    if let Some(ys) = v.as_str() {
-                        a.name = ys;
-                    }
-                    a
-                }
-                s => {
-                    panic!("Unknown ArgGroup setting '{}' in YAML file for \
-                             ArgGroup '{}'",
-                           s,
-                           a.name)
-                }
-            }
+        // We WANT this to panic on error
+        match loader::group_from_yaml(b) {
+            Ok(g) => g,
+            Err(e) => panic!("{}", e.message),
         }
-
-        a
     }
 }
 
@@ -629,6 +645,7 @@ impl<'a> Clone for ArgGroup<'a> {
             requires: self.requires.clone(),
             conflicts: self.conflicts.clone(),
             multiple: self.multiple,
+            heading: self.heading,
         }
     }
 }
@@ -7,9 +7,8 @@ pub use self::group::ArgGroup;
 pub use self::matched_arg::MatchedArg;
 pub use self::settings::{ArgFlags, ArgSettings};
 pub use self::subcommand::SubCommand;
+pub use self::value_hint::ValueHint;
 
-#[macro_use]
-mod macros;
 mod arg;
 pub mod any_arg;
 mod arg_matches;
@@ -19,3 +18,4 @@ mod arg_builder;
 mod matched_arg;
 mod group;
 pub mod settings;
+mod value_hint;
@@ -0,0 +1,95 @@
+// Std
+use std::ascii::AsciiExt;
+use std::str::FromStr;
+
+/// Describes the kind of value an argument expects, which the shell completion generators use to
+/// offer native completions (i.e. file paths, host names, etc.) for that value.
+///
+/// **NOTE:** [`Arg::possible_values`] always take precedence over a value hint.
+///
+/// # Examples
+///
+/// ```rust
+/// # use clap::{App, Arg, ValueHint};
+/// App::new("prog")
+///     .arg(Arg::with_name("output")
+///         .long("output")
+///         .takes_value(true)
+///         .value_hint(ValueHint::FilePath))
+/// # ;
+/// ```
+/// [`Arg::possible_values`]: ./struct.Arg.html#method.possible_values
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum ValueHint {
+    /// The value is the path of a file
+    FilePath,
+    /// The value is the path of a directory
+    DirPath,
+    /// The value is the path of an executable file
+    ExecutablePath,
+    /// The value is a host name, such as `example.com`
+    Hostname,
+    /// The value is the name of a user on the system
+    Username,
+    /// The value is a URL, such as `https://example.com`
+    Url,
+    /// The value (and all remaining values) are a command followed by its arguments
+    ///
+    /// **NOTE:** This is most useful for the last positional argument with
+    /// [`Arg::multiple(true)`], such as the command run by `sudo` or `env`
+    /// [`Arg::multiple(true)`]: ./struct.Arg.html#method.multiple
+    CommandWithArgs,
+    /// The value is free-form, and no completion should be offered
+    Other,
+}
+
+impl ValueHint {
+    /// A list of possible variants in `&'static str` form
+    pub fn variants() -> [&'static str; 8] {
+        ["filepath",
+         "dirpath",
+         "executablepath",
+         "hostname",
+         "username",
+         "url",
+         "commandwithargs",
+         "other"]
+    }
+}
+
+impl FromStr for ValueHint {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, <Self as FromStr>::Err> {
+        match &*s.to_ascii_lowercase() {
+            "filepath" => Ok(ValueHint::FilePath),
+            "dirpath" => Ok(ValueHint::DirPath),
+            "executablepath" => Ok(ValueHint::ExecutablePath),
+            "hostname" => Ok(ValueHint::Hostname),
+            "username" => Ok(ValueHint::Username),
+            "url" => Ok(ValueHint::Url),
+            "commandwithargs" => Ok(ValueHint::CommandWithArgs),
+            "other" => Ok(ValueHint::Other),
+            _ => Err("unknown ValueHint, cannot convert from str".to_owned()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ValueHint;
+
+    #[test]
+    fn value_hint_fromstr() {
+        assert_eq!("filepath".parse::<ValueHint>().unwrap(), ValueHint::FilePath);
+        assert_eq!("DirPath".parse::<ValueHint>().unwrap(), ValueHint::DirPath);
+        assert_eq!("executablepath".parse::<ValueHint>().unwrap(),
+                   ValueHint::ExecutablePath);
+        assert_eq!("hostname".parse::<ValueHint>().unwrap(), ValueHint::Hostname);
+        assert_eq!("username".parse::<ValueHint>().unwrap(), ValueHint::Username);
+        assert_eq!("url".parse::<ValueHint>().unwrap(), ValueHint::Url);
+        assert_eq!("commandwithargs".parse::<ValueHint>().unwrap(),
+                   ValueHint::CommandWithArgs);
+        assert_eq!("other".parse::<ValueHint>().unwrap(), ValueHint::Other);
+        assert!("hahahaha".parse::<ValueHint>().is_err());
+    }
+}
@@ -3,7 +3,7 @@ use std::io::Write;
 
 // Internal
 use app::parser::Parser;
-use args::{ArgSettings, OptBuilder};
+use args::{ArgSettings, OptBuilder, ValueHint};
 use completions;
 
 pub struct BashGen<'a, 'b>
@@ -42,7 +42,7 @@ impl<'a, 'b> BashGen<'a, 'b> {
         {name})
             opts=\"{name_opts}\"
             if [[ ${{cur}} == -* || ${{COMP_CWORD}} -eq 1 ]] ; then
-                COMPREPLY=( $(compgen -W \"${{opts}}\" -- ${{cur}}) )
+                COMPREPLY=( $(compgen -W \"${{opts}}\" -- ${{cur}}){name_pos_vals} )
                 return 0
             fi
             case \"${{prev}}\" in
@@ -51,7 +51,7 @@ impl<'a, 'b> BashGen<'a, 'b> {
                     COMPREPLY=()
                     ;;
             esac
-            COMPREPLY=( $(compgen -W \"${{opts}}\" -- ${{cur}}) )
+            COMPREPLY=( $(compgen -W \"${{opts}}\" -- ${{cur}}){name_pos_vals} )
             return 0
             ;;
         {subcmd_details}
@@ -64,6 +64,8 @@ complete -F _{name} -o bashdefault -o default {name}
                    name_opts = self.all_options_for_path(self.p.meta.bin_name.as_ref().unwrap()),
                    name_opts_details =
                        self.option_details_for_path(self.p.meta.bin_name.as_ref().unwrap()),
+                   name_pos_vals =
+                       self.positional_vals_for_path(self.p.meta.bin_name.as_ref().unwrap()),
                    subcmds = self.all_subcommands(),
                    subcmd_details = self.subcommand_details())
                .as_bytes());
@@ -98,7 +100,7 @@ complete -F _{name} -o bashdefault -o default {name}
         {subcmd})
             opts=\"{sc_opts}\"
             if [[ ${{cur}} == -* || ${{COMP_CWORD}} -eq {level} ]] ; then
-                COMPREPLY=( $(compgen -W \"${{opts}}\" -- ${{cur}}) )
+                COMPREPLY=( $(compgen -W \"${{opts}}\" -- ${{cur}}){pos_vals} )
                 return 0
             fi
             case \"${{prev}}\" in
@@ -107,14 +109,15 @@ complete -F _{name} -o bashdefault -o default {name}
                     COMPREPLY=()
                     ;;
             esac
-            COMPREPLY=( $(compgen -W \"${{opts}}\" -- ${{cur}}) )
+            COMPREPLY=( $(compgen -W \"${{opts}}\" -- ${{cur}}){pos_vals} )
             return 0
             ;;",
                                   subcmd_dets,
                                   subcmd = sc.replace("-", "__"),
                                   sc_opts = self.all_options_for_path(&*sc),
                                   level = sc.split("__").map(|_| 1).fold(0, |acc, n| acc + n),
-                                  opts_details = self.option_details_for_path(&*sc));
+                                  opts_details = self.option_details_for_path(&*sc),
+                                  pos_vals = self.positional_vals_for_path(&*sc));
         }
 
         subcmd_dets
@@ -153,6 +156,35 @@ complete -F _{name} -o bashdefault -o default {name}
         opts
     }
 
+    // The script doesn't count positional arguments, so the values of all of them are offered
+    // along with the options and subcommands
+    fn positional_vals_for_path(&self, path: &str) -> String {
+        debugln!("BashGen::positional_vals_for_path: path={}", path);
+        let mut p = self.p;
+        for sc in path.split("__").skip(1) {
+            debugln!("BashGen::positional_vals_for_path:iter: sc={}", sc);
+            p = &find_subcmd!(p, sc).unwrap().p;
+        }
+        let mut vals: Vec<String> = vec![];
+        for pos in p.positionals().filter(|pos| pos.b.deprecated.is_none()) {
+            let v = if let Some(pv) = pos.v.possible_vals.as_ref() {
+                format!(" $(compgen -W \"{}\" -- ${{cur}})", pv.join(" "))
+            } else if let Some(compgen) = pos.v.value_hint.and_then(compgen_for) {
+                format!(" $(compgen {} -- ${{cur}})", compgen)
+            } else {
+                continue;
+            };
+            if !vals.contains(&v) {
+                vals.push(v);
+            }
+        }
+        // The directories are among the files already
+        if vals.iter().any(|v| v.contains("compgen -f ")) {
+            vals.retain(|v| !v.contains("compgen -d "));
+        }
+        vals.concat()
+    }
+
     fn vals_for(&self, o: &OptBuilder) -> String {
         debugln!("BashGen::vals_for: o={}", o.b.name);
         use args::AnyArg;
@@ -161,6 +193,9 @@ complete -F _{name} -o bashdefault -o default {name}
         if let Some(vals) = o.possible_vals() {
             needs_quotes = false;
             ret = format!("$(compgen -W \"{}\" -- ${{cur}})", vals.join(" "));
+        } else if let Some(compgen) = o.value_hint().and_then(compgen_for) {
+            needs_quotes = false;
+            ret = format!("$(compgen {} -- ${{cur}})", compgen);
         } else if let Some(vec) = o.val_names() {
             let mut it = vec.iter().peekable();
             while let Some((_, val)) = it.next() {
@@ -202,21 +237,36 @@ complete -F _{name} -o bashdefault -o default {name}
             debugln!("BashGen::all_options_for_path:iter: sc={}", sc);
             p = &find_subcmd!(p, sc).unwrap().p;
         }
-        let mut opts = shorts!(p).fold(String::new(), |acc, s| format!("{} -{}", acc, s));
+        // Deprecated arguments and subcommands aren't offered, though the options still complete
+        // their values in `option_details_for_path`
+        let switches: Vec<_> = p.flags()
+            .filter(|f| f.b.deprecated.is_none())
+            .map(|f| &f.s)
+            .chain(p.opts().filter(|o| o.b.deprecated.is_none()).map(|o| &o.s))
+            .collect();
+        let mut opts = switches.iter()
+            .filter_map(|s| s.short)
+            .fold(String::new(), |acc, s| format!("{} -{}", acc, s));
         opts = format!("{} {}",
                        opts,
-                       longs!(p).fold(String::new(), |acc, l| format!("{} --{}", acc, l)));
+                       switches.iter()
+                           .filter_map(|s| s.long)
+                           .fold(String::new(), |acc, l| format!("{} --{}", acc, l)));
         opts = format!("{} {}",
                        opts,
                        p.positionals
                            .values()
+                           .filter(|p| p.b.deprecated.is_none())
                            .fold(String::new(), |acc, p| format!("{} {}", acc, p)));
+        let subcommands: Vec<_> = p.subcommands
+            .iter()
+            .filter(|sc| sc.p.meta.deprecated.is_none())
+            .collect();
         opts = format!("{} {}",
                        opts,
-                       p.subcommands
-                           .iter()
+                       subcommands.iter()
                            .fold(String::new(), |acc, s| format!("{} {}", acc, s.p.meta.name)));
-        for sc in &p.subcommands {
+        for sc in subcommands {
             if let Some(ref aliases) = sc.p.meta.aliases {
                 opts = format!("{} {}",
                                opts,
@@ -228,3 +278,16 @@ complete -F _{name} -o bashdefault -o default {name}
         opts
     }
 }
+
+// Maps a value hint to the `compgen` action which produces native completions for it, or `None`
+// when bash has no builtin way to complete that kind of value
+fn compgen_for(hint: ValueHint) -> Option<&'static str> {
+    match hint {
+        ValueHint::FilePath | ValueHint::ExecutablePath => Some("-f"),
+        ValueHint::DirPath => Some("-d"),
+        ValueHint::CommandWithArgs => Some("-c"),
+        ValueHint::Hostname => Some("-A hostname"),
+        ValueHint::Username => Some("-u"),
+        ValueHint::Url | ValueHint::Other => None,
+    }
+}
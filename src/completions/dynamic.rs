@@ -0,0 +1,324 @@
+// Std
+use std::io::Write;
+
+// Internal
+use app::parser::Parser;
+use app::AppSettings as AS;
+use args::{AnyArg, ArgSettings, OptBuilder, ValueHint};
+use completions::Shell;
+
+/// The name of the hidden subcommand the generated scripts call back into
+pub const COMPLETE_SC: &'static str = "__complete";
+
+// When the last candidate is one of these, the scripts complete file or directory names
+// themselves, since the shell knows best how to do that
+const FILES: &'static str = ":files";
+const DIRS: &'static str = ":dirs";
+
+pub struct DynamicGen<'a, 'b>
+    where 'a: 'b
+{
+    p: &'b Parser<'a, 'b>,
+}
+
+impl<'a, 'b> DynamicGen<'a, 'b> {
+    pub fn new(p: &'b Parser<'a, 'b>) -> Self { DynamicGen { p: p } }
+
+    // Writes a small script which asks the binary itself for the candidates by running
+    // `{name} __complete -- <words...>`, where the words are everything typed after the binary
+    // name up to and including the word being completed (which may be empty). Bash splits
+    // `--opt=val` into three words, which are put back together before calling the binary, and
+    // since bash only replaces the part after the `=` that prefix is removed from the candidates.
+    pub fn generate_to<W: Write>(&self, for_shell: Shell, buf: &mut W) {
+        let name = self.p.meta.bin_name.as_ref().unwrap();
+        let script = match for_shell {
+            Shell::Bash => {
+                format!("_{fn_name}() {{
+    local IFS=$'\\n'
+    local -a words candidates
+    local i f cur pre=\"\"
+    for (( i = 1; i <= COMP_CWORD; i++ )); do
+        if [[ ${{#words[@]}} -gt 0 && ( ${{COMP_WORDS[i]}} == \"=\" || ${{COMP_WORDS[i-1]}} == \"=\" ) ]]; then
+            words[${{#words[@]}}-1]+=\"${{COMP_WORDS[i]}}\"
+        else
+            words+=(\"${{COMP_WORDS[i]}}\")
+        fi
+    done
+    cur=\"${{words[${{#words[@]}}-1]}}\"
+    [[ $cur == -*=* ]] && pre=\"${{cur%%=*}}=\"
+
+    candidates=( $({name} {sc} -- \"${{words[@]}}\" 2>/dev/null) )
+    case \"${{candidates[*]: -1}}\" in
+        {files})
+            unset 'candidates[${{#candidates[@]}}-1]'
+            compopt -o filenames 2>/dev/null
+            for f in $(compgen -f -- \"${{cur#\"$pre\"}}\"); do candidates+=(\"$pre$f\"); done
+            ;;
+        {dirs})
+            unset 'candidates[${{#candidates[@]}}-1]'
+            compopt -o filenames 2>/dev/null
+            for f in $(compgen -d -- \"${{cur#\"$pre\"}}\"); do candidates+=(\"$pre$f\"); done
+            ;;
+    esac
+    COMPREPLY=( \"${{candidates[@]#\"${{cur%\"${{COMP_WORDS[COMP_CWORD]}}\"}}\"}}\" )
+    return 0
+}}
+
+complete -F _{fn_name} -o bashdefault -o default {name}
+",
+                        name = name,
+                        fn_name = name.replace("-", "__"),
+                        sc = COMPLETE_SC,
+                        files = FILES,
+                        dirs = DIRS)
+            }
+            Shell::Zsh => {
+                format!("#compdef {name}
+
+_{name}() {{
+    local -a candidates
+    local hint
+    candidates=(${{(f)\"$({name} {sc} -- \"${{(@)words[2,CURRENT]}}\" 2>/dev/null)\"}})
+    hint=$candidates[-1]
+    if [[ $hint == {files} || $hint == {dirs} ]]; then
+        candidates[-1]=()
+    fi
+    compadd -a candidates
+    case $hint in
+        {files}) compset -P '*='; _files ;;
+        {dirs}) compset -P '*='; _files -/ ;;
+    esac
+}}
+
+_{name} \"$@\"",
+                        name = name,
+                        sc = COMPLETE_SC,
+                        files = FILES,
+                        dirs = DIRS)
+            }
+            Shell::Fish => {
+                format!("function __fish_{fn_name}_complete
+    set -l cur (commandline -ct)
+    set -l words (commandline -opc)[2..-1] \"$cur\"
+    set -l candidates ({name} {sc} -- $words 2>/dev/null)
+    switch \"$candidates[-1]\"
+        case {files}
+            set -e candidates[-1]
+            set candidates $candidates (__fish_complete_path $cur)
+        case {dirs}
+            set -e candidates[-1]
+            set candidates $candidates (__fish_complete_directories $cur)
+    end
+    printf '%s\\n' $candidates
+end
+
+complete -c {name} -f -a \"(__fish_{fn_name}_complete)\"
+",
+                        name = name,
+                        fn_name = name.replace("-", "_"),
+                        sc = COMPLETE_SC,
+                        files = FILES,
+                        dirs = DIRS)
+            }
+            Shell::PowerShell => {
+                format!(r#"
+Register-ArgumentCompleter -Native -CommandName '{name}' -ScriptBlock {{
+    param($wordToComplete, $commandAst, $cursorPosition)
+
+    $words = @($commandAst.CommandElements |
+        Select-Object -Skip 1 |
+        ?{{ $_.Extent.EndOffset -lt $cursorPosition }} |
+        %{{ $_.ToString() }})
+    $words += $wordToComplete
+
+    # Returning nothing lets PowerShell complete paths instead
+    & '{name}' {sc} -- @words 2>$null |
+        ?{{ $_ -ne '{files}' -and $_ -ne '{dirs}' }} |
+        %{{ New-Object System.Management.Automation.CompletionResult $_, $_, 'ParameterValue', $_ }}
+}}
+"#,
+                        name = name,
+                        sc = COMPLETE_SC,
+                        files = FILES,
+                        dirs = DIRS)
+            }
+            Shell::Elvish => {
+                format!("
+set edit:completion:arg-completer[{name}] = {{|@words|
+    {name} {sc} -- $@words[1..] 2>/dev/null | from-lines | each {{|c|
+        if (or (eq $c {files}) (eq $c {dirs})) {{
+            edit:complete-filename $words[-1]
+        }} else {{
+            put $c
+        }}
+    }}
+}}
+",
+                        name = name,
+                        sc = COMPLETE_SC,
+                        files = FILES,
+                        dirs = DIRS)
+            }
+        };
+        w!(buf, script.as_bytes());
+    }
+}
+
+// Computes the completion candidates for the last of `words`, which are the words of a partially
+// typed command line following the binary name. The preceding words are used to find which
+// (sub)command is being completed, and whether the last word is the value of an option or a
+// positional argument.
+pub fn complete(p: &Parser, words: &[String]) -> Vec<String> {
+    debugln!("dynamic::complete: words={:?}", words);
+    let (cur, prev) = match words.split_last() {
+        Some((cur, prev)) => (&**cur, prev),
+        None => ("", &[][..]),
+    };
+
+    let mut p = p;
+    let mut pending: Option<&OptBuilder> = None;
+    let mut pos_counter = 1;
+    let mut trailing = false;
+    for w in prev {
+        if pending.take().is_some() {
+            continue;
+        }
+        if trailing {
+            pos_counter += 1;
+            continue;
+        }
+        if w == "--" {
+            trailing = true;
+            continue;
+        }
+        if w.starts_with("--") {
+            if !w.contains('=') {
+                pending = find_long_opt(p, &w[2..]);
+            }
+            continue;
+        }
+        if w.starts_with('-') && w.len() > 1 {
+            // In a stack of shorts such as -abo, an option consumes the rest of the word as its
+            // value, so only an option in the last position still needs one
+            for (i, c) in w[1..].char_indices() {
+                if let Some(o) = p.opts.iter().find(|o| o.s.short == Some(c)) {
+                    if i + c.len_utf8() == w.len() - 1 {
+                        pending = Some(o);
+                    }
+                    break;
+                }
+            }
+            continue;
+        }
+        if let Some(sc) = p.subcommands.iter().find(|sc| {
+            sc.p.meta.name == *w ||
+            sc.p.meta.aliases.as_ref().map_or(false, |a| a.iter().any(|&(n, _)| n == w))
+        }) {
+            debugln!("dynamic::complete: found subcommand {}", sc.p.meta.name);
+            p = &sc.p;
+            pos_counter = 1;
+            continue;
+        }
+        pos_counter += 1;
+    }
+
+    // Deprecated arguments and subcommands still count above, so that the words which follow
+    // them are understood, but they're never offered
+    if let Some(o) = pending {
+        debugln!("dynamic::complete: completing value of {}", o.b.name);
+        return values_for(o, cur);
+    }
+    if !trailing && cur.starts_with("--") {
+        if let Some(i) = cur.find('=') {
+            return find_long_opt(p, &cur[2..i]).map_or(vec![], |o| {
+                values_for(o, &cur[i + 1..])
+                    .into_iter()
+                    .map(|v| if v == FILES || v == DIRS {
+                        v
+                    } else {
+                        format!("{}{}", &cur[..i + 1], v)
+                    })
+                    .collect()
+            });
+        }
+        return switches_of(p).into_iter().filter(|s| s.starts_with(cur)).collect();
+    }
+    if !trailing && cur.starts_with('-') {
+        return switches_of(p).into_iter().filter(|s| s.starts_with(cur)).collect();
+    }
+
+    let mut ret = vec![];
+    if !trailing {
+        for sc in p.subcommands
+            .iter()
+            .filter(|sc| !sc.p.is_set(AS::Hidden) && sc.p.meta.deprecated.is_none()) {
+            ret.push(sc.p.meta.name.clone());
+            if let Some(ref aliases) = sc.p.meta.aliases {
+                ret.extend(aliases.iter().filter(|&&(_, vis)| vis).map(|&(n, _)| n.to_owned()));
+            }
+        }
+        ret.retain(|s| s.starts_with(cur));
+    }
+    let pos = p.positionals.get(pos_counter).or_else(|| {
+        p.positionals.values().last().and_then(|pos| if pos.is_set(ArgSettings::Multiple) {
+            Some(pos)
+        } else {
+            None
+        })
+    });
+    if let Some(pos) = pos {
+        debugln!("dynamic::complete: completing value of {}", pos.b.name);
+        ret.extend(values_for(pos, cur));
+    }
+    ret
+}
+
+fn find_long_opt<'a, 'b, 'p>(p: &'p Parser<'a, 'b>, l: &str) -> Option<&'p OptBuilder<'a, 'b>> {
+    p.opts.iter().find(|o| {
+        o.s.long == Some(l) ||
+        o.s.aliases.as_ref().map_or(false, |a| a.iter().any(|&(n, _)| n == l))
+    })
+}
+
+// All the visible shorts, longs and visible long aliases of the flags and options of `p` which
+// aren't deprecated
+fn switches_of(p: &Parser) -> Vec<String> {
+    let mut ret = vec![];
+    let flags = p.flags
+        .iter()
+        .filter(|f| !f.is_set(ArgSettings::Hidden) && f.b.deprecated.is_none())
+        .map(|f| &f.s);
+    let opts = p.opts
+        .iter()
+        .filter(|o| !o.is_set(ArgSettings::Hidden) && o.b.deprecated.is_none())
+        .map(|o| &o.s);
+    for s in flags.chain(opts) {
+        if let Some(l) = s.long {
+            ret.push(format!("--{}", l));
+        }
+        if let Some(ref aliases) = s.aliases {
+            ret.extend(aliases.iter().filter(|&&(_, vis)| vis).map(|&(n, _)| format!("--{}", n)));
+        }
+        if let Some(c) = s.short {
+            ret.push(format!("-{}", c));
+        }
+    }
+    ret
+}
+
+fn values_for<'a, 'b, A: AnyArg<'a, 'b>>(a: &A, cur: &str) -> Vec<String> {
+    if a.deprecated().is_some() {
+        vec![]
+    } else if let Some(pv) = a.possible_vals() {
+        pv.iter().filter(|v| v.starts_with(cur)).map(|v| v.to_string()).collect()
+    } else if let Some(f) = a.completer() {
+        f(cur)
+    } else {
+        match a.value_hint() {
+            Some(ValueHint::FilePath) |
+            Some(ValueHint::ExecutablePath) => vec![FILES.to_owned()],
+            Some(ValueHint::DirPath) => vec![DIRS.to_owned()],
+            _ => vec![],
+        }
+    }
+}
@@ -0,0 +1,168 @@
+// Std
+use std::io::Write;
+
+// Internal
+use app::parser::Parser;
+use app::AppSettings as AS;
+use args::{AnyArg, ArgSettings};
+use INTERNAL_ERROR_MSG;
+
+pub struct ElvishGen<'a, 'b>
+    where 'a: 'b
+{
+    p: &'b Parser<'a, 'b>,
+}
+
+impl<'a, 'b> ElvishGen<'a, 'b> {
+    pub fn new(p: &'b Parser<'a, 'b>) -> Self { ElvishGen { p: p } }
+
+    pub fn generate_to<W: Write>(&self, buf: &mut W) {
+        let bin_name = self.p.meta.bin_name.as_ref().expect(INTERNAL_ERROR_MSG);
+
+        let mut completions = String::new();
+        let mut values = String::new();
+        generate_inner(self.p, bin_name, &mut completions, &mut values);
+
+        let result = format!(r#"
+use builtin;
+use str;
+
+set edit:completion:arg-completer['{bin_name}'] = {{|@words|
+    fn spaces {{|n|
+        builtin:repeat $n ' ' | str:join ''
+    }}
+    fn cand {{|text desc|
+        edit:complex-candidate $text &display=$text' '(spaces (- 14 (wcswidth $text)))$desc
+    }}
+    var completions = [{completions}
+    ]
+    var values = {values}
+    var command = '{bin_name}'
+    for word $words[1..-1] {{
+        if (has-key $completions $command';'$word) {{
+            set command = $command';'$word
+        }}
+    }}
+    if (and (> (count $words) 2) (has-key $values $command';'$words[-2])) {{
+        $values[$command';'$words[-2]]
+    }} else {{
+        $completions[$command]
+    }}
+}}
+"#,
+                             bin_name = escape_string(bin_name),
+                             completions = completions,
+                             values = if values.is_empty() {
+                                 String::from("[&]")
+                             } else {
+                                 format!("[{}\n    ]", values)
+                             });
+
+        w!(buf, result.as_bytes());
+    }
+}
+
+// Escapes a string for use inside a single quoted elvish string
+fn escape_string(s: &str) -> String { s.replace("'", "''") }
+
+fn get_help<'a, 'b, A: AnyArg<'a, 'b>>(a: &A) -> String {
+    escape_string(a.help().unwrap_or("").lines().next().unwrap_or(""))
+}
+
+// Writes the candidates of the command at `path` (the `;` separated names of the command and its
+// parents) to `completions`, and the possible values of its options to `values`, then recurses
+// into the subcommands. Aliased subcommands are written out once for each alias, so that walking
+// the words on the command line finds them under either name.
+fn generate_inner(p: &Parser, path: &str, completions: &mut String, values: &mut String) {
+    debugln!("ElvishGen::generate_inner: path={}", path);
+    let mut cands = String::new();
+
+    for o in p.opts().filter(|o| !o.is_set(ArgSettings::Hidden)) {
+        let mut switches = vec![];
+        if let Some(s) = o.short() {
+            switches.push(format!("-{}", s));
+        }
+        if let Some(l) = o.long() {
+            switches.push(format!("--{}", l));
+        }
+        for sw in switches {
+            // A deprecated option isn't offered, but its values still are once it's typed
+            if o.deprecated().is_none() {
+                cands.push_str(&format!("
+            cand '{}' '{}'",
+                                        sw,
+                                        get_help(o)));
+            }
+            if let Some(pv) = o.possible_vals() {
+                let vals = pv.iter()
+                    .map(|v| format!("
+            cand '{}' ''", escape_string(v)))
+                    .collect::<String>();
+                values.push_str(&format!("
+        &'{}'= {{{}
+        }}",
+                                         escape_string(&format!("{};{}", path, sw)),
+                                         vals));
+            }
+        }
+    }
+
+    for f in p.flags().filter(|f| !f.is_set(ArgSettings::Hidden) && f.deprecated().is_none()) {
+        if let Some(s) = f.short() {
+            cands.push_str(&format!("
+            cand '-{}' '{}'",
+                                    s,
+                                    get_help(f)));
+        }
+        if let Some(l) = f.long() {
+            cands.push_str(&format!("
+            cand '--{}' '{}'",
+                                    l,
+                                    get_help(f)));
+        }
+    }
+
+    for pos in p.positionals()
+        .filter(|p| !p.is_set(ArgSettings::Hidden) && p.deprecated().is_none()) {
+        if let Some(pv) = pos.possible_vals() {
+            for v in pv {
+                cands.push_str(&format!("
+            cand '{}' '{}'",
+                                        escape_string(v),
+                                        get_help(pos)));
+            }
+        }
+    }
+
+    let subcommands: Vec<_> = p.subcommands().filter(|sc| !sc.p.is_set(AS::Hidden)).collect();
+    for sc in subcommands.iter().filter(|sc| sc.p.meta.deprecated.is_none()) {
+        let about = escape_string(sc.p.meta.about.unwrap_or("").lines().next().unwrap_or(""));
+        cands.push_str(&format!("
+            cand '{}' '{}'",
+                                escape_string(&sc.p.meta.name),
+                                about));
+        if let Some(ref aliases) = sc.p.meta.aliases {
+            for &(alias, _) in aliases.iter().filter(|&&(_, vis)| vis) {
+                cands.push_str(&format!("
+            cand '{}' '{}'",
+                                        escape_string(alias),
+                                        about));
+            }
+        }
+    }
+
+    completions.push_str(&format!("
+        &'{}'= {{{}
+        }}",
+                                  escape_string(path),
+                                  cands));
+
+    for sc in &subcommands {
+        generate_inner(&sc.p, &format!("{};{}", path, sc.p.meta.name), completions, values);
+        if let Some(ref aliases) = sc.p.meta.aliases {
+            for &(alias, _) in aliases {
+                generate_inner(&sc.p, &format!("{};{}", path, alias), completions, values);
+            }
+        }
+    }
+}
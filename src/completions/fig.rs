@@ -0,0 +1,198 @@
+// Std
+use std::collections::BTreeMap;
+use std::io::Write;
+
+// Third Party
+use serde_json::{self, Map, Value};
+
+// Internal
+use app::parser::Parser;
+use app::AppSettings as AS;
+use args::{AnyArg, ArgSettings, ValueHint};
+use completions;
+use INTERNAL_ERROR_MSG;
+
+pub struct FigGen<'a, 'b>
+    where 'a: 'b
+{
+    p: &'b Parser<'a, 'b>,
+}
+
+impl<'a, 'b> FigGen<'a, 'b> {
+    pub fn new(p: &'b Parser<'a, 'b>) -> Self { FigGen { p: p } }
+
+    pub fn generate_to<W: Write>(&self, buf: &mut W) {
+        let bin_name = self.p.meta.bin_name.as_ref().expect(INTERNAL_ERROR_MSG);
+        let mut spec = gen_command(self.p, vec![Value::from(&**bin_name)]);
+
+        // Resolves each subcommand path to its parser, keyed by the position of every subcommand
+        // along the way so that siblings keep the order they were defined in
+        let mut paths = completions::get_all_subcommand_paths(self.p, true);
+        paths.sort();
+        paths.dedup();
+        let mut cmds = vec![];
+        'paths: for path in paths {
+            let mut sc = self.p;
+            let mut key = vec![];
+            for name in path.split("__").skip(1) {
+                sc = match sc.subcommands.iter().position(|s| s.p.meta.name == name) {
+                    Some(i) if !sc.subcommands[i].p.is_set(AS::Hidden) => {
+                        key.push(i);
+                        &sc.subcommands[i].p
+                    }
+                    // The path of an alias, or of a hidden subcommand
+                    _ => continue 'paths,
+                };
+            }
+            cmds.push((key, sc));
+        }
+        cmds.sort_by(|a, b| a.0.cmp(&b.0));
+
+        // Every subcommand comes after its parent, so going backwards each one is complete by the
+        // time it's handed to its parent
+        let mut children: BTreeMap<Vec<usize>, Vec<Value>> = BTreeMap::new();
+        for (key, sc) in cmds.into_iter().rev() {
+            let mut names = vec![Value::from(&*sc.meta.name)];
+            if let Some(ref aliases) = sc.meta.aliases {
+                names.extend(aliases.iter()
+                    .filter(|&&(_, vis)| vis)
+                    .map(|&(n, _)| Value::from(n)));
+            }
+            let mut cmd = gen_command(sc, names);
+            add_subcommands(&mut cmd, children.remove(&key));
+            let parent = key[..key.len() - 1].to_vec();
+            children.entry(parent).or_insert_with(Vec::new).push(Value::Object(cmd));
+        }
+        add_subcommands(&mut spec, children.remove(&vec![]));
+
+        let out = serde_json::to_string_pretty(&Value::Object(spec))
+            .expect("failed to serialize the spec");
+        w!(buf, out.as_bytes());
+        w!(buf, b"\n");
+    }
+}
+
+// Adds the subcommands collected (in reverse) by `FigGen::generate_to`
+fn add_subcommands(cmd: &mut Map<String, Value>, subcommands: Option<Vec<Value>>) {
+    if let Some(mut subcommands) = subcommands {
+        subcommands.reverse();
+        cmd.insert("subcommands".to_owned(), Value::Array(subcommands));
+    }
+}
+
+// Describes the command `p`, known by `names`, leaving its subcommands to the caller
+fn gen_command(p: &Parser, names: Vec<Value>) -> Map<String, Value> {
+    debugln!("FigGen::gen_command: name={}", p.meta.name);
+    let mut ret = Map::new();
+    ret.insert("name".to_owned(), one_or_many(names));
+    if let Some(about) = p.meta.about {
+        ret.insert("description".to_owned(), Value::from(about));
+    }
+    // Fig still parses hidden subcommands and options, it just doesn't suggest them
+    if p.meta.deprecated.is_some() {
+        ret.insert("hidden".to_owned(), Value::from(true));
+    }
+
+    let mut options = vec![];
+    for f in p.flags().filter(|f| !f.is_set(ArgSettings::Hidden)) {
+        options.push(Value::Object(gen_option(f)));
+    }
+    for o in p.opts().filter(|o| !o.is_set(ArgSettings::Hidden)) {
+        let mut opt = gen_option(o);
+        opt.insert("args".to_owned(), Value::Object(gen_arg(o)));
+        options.push(Value::Object(opt));
+    }
+    if !options.is_empty() {
+        ret.insert("options".to_owned(), Value::Array(options));
+    }
+
+    let args: Vec<_> = p.positionals()
+        .filter(|pos| !pos.is_set(ArgSettings::Hidden))
+        .map(|pos| Value::Object(gen_arg(pos)))
+        .collect();
+    if !args.is_empty() {
+        ret.insert("args".to_owned(), one_or_many(args));
+    }
+
+    ret
+}
+
+// Describes the switches of a flag or option, leaving its value (if any) to the caller
+fn gen_option<'a, 'b, A: AnyArg<'a, 'b>>(a: &A) -> Map<String, Value> {
+    let mut names = vec![];
+    if let Some(s) = a.short() {
+        names.push(Value::from(format!("-{}", s)));
+    }
+    if let Some(l) = a.long() {
+        names.push(Value::from(format!("--{}", l)));
+    }
+    if let Some(aliases) = a.aliases() {
+        names.extend(aliases.into_iter().map(|n| Value::from(format!("--{}", n))));
+    }
+    let mut ret = Map::new();
+    ret.insert("name".to_owned(), one_or_many(names));
+    if let Some(h) = a.help() {
+        ret.insert("description".to_owned(), Value::from(h));
+    }
+    if a.is_set(ArgSettings::Required) {
+        ret.insert("isRequired".to_owned(), Value::from(true));
+    }
+    if a.is_set(ArgSettings::Multiple) {
+        ret.insert("isRepeatable".to_owned(), Value::from(true));
+    }
+    if a.deprecated().is_some() {
+        ret.insert("hidden".to_owned(), Value::from(true));
+    }
+    ret
+}
+
+// Describes the value taken by an option or positional argument
+fn gen_arg<'a, 'b, A: AnyArg<'a, 'b>>(a: &A) -> Map<String, Value> {
+    let name = a.val_names()
+        .and_then(|names| names.values().next())
+        .map_or(a.name(), |n| *n);
+    let mut ret = Map::new();
+    ret.insert("name".to_owned(), Value::from(name));
+    if !a.has_switch() {
+        if let Some(h) = a.help() {
+            ret.insert("description".to_owned(), Value::from(h));
+        }
+        if !a.is_set(ArgSettings::Required) {
+            ret.insert("isOptional".to_owned(), Value::from(true));
+        }
+        if a.is_set(ArgSettings::Multiple) {
+            ret.insert("isVariadic".to_owned(), Value::from(true));
+        }
+    } else if a.min_vals() == Some(0) {
+        ret.insert("isOptional".to_owned(), Value::from(true));
+    }
+    if a.deprecated().is_some() {
+        // Keeps its place among the positional arguments without suggesting anything
+    } else if let Some(pv) = a.possible_vals() {
+        ret.insert("suggestions".to_owned(),
+                   Value::Array(pv.iter().map(|&v| Value::from(v)).collect()));
+    } else {
+        match a.value_hint() {
+            Some(ValueHint::FilePath) |
+            Some(ValueHint::ExecutablePath) => {
+                ret.insert("template".to_owned(), Value::from("filepaths"));
+            }
+            Some(ValueHint::DirPath) => {
+                ret.insert("template".to_owned(), Value::from("folders"));
+            }
+            Some(ValueHint::CommandWithArgs) => {
+                ret.insert("isCommand".to_owned(), Value::from(true));
+            }
+            _ => (),
+        }
+    }
+    if let Some(d) = a.default_val() {
+        ret.insert("default".to_owned(), Value::from(d.to_string_lossy().into_owned()));
+    }
+    ret
+}
+
+// Fig accepts either a single value or an array of them for names and args
+fn one_or_many(mut v: Vec<Value>) -> Value {
+    if v.len() == 1 { v.remove(0) } else { Value::Array(v) }
+}
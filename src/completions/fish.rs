@@ -4,6 +4,7 @@ use std::io::Write;
 
 // Internal
 use app::parser::Parser;
+use args::ValueHint;
 
 pub struct FishGen<'a, 'b>
     where 'a: 'b
@@ -58,7 +59,7 @@ fn gen_fish_inner(root_command: &str, comp_gen: &FishGen, parent_cmds: &str, buf
                                  root_command,
                                  parent_cmds);
 
-    for option in comp_gen.p.opts() {
+    for option in comp_gen.p.opts().filter(|o| o.b.deprecated.is_none()) {
         let mut template = basic_template.clone();
         if let Some(data) = option.s.short {
             template.push_str(format!(" -s {}", data).as_str());
@@ -69,14 +70,30 @@ fn gen_fish_inner(root_command: &str, comp_gen: &FishGen, parent_cmds: &str, buf
         if let Some(data) = option.b.help {
             template.push_str(format!(" -d \"{}\"", data).as_str());
         }
-        if let Some(ref data) = option.v.possible_vals {
+        if let (Some(ref data), Some(ref helps)) = (option.v.possible_vals.as_ref(),
+                                                    option.v.pv_help.as_ref()) {
+            // `complete` expands the braces, and takes what follows a tab as the description
+            let vals: Vec<_> = data.iter()
+                .map(|pv| {
+                    let h = helps.iter().find(|&&(v, _)| v == *pv).map_or("", |&(_, h)| h);
+                    let h = h.replace('\\', "\\\\")
+                        .replace('\'', "\\'")
+                        .replace('"', "\\\"")
+                        .replace('$', "\\$");
+                    format!("{}\\t'{}'", pv, h)
+                })
+                .collect();
+            template.push_str(format!(" -r -f -a \"{{{}}}\"", vals.join(",")).as_str());
+        } else if let Some(ref data) = option.v.possible_vals {
             template.push_str(format!(" -r -f -a \"{}\"", data.join(" ")).as_str());
+        } else if let Some(hint) = option.v.value_hint {
+            template.push_str(value_completion_for(hint));
         }
         buffer.push_str(template.as_str());
         buffer.push_str("\n");
     }
 
-    for flag in comp_gen.p.flags() {
+    for flag in comp_gen.p.flags().filter(|f| f.b.deprecated.is_none()) {
         let mut template = basic_template.clone();
         if let Some(data) = flag.s.short {
             template.push_str(format!(" -s {}", data).as_str());
@@ -91,7 +108,24 @@ fn gen_fish_inner(root_command: &str, comp_gen: &FishGen, parent_cmds: &str, buf
         buffer.push_str("\n");
     }
 
-    for subcommand in &comp_gen.p.subcommands {
+    for pos in comp_gen.p.positionals().filter(|pos| pos.b.deprecated.is_none()) {
+        let mut template = basic_template.clone();
+        if let Some(ref data) = pos.v.possible_vals {
+            template.push_str(format!(" -f -a \"{}\"", data.join(" ")).as_str());
+        } else if let Some(hint) = pos.v.value_hint {
+            // A positional argument has no switch which `-r` could apply to
+            template.push_str(value_completion_for(hint).trim_left_matches(" -r"));
+        } else {
+            continue;
+        }
+        if let Some(data) = pos.b.help {
+            template.push_str(format!(" -d \"{}\"", data).as_str());
+        }
+        buffer.push_str(template.as_str());
+        buffer.push_str("\n");
+    }
+
+    for subcommand in comp_gen.p.subcommands().filter(|sc| sc.p.meta.deprecated.is_none()) {
         let mut template = basic_template.clone();
         template.push_str(" -f");
         template.push_str(format!(" -a \"{}\"", &subcommand.p.meta.name).as_str());
@@ -114,3 +148,16 @@ fn gen_fish_inner(root_command: &str, comp_gen: &FishGen, parent_cmds: &str, buf
         gen_fish_inner(root_command, &sub_comp_gen, &sub_parent_cmds, buffer);
     }
 }
+
+// Maps a value hint to the `complete` switches which make fish offer native completions for it
+fn value_completion_for(hint: ValueHint) -> &'static str {
+    match hint {
+        ValueHint::FilePath => " -r -a \"(__fish_complete_path)\"",
+        ValueHint::DirPath => " -r -f -a \"(__fish_complete_directories)\"",
+        ValueHint::ExecutablePath => " -r -a \"(__fish_complete_path)\"",
+        ValueHint::Hostname => " -r -f -a \"(__fish_print_hostnames)\"",
+        ValueHint::Username => " -r -f -a \"(__fish_complete_users)\"",
+        ValueHint::CommandWithArgs => " -r -f -a \"(__fish_complete_command)\"",
+        ValueHint::Url | ValueHint::Other => " -r -f",
+    }
+}
@@ -4,7 +4,11 @@ mod bash;
 mod fish;
 mod zsh;
 mod powershell;
+mod elvish;
+#[cfg(feature = "serde")]
+mod fig;
 mod shell;
+pub mod dynamic;
 
 // Std
 use std::io::Write;
@@ -15,6 +19,10 @@ use self::bash::BashGen;
 use self::fish::FishGen;
 use self::zsh::ZshGen;
 use self::powershell::PowerShellGen;
+use self::elvish::ElvishGen;
+#[cfg(feature = "serde")]
+use self::fig::FigGen;
+use self::dynamic::DynamicGen;
 pub use self::shell::Shell;
 
 pub struct ComplGen<'a, 'b>
@@ -32,8 +40,16 @@ impl<'a, 'b> ComplGen<'a, 'b> {
             Shell::Fish => FishGen::new(self.p).generate_to(buf),
             Shell::Zsh => ZshGen::new(self.p).generate_to(buf),
             Shell::PowerShell => PowerShellGen::new(self.p).generate_to(buf),
+            Shell::Elvish => ElvishGen::new(self.p).generate_to(buf),
         }
     }
+
+    pub fn generate_dynamic<W: Write>(&self, for_shell: Shell, buf: &mut W) {
+        DynamicGen::new(self.p).generate_to(for_shell, buf)
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn generate_fig_spec<W: Write>(&self, buf: &mut W) { FigGen::new(self.p).generate_to(buf) }
 }
 
 // Gets all subcommands including child subcommands in the form of 'name' where the name
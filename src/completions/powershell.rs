@@ -4,6 +4,7 @@ use std::io::Write;
 
 // Internal
 use app::parser::Parser;
+use args::{AnyArg, ValueHint};
 use INTERNAL_ERROR_MSG;
 
 pub struct PowerShellGen<'a, 'b>
@@ -51,6 +52,11 @@ impl<'a, 'b> PowerShellGen<'a, 'b> {
                 }}
             }}
 
+        $previous = $commandAst.CommandElements |
+            ?{{ $_.Extent.EndOffset -lt $cursorPosition }} |
+            Select-Object -Last 1 |
+            %{{ $_.ToString() }}
+
         $completions = @()
 
         switch ($command) {{
@@ -98,23 +104,30 @@ fn generate_inner<'a, 'b, 'p>(p: &'p Parser<'a, 'b>, previous_command_name: &str
     };
 
     let mut completions = String::new();
-    for subcommand in &p.subcommands {
+    for subcommand in p.subcommands().filter(|sc| sc.p.meta.deprecated.is_none()) {
         completions.push_str(&format!("'{}', ", &subcommand.p.meta.name));
     }
-    for short in shorts!(p) {
+    let switches: Vec<_> = p.flags()
+        .filter(|f| f.b.deprecated.is_none())
+        .map(|f| &f.s)
+        .chain(p.opts().filter(|o| o.b.deprecated.is_none()).map(|o| &o.s))
+        .collect();
+    for short in switches.iter().filter_map(|s| s.short) {
         completions.push_str(&format!("'-{}', ", short));
     }
-    for long in longs!(p) {
+    for long in switches.iter().filter_map(|s| s.long) {
         completions.push_str(&format!("'--{}', ", long));
     }
 
     let mut subcommands_cases = format!(r"
-            '{}' {{
-                $completions = @({})
+            '{}' {{{}
+                $completions = @({}){}
             }}
 ",
                                         &command_name,
-                                        completions.trim_right_matches(", "));
+                                        value_cases_of(p),
+                                        completions.trim_right_matches(", "),
+                                        positional_values_of(p));
 
     for subcommand in &p.subcommands {
         let (subcommand_subcommands_detection_cases, subcommand_subcommands_cases) =
@@ -125,3 +138,88 @@ fn generate_inner<'a, 'b, 'p>(p: &'p Parser<'a, 'b>, previous_command_name: &str
 
     (subcommands_detection_cases, subcommands_cases)
 }
+
+// Completes the values of positional arguments that have a value hint PowerShell can natively
+// complete, along with the subcommands and switches. Positional arguments aren't counted, so this
+// applies to all of them.
+fn positional_values_of(p: &Parser) -> String {
+    debugln!("PowerShellGen::positional_values_of;");
+    const FILES: &'static str =
+        "[System.Management.Automation.CompletionCompleters]::CompleteFilename($wordToComplete)";
+    const DIRS: &'static str =
+        "[System.Management.Automation.CompletionCompleters]::CompleteFilename($wordToComplete) |
+                        ?{ $_.ResultType -eq 'ProviderContainer' }";
+    const COMMANDS: &'static str =
+        "[System.Management.Automation.CompletionCompleters]::CompleteCommand($wordToComplete)";
+    let mut actions = vec![];
+    for pos in p.positionals().filter(|pos| pos.b.deprecated.is_none()) {
+        let action = match pos.value_hint() {
+            Some(ValueHint::FilePath) |
+            Some(ValueHint::ExecutablePath) => FILES,
+            Some(ValueHint::DirPath) => DIRS,
+            Some(ValueHint::CommandWithArgs) => COMMANDS,
+            _ => continue,
+        };
+        if !actions.contains(&action) {
+            actions.push(action);
+        }
+    }
+    // The directories are among the files already
+    if actions.contains(&FILES) {
+        actions.retain(|&a| a != DIRS);
+    }
+    if actions.is_empty() {
+        return String::new();
+    }
+    format!("
+                if ($wordToComplete -notlike '-*') {{{}
+                }}",
+            actions.iter().map(|a| format!("
+                    {}", a)).collect::<String>())
+}
+
+// Generates a switch on the previous word which completes the values of options that have a
+// value hint PowerShell can natively complete. Returning nothing from the completer makes
+// PowerShell fall back to its own path completion.
+fn value_cases_of(p: &Parser) -> String {
+    debugln!("PowerShellGen::value_cases_of;");
+    let mut cases = String::new();
+    for o in p.opts().filter(|o| o.possible_vals().is_none()) {
+        let action = match o.value_hint() {
+            Some(ValueHint::FilePath) |
+            Some(ValueHint::ExecutablePath) => "return",
+            Some(ValueHint::DirPath) => {
+                "return Get-ChildItem -Directory -Path \"$wordToComplete*\" |
+                            %{ New-Object System.Management.Automation.CompletionResult $_.FullName, $_.Name, 'ProviderContainer', $_.FullName }"
+            }
+            Some(ValueHint::CommandWithArgs) => {
+                "return Get-Command -Name \"$wordToComplete*\" |
+                            %{ New-Object System.Management.Automation.CompletionResult $_.Name, $_.Name, 'Command', $_.Name }"
+            }
+            _ => continue,
+        };
+        if let Some(short) = o.short() {
+            cases.push_str(&format!("
+                    '-{}' {{
+                        {}
+                    }}",
+                                    short,
+                                    action));
+        }
+        if let Some(long) = o.long() {
+            cases.push_str(&format!("
+                    '--{}' {{
+                        {}
+                    }}",
+                                    long,
+                                    action));
+        }
+    }
+    if cases.is_empty() {
+        return cases;
+    }
+    format!("
+                switch ($previous) {{{}
+                }}",
+            cases)
+}
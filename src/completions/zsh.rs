@@ -6,7 +6,7 @@ use std::ascii::AsciiExt;
 // Internal
 use app::App;
 use app::parser::Parser;
-use args::{ArgSettings, AnyArg};
+use args::{ArgSettings, AnyArg, ValueHint};
 use completions;
 use INTERNAL_ERROR_MSG;
 
@@ -137,7 +137,7 @@ fn subcommands_and_args_of(p: &Parser) -> String {
     }
 
     // First the subcommands
-    for sc in p.subcommands() {
+    for sc in p.subcommands().filter(|sc| sc.p.meta.deprecated.is_none()) {
         debugln!("ZshGen::subcommands_and_args_of:iter: subcommand={}", sc.p.meta.name);
         add_sc(sc, &sc.p.meta.name, &mut ret);
         if let Some(ref v) = sc.p.meta.aliases {
@@ -148,7 +148,7 @@ fn subcommands_and_args_of(p: &Parser) -> String {
     }
 
     // Then the positional args
-    for arg in p.positionals() {
+    for arg in p.positionals().filter(|pos| pos.b.deprecated.is_none()) {
         debugln!("ZshGen::subcommands_and_args_of:iter: arg={}", arg.b.name);
         let a = format!("\"{name}:{help}\" \\", 
             name = arg.b.name.to_ascii_uppercase(), 
@@ -263,9 +263,11 @@ fn get_args_of(p: &Parser) -> String {
     let mut ret = vec![String::from("_arguments -s -S -C \\")];
     let opts = write_opts_of(p);
     let flags = write_flags_of(p);
-    let sc_or_a = if p.has_subcommands() || p.has_positionals() {
+    let sc_or_a = if p.has_subcommands() {
         format!("\"1:: :_{name}_commands\" \\", 
                 name = p.meta.bin_name.as_ref().unwrap().replace(" ", "__"))
+    } else if p.has_positionals() {
+        write_positionals_of(p)
     } else {
         String::new()
     };
@@ -310,13 +312,20 @@ fn write_opts_of(p: &Parser) -> String {
         } else {
             ""
         };
-        let pv = if let Some(pv_vec) = o.possible_vals() {
+        // `_arguments` still recognizes an option marked with `!`, but doesn't offer it
+        let not = if o.deprecated().is_some() { "!" } else { "" };
+        let pv = if let Some(pv_help) = o.possible_vals_help() {
+            format!(": :(({}))", values_with_help(o.possible_vals().unwrap_or(&[]), pv_help))
+        } else if let Some(pv_vec) = o.possible_vals() {
             format!(": :({})", pv_vec.join(" "))
+        } else if let Some(hint) = o.value_hint() {
+            format!(": :{}", action_for(hint))
         } else {
             String::new()
         };
         if let Some(short) = o.short() {
-            let s = format!("\"{conflicts}{multiple}-{arg}+[{help}]{possible_values}\" \\",
+            let s = format!("\"{not}{conflicts}{multiple}-{arg}+[{help}]{possible_values}\" \\",
+                not = not,
                 conflicts = conflicts,
                 multiple = multiple,
                 arg = short,
@@ -327,7 +336,8 @@ fn write_opts_of(p: &Parser) -> String {
             ret.push(s);
         }
         if let Some(long) = o.long() {
-            let l = format!("\"{conflicts}{multiple}--{arg}+[{help}]{possible_values}\" \\",
+            let l = format!("\"{not}{conflicts}{multiple}--{arg}+[{help}]{possible_values}\" \\",
+                not = not,
                 conflicts = conflicts,
                 multiple = multiple,
                 arg = long,
@@ -342,6 +352,61 @@ fn write_opts_of(p: &Parser) -> String {
     ret.join("\n")
 }
 
+// Without subcommands in the way, each positional argument gets its own spec, so that its values
+// are completed like those of an option:
+//    [index|*]:[name]:[action]
+//
+// Where an optional argument's index is followed by '::' and the last multiple argument is '*'.
+fn write_positionals_of(p: &Parser) -> String {
+    debugln!("write_positionals_of;");
+    let mut ret = vec![];
+    let last = p.positionals.len();
+    for (i, arg) in p.positionals().enumerate() {
+        debugln!("write_positionals_of:iter: arg={}", arg.b.name);
+        let pos = if i + 1 == last && arg.is_set(ArgSettings::Multiple) {
+            String::from("*:")
+        } else if arg.is_set(ArgSettings::Required) {
+            format!("{}:", arg.index)
+        } else {
+            format!("{}::", arg.index)
+        };
+        let action = if arg.deprecated().is_some() {
+            String::new()
+        } else if let Some(pv_help) = arg.possible_vals_help() {
+            format!("(({}))", values_with_help(arg.possible_vals().unwrap_or(&[]), pv_help))
+        } else if let Some(pv_vec) = arg.possible_vals() {
+            format!("({})", pv_vec.join(" "))
+        } else if let Some(hint) = arg.value_hint() {
+            action_for(hint).to_owned()
+        } else {
+            String::new()
+        };
+        let s = format!("\"{pos}{name}:{action}\" \\",
+            pos = pos,
+            name = arg.b.name.to_ascii_uppercase(),
+            action = action);
+
+        debugln!("write_positionals_of:iter: Wrote...{}", &*s);
+        ret.push(s);
+    }
+
+    ret.join("\n")
+}
+
+// Maps a value hint to the zsh completion function (or action) which completes that kind of value
+fn action_for(hint: ValueHint) -> &'static str {
+    match hint {
+        ValueHint::FilePath => "_files",
+        ValueHint::DirPath => "_files -/",
+        ValueHint::ExecutablePath => "_absolute_command_paths",
+        ValueHint::Hostname => "_hosts",
+        ValueHint::Username => "_users",
+        ValueHint::Url => "_urls",
+        ValueHint::CommandWithArgs => "_cmdambivalent",
+        ValueHint::Other => "( )",
+    }
+}
+
 fn write_flags_of(p: &Parser) -> String {
     debugln!("write_flags_of;");
     let mut ret = vec![];
@@ -360,8 +425,10 @@ fn write_flags_of(p: &Parser) -> String {
         } else {
             ""
         };
+        let not = if f.deprecated().is_some() { "!" } else { "" };
         if let Some(short) = f.short() {
-            let s = format!("\"{conflicts}{multiple}-{arg}[{help}]\" \\",
+            let s = format!("\"{not}{conflicts}{multiple}-{arg}[{help}]\" \\",
+                not = not,
                 multiple = multiple,
                 conflicts = conflicts,
                 arg = short,
@@ -372,7 +439,8 @@ fn write_flags_of(p: &Parser) -> String {
         }
 
         if let Some(long) = f.long() {
-            let l = format!("\"{conflicts}{multiple}--{arg}[{help}]\" \\",
+            let l = format!("\"{not}{conflicts}{multiple}--{arg}[{help}]\" \\",
+                not = not,
                 conflicts = conflicts,
                 multiple = multiple,
                 arg = long,
@@ -385,3 +453,21 @@ fn write_flags_of(p: &Parser) -> String {
 
     ret.join("\n")
 }
+
+// The `((value\:"description" ...))` action of `_arguments`, escaped for the double quoted spec
+fn values_with_help(pvs: &[&str], helps: &[(&str, &str)]) -> String {
+    pvs.iter()
+        .map(|pv| match helps.iter().find(|&&(v, _)| v == *pv) {
+            Some(&(_, h)) => {
+                format!("{}\\:\\\"{}\\\"",
+                        pv,
+                        h.replace('\\', "\\\\")
+                            .replace('"', "\\\\\\\"")
+                            .replace('`', "\\`")
+                            .replace('$', "\\$"))
+            }
+            None => pv.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
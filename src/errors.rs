@@ -8,6 +8,7 @@ use std::process;
 use std::result::Result as StdResult;
 
 // Internal
+use app::pager;
 use args::{FlagBuilder, AnyArg};
 use fmt;
 use suggestions;
@@ -350,6 +351,24 @@ pub enum ErrorKind {
     /// ```
     VersionDisplayed,
 
+    /// Not a true "error" as it means the hidden `__complete` subcommand enabled by
+    /// [`AppSettings::DynamicCompletion`] was used. The completion candidates, one per line, are
+    /// the message and will be sent to `stdout`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clap::{App, Arg, AppSettings, ErrorKind};
+    /// let result = App::new("prog")
+    ///     .setting(AppSettings::DynamicCompletion)
+    ///     .arg(Arg::from_usage("--flag 'some flag'"))
+    ///     .get_matches_from_safe(vec!["prog", "__complete", "--", "--f"]);
+    /// assert!(result.is_err());
+    /// assert_eq!(result.unwrap_err().kind, ErrorKind::CompletionsDisplayed);
+    /// ```
+    /// [`AppSettings::DynamicCompletion`]: ./enum.AppSettings.html#variant.DynamicCompletion
+    CompletionsDisplayed,
+
     /// Occurs when using the [`value_t!`] and [`values_t!`] macros to convert an argument value
     /// into type `T`, but the argument you requested wasn't used. I.e. you asked for an argument
     /// with name `config` to be converted, but `config` wasn't used by the user.
@@ -367,6 +386,15 @@ pub enum ErrorKind {
     /// [`Display`]: https://doc.rust-lang.org/std/fmt/trait.Display.html
     /// [Format error]: https://doc.rust-lang.org/std/fmt/struct.Error.html
     Format,
+
+    /// Occurs when loading an [`App`] from a data file (i.e. with [`App::try_from_yaml`]) which
+    /// has an unknown key, a value of the wrong type, or isn't valid at all. The first element
+    /// of [`Error::info`] is the path of the offending key, such as
+    /// `subcommands[2].args.verbose.multiple`, and the second is what went wrong.
+    /// [`App`]: ./struct.App.html
+    /// [`App::try_from_yaml`]: ./struct.App.html#method.try_from_yaml
+    /// [`Error::info`]: ./struct.Error.html#structfield.info
+    InvalidDefinition,
 }
 
 /// Command Line Argument Parser Error
@@ -378,6 +406,8 @@ pub struct Error {
     pub kind: ErrorKind,
     /// Any additional information passed along, such as the argument name that caused the error
     pub info: Option<Vec<String>>,
+    // Whether `exit` should page the help message (see `AppSettings::PagedHelp`)
+    paged: bool,
 }
 
 impl Error {
@@ -385,7 +415,8 @@ impl Error {
     pub fn use_stderr(&self) -> bool {
         match self.kind {
             ErrorKind::HelpDisplayed |
-            ErrorKind::VersionDisplayed => false,
+            ErrorKind::VersionDisplayed |
+            ErrorKind::CompletionsDisplayed => false,
             _ => true,
         }
     }
@@ -396,11 +427,36 @@ impl Error {
             wlnerr!("{}", self.message);
             process::exit(1);
         }
+        if self.is_paged_help() && pager::page(&self.message) {
+            process::exit(0);
+        }
         let out = io::stdout();
         writeln!(&mut out.lock(), "{}", self.message).expect("Error writing Error to stdout");
         process::exit(0);
     }
 
+    fn is_paged_help(&self) -> bool { self.kind == ErrorKind::HelpDisplayed && self.paged }
+
+    #[doc(hidden)]
+    pub fn with_message(message: String, kind: ErrorKind) -> Self {
+        Error {
+            message: message,
+            kind: kind,
+            info: None,
+            paged: false,
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn help_displayed(message: String, paged: bool) -> Self {
+        Error {
+            message: message,
+            kind: ErrorKind::HelpDisplayed,
+            info: None,
+            paged: paged,
+        }
+    }
+
     #[doc(hidden)]
     pub fn write_to<W: Write>(&self, w: &mut W) -> io::Result<()> { write!(w, "{}", self.message) }
 
@@ -408,28 +464,24 @@ impl Error {
     pub fn argument_conflict<'a, 'b, A, O, U>(arg: &A,
                                               other: Option<O>,
                                               usage: U,
-                                              color: fmt::ColorWhen)
+                                              c: fmt::Colorizer)
                                               -> Self
         where A: AnyArg<'a, 'b> + Display,
               O: Into<String>,
               U: Display
     {
         let mut v = vec![arg.name().to_owned()];
-        let c = fmt::Colorizer {
-            use_stderr: true,
-            when: color,
-        };
         Error {
             message: format!("{} The argument '{}' cannot be used with {}\n\n\
                             {}\n\n\
                             For more information try {}",
                              c.error("error:"),
-                             c.warning(&*arg.to_string()),
+                             c.value(&*arg.to_string()),
                              match other {
                                  Some(name) => {
                                      let n = name.into();
                                      v.push(n.clone());
-                                     c.warning(format!("'{}'", n))
+                                     c.value(format!("'{}'", n))
                                  }
                                  None => {
                                      c.none("one or more of the other specified arguments"
@@ -437,32 +489,30 @@ impl Error {
                                  }
                              },
                              usage,
-                             c.good("--help")),
+                             c.literal("--help")),
             kind: ErrorKind::ArgumentConflict,
             info: Some(v),
+            paged: false,
         }
     }
 
     #[doc(hidden)]
-    pub fn empty_value<'a, 'b, A, U>(arg: &A, usage: U, color: fmt::ColorWhen) -> Self
+    pub fn empty_value<'a, 'b, A, U>(arg: &A, usage: U, c: fmt::Colorizer) -> Self
         where A: AnyArg<'a, 'b> + Display,
               U: Display
     {
-        let c = fmt::Colorizer {
-            use_stderr: true,
-            when: color,
-        };
         Error {
             message: format!("{} The argument '{}' requires a value but none was supplied\
                             \n\n\
                             {}\n\n\
                             For more information try {}",
                              c.error("error:"),
-                             c.warning(arg.to_string()),
+                             c.value(arg.to_string()),
                              usage,
-                             c.good("--help")),
+                             c.literal("--help")),
             kind: ErrorKind::EmptyValue,
             info: Some(vec![arg.name().to_owned()]),
+            paged: false,
         }
     }
 
@@ -471,25 +521,22 @@ impl Error {
                                              good_vals: &[G],
                                              arg: &A,
                                              usage: U,
-                                             color: fmt::ColorWhen)
+                                             c: fmt::Colorizer)
                                              -> Self
         where B: AsRef<str>,
               G: AsRef<str> + Display,
               A: AnyArg<'a, 'b> + Display,
               U: Display
     {
-        let c = fmt::Colorizer {
-            use_stderr: true,
-            when: color,
-        };
         let suffix =
             suggestions::did_you_mean_suffix(bad_val.as_ref(),
                                              good_vals.iter(),
-                                             suggestions::DidYouMeanMessageStyle::EnumValue);
+                                             suggestions::DidYouMeanMessageStyle::EnumValue,
+                                             &c);
 
         let mut sorted = vec![];
         for v in good_vals {
-            let val = format!("{}", c.good(v));
+            let val = format!("{}", c.literal(v));
             sorted.push(val);
         }
         sorted.sort();
@@ -501,14 +548,15 @@ impl Error {
                             {}\n\n\
                             For more information try {}",
                              c.error("error:"),
-                             c.warning(bad_val.as_ref()),
-                             c.warning(arg.to_string()),
+                             c.value(bad_val.as_ref()),
+                             c.value(arg.to_string()),
                              valid_values,
                              suffix.0,
                              usage,
-                             c.good("--help")),
+                             c.literal("--help")),
             kind: ErrorKind::InvalidValue,
             info: Some(vec![arg.name().to_owned(), bad_val.as_ref().to_owned()]),
+            paged: false,
         }
     }
 
@@ -517,7 +565,7 @@ impl Error {
                                           did_you_mean: D,
                                           name: N,
                                           usage: U,
-                                          color: fmt::ColorWhen)
+                                          c: fmt::Colorizer)
                                           -> Self
         where S: Into<String>,
               D: AsRef<str> + Display,
@@ -525,10 +573,6 @@ impl Error {
               U: Display
     {
         let s = subcmd.into();
-        let c = fmt::Colorizer {
-            use_stderr: true,
-            when: color,
-        };
         Error {
             message: format!("{} The subcommand '{}' wasn't recognized\n\t\
                             Did you mean '{}'?\n\n\
@@ -537,52 +581,46 @@ impl Error {
                             {}\n\n\
                             For more information try {}",
                              c.error("error:"),
-                             c.warning(&*s),
-                             c.good(did_you_mean.as_ref()),
+                             c.value(&*s),
+                             c.suggestion(did_you_mean.as_ref()),
                              name,
-                             c.good("--"),
+                             c.literal("--"),
                              &*s,
                              usage,
-                             c.good("--help")),
+                             c.literal("--help")),
             kind: ErrorKind::InvalidSubcommand,
             info: Some(vec![s]),
+            paged: false,
         }
     }
 
     #[doc(hidden)]
-    pub fn unrecognized_subcommand<S, N>(subcmd: S, name: N, color: fmt::ColorWhen) -> Self
+    pub fn unrecognized_subcommand<S, N>(subcmd: S, name: N, c: fmt::Colorizer) -> Self
         where S: Into<String>,
               N: Display
     {
         let s = subcmd.into();
-        let c = fmt::Colorizer {
-            use_stderr: true,
-            when: color,
-        };
         Error {
             message: format!("{} The subcommand '{}' wasn't recognized\n\n\
                             {}\n\t\
                                 {} help <subcommands>...\n\n\
                             For more information try {}",
                              c.error("error:"),
-                             c.warning(&*s),
-                             c.warning("USAGE:"),
+                             c.value(&*s),
+                             c.header("USAGE:"),
                              name,
-                             c.good("--help")),
+                             c.literal("--help")),
             kind: ErrorKind::UnrecognizedSubcommand,
             info: Some(vec![s]),
+            paged: false,
         }
     }
 
     #[doc(hidden)]
-    pub fn missing_required_argument<R, U>(required: R, usage: U, color: fmt::ColorWhen) -> Self
+    pub fn missing_required_argument<R, U>(required: R, usage: U, c: fmt::Colorizer) -> Self
         where R: Display,
               U: Display
     {
-        let c = fmt::Colorizer {
-            use_stderr: true,
-            when: color,
-        };
         Error {
             message: format!("{} The following required arguments were not provided:{}\n\n\
                             {}\n\n\
@@ -590,52 +628,47 @@ impl Error {
                              c.error("error:"),
                              required,
                              usage,
-                             c.good("--help")),
+                             c.literal("--help")),
             kind: ErrorKind::MissingRequiredArgument,
             info: None,
+            paged: false,
         }
     }
 
     #[doc(hidden)]
-    pub fn missing_subcommand<N, U>(name: N, usage: U, color: fmt::ColorWhen) -> Self
+    pub fn missing_subcommand<N, U>(name: N, usage: U, c: fmt::Colorizer) -> Self
         where N: AsRef<str> + Display,
               U: Display
     {
-        let c = fmt::Colorizer {
-            use_stderr: true,
-            when: color,
-        };
         Error {
             message: format!("{} '{}' requires a subcommand, but one was not provided\n\n\
                             {}\n\n\
                             For more information try {}",
                              c.error("error:"),
-                             c.warning(name),
+                             c.value(name),
                              usage,
-                             c.good("--help")),
+                             c.literal("--help")),
             kind: ErrorKind::MissingSubcommand,
             info: None,
+            paged: false,
         }
     }
 
 
     #[doc(hidden)]
-    pub fn invalid_utf8<U>(usage: U, color: fmt::ColorWhen) -> Self
+    pub fn invalid_utf8<U>(usage: U, c: fmt::Colorizer) -> Self
         where U: Display
     {
-        let c = fmt::Colorizer {
-            use_stderr: true,
-            when: color,
-        };
         Error {
             message: format!("{} Invalid UTF-8 was detected in one or more arguments\n\n\
                             {}\n\n\
                             For more information try {}",
                              c.error("error:"),
                              usage,
-                             c.good("--help")),
+                             c.literal("--help")),
             kind: ErrorKind::InvalidUtf8,
             info: None,
+            paged: false,
         }
     }
 
@@ -643,29 +676,26 @@ impl Error {
     pub fn too_many_values<'a, 'b, V, A, U>(val: V,
                                             arg: &A,
                                             usage: U,
-                                            color: fmt::ColorWhen)
+                                            c: fmt::Colorizer)
                                             -> Self
         where V: AsRef<str> + Display + ToOwned,
               A: AnyArg<'a, 'b> + Display,
               U: Display
     {
         let v = val.as_ref();
-        let c = fmt::Colorizer {
-            use_stderr: true,
-            when: color,
-        };
         Error {
             message: format!("{} The value '{}' was provided to '{}', but it wasn't expecting \
                             any more values\n\n\
                             {}\n\n\
                             For more information try {}",
                              c.error("error:"),
-                             c.warning(v),
-                             c.warning(arg.to_string()),
+                             c.value(v),
+                             c.value(arg.to_string()),
                              usage,
-                             c.good("--help")),
+                             c.literal("--help")),
             kind: ErrorKind::TooManyValues,
             info: Some(vec![arg.name().to_owned(), v.to_owned()]),
+            paged: false,
         }
     }
 
@@ -674,58 +704,52 @@ impl Error {
                                         min_vals: u64,
                                         curr_vals: usize,
                                         usage: U,
-                                        color: fmt::ColorWhen)
+                                        c: fmt::Colorizer)
                                         -> Self
         where A: AnyArg<'a, 'b> + Display,
               U: Display
     {
-        let c = fmt::Colorizer {
-            use_stderr: true,
-            when: color,
-        };
         Error {
             message: format!("{} The argument '{}' requires at least {} values, but only {} w{} \
                             provided\n\n\
                             {}\n\n\
                             For more information try {}",
                              c.error("error:"),
-                             c.warning(arg.to_string()),
-                             c.warning(min_vals.to_string()),
-                             c.warning(curr_vals.to_string()),
+                             c.value(arg.to_string()),
+                             c.value(min_vals.to_string()),
+                             c.value(curr_vals.to_string()),
                              if curr_vals > 1 { "ere" } else { "as" },
                              usage,
-                             c.good("--help")),
+                             c.literal("--help")),
             kind: ErrorKind::TooFewValues,
             info: Some(vec![arg.name().to_owned()]),
+            paged: false,
         }
     }
 
     #[doc(hidden)]
-    pub fn value_validation<'a, 'b, A>(arg: Option<&A>, err: String, color: fmt::ColorWhen) -> Self
+    pub fn value_validation<'a, 'b, A>(arg: Option<&A>, err: String, c: fmt::Colorizer) -> Self
         where A: AnyArg<'a, 'b> + Display
     {
-        let c = fmt::Colorizer {
-            use_stderr: true,
-            when: color,
-        };
         Error {
             message: format!("{} Invalid value{}: {}",
                              c.error("error:"),
                              if let Some(a) = arg {
-                                 format!(" for '{}'", c.warning(a.to_string()))
+                                 format!(" for '{}'", c.value(a.to_string()))
                              } else {
                                  "".to_string()
                              },
                              err),
             kind: ErrorKind::ValueValidation,
             info: None,
+            paged: false,
         }
     }
 
     #[doc(hidden)]
     pub fn value_validation_auto(err: String) -> Self {
         let n: Option<&FlagBuilder> = None; 
-        Error::value_validation(n, err, fmt::ColorWhen::Auto)
+        Error::value_validation(n, err, fmt::Colorizer::default())
     }
 
     #[doc(hidden)]
@@ -734,53 +758,47 @@ impl Error {
                                                    curr_vals: usize,
                                                    suffix: S,
                                                    usage: U,
-                                                   color: fmt::ColorWhen)
+                                                   c: fmt::Colorizer)
                                                    -> Self
         where A: AnyArg<'a, 'b> + Display,
               S: Display,
               U: Display
     {
-        let c = fmt::Colorizer {
-            use_stderr: true,
-            when: color,
-        };
         Error {
             message: format!("{} The argument '{}' requires {} values, but {} w{} \
                             provided\n\n\
                             {}\n\n\
                             For more information try {}",
                              c.error("error:"),
-                             c.warning(arg.to_string()),
-                             c.warning(num_vals.to_string()),
-                             c.warning(curr_vals.to_string()),
+                             c.value(arg.to_string()),
+                             c.value(num_vals.to_string()),
+                             c.value(curr_vals.to_string()),
                              suffix,
                              usage,
-                             c.good("--help")),
+                             c.literal("--help")),
             kind: ErrorKind::WrongNumberOfValues,
             info: Some(vec![arg.name().to_owned()]),
+            paged: false,
         }
     }
 
     #[doc(hidden)]
-    pub fn unexpected_multiple_usage<'a, 'b, A, U>(arg: &A, usage: U, color: fmt::ColorWhen) -> Self
+    pub fn unexpected_multiple_usage<'a, 'b, A, U>(arg: &A, usage: U, c: fmt::Colorizer) -> Self
         where A: AnyArg<'a, 'b> + Display,
               U: Display
     {
-        let c = fmt::Colorizer {
-            use_stderr: true,
-            when: color,
-        };
         Error {
             message: format!("{} The argument '{}' was provided more than once, but cannot \
                             be used multiple times\n\n\
                             {}\n\n\
                             For more information try {}",
                              c.error("error:"),
-                             c.warning(arg.to_string()),
+                             c.value(arg.to_string()),
                              usage,
-                             c.good("--help")),
+                             c.literal("--help")),
             kind: ErrorKind::UnexpectedMultipleUsage,
             info: Some(vec![arg.name().to_owned()]),
+            paged: false,
         }
     }
 
@@ -788,45 +806,39 @@ impl Error {
     pub fn unknown_argument<A, U>(arg: A,
                                   did_you_mean: &str,
                                   usage: U,
-                                  color: fmt::ColorWhen)
+                                  c: fmt::Colorizer)
                                   -> Self
         where A: Into<String>,
               U: Display
     {
         let a = arg.into();
-        let c = fmt::Colorizer {
-            use_stderr: true,
-            when: color,
-        };
         Error {
             message: format!("{} Found argument '{}' which wasn't expected, or isn't valid in \
             this context{}\n\
                             {}\n\n\
                             For more information try {}",
                              c.error("error:"),
-                             c.warning(&*a),
+                             c.value(&*a),
                              if did_you_mean.is_empty() {
                                  "\n".to_owned()
                              } else {
                                  format!("{}\n", did_you_mean)
                              },
                              usage,
-                             c.good("--help")),
+                             c.literal("--help")),
             kind: ErrorKind::UnknownArgument,
             info: Some(vec![a]),
+            paged: false,
         }
     }
 
     #[doc(hidden)]
-    pub fn io_error(e: &Error, color: fmt::ColorWhen) -> Self {
-        let c = fmt::Colorizer {
-            use_stderr: true,
-            when: color,
-        };
+    pub fn io_error(e: &Error, c: fmt::Colorizer) -> Self {
         Error {
             message: format!("{} {}", c.error("error:"), e.description()),
             kind: ErrorKind::Io,
             info: None,
+            paged: false,
         }
     }
 
@@ -835,16 +847,32 @@ impl Error {
         where A: Into<String>
     {
         let a = arg.into();
-        let c = fmt::Colorizer {
-            use_stderr: true,
-            when: fmt::ColorWhen::Auto,
-        };
+        let c = fmt::Colorizer::default();
         Error {
             message: format!("{} The argument '{}' wasn't found",
                              c.error("error:"),
                              a.clone()),
             kind: ErrorKind::ArgumentNotFound,
             info: Some(vec![a]),
+            paged: false,
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn invalid_definition(path: &str, reason: &str) -> Self {
+        let c = fmt::Colorizer::default();
+        Error {
+            message: if path.is_empty() {
+                format!("{} Invalid definition: {}", c.error("error:"), reason)
+            } else {
+                format!("{} Invalid definition at '{}': {}",
+                        c.error("error:"),
+                        c.value(path),
+                        reason)
+            },
+            kind: ErrorKind::InvalidDefinition,
+            info: Some(vec![path.to_owned(), reason.to_owned()]),
+            paged: false,
         }
     }
 
@@ -853,14 +881,12 @@ impl Error {
     /// This can be used in combination with `Error::exit` to exit your program
     /// with a custom error message.
     pub fn with_description(description: &str, kind: ErrorKind) -> Self {
-        let c = fmt::Colorizer {
-            use_stderr: true,
-            when: fmt::ColorWhen::Auto,
-        };
+        let c = fmt::Colorizer::default();
         Error {
             message: format!("{} {}", c.error("error:"), description),
             kind: kind,
             info: None,
+            paged: false,
         }
     }
 }
@@ -882,3 +908,29 @@ impl From<std_fmt::Error> for Error {
         Error::with_description(e.description(), ErrorKind::Format)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use {App, AppSettings, ErrorKind, SubCommand};
+
+    #[test]
+    fn paged_subcommand_help() {
+        let app = App::new("prog")
+            .setting(AppSettings::PagedHelp)
+            .subcommand(SubCommand::with_name("sub").subcommand(SubCommand::with_name("deep")));
+        for args in &[vec!["prog", "--help"],
+                      vec!["prog", "sub", "--help"],
+                      vec!["prog", "help", "sub"],
+                      vec!["prog", "sub", "deep", "-h"]] {
+            let err = app.clone().get_matches_from_safe(args).err().unwrap();
+            assert_eq!(err.kind, ErrorKind::HelpDisplayed);
+            assert!(err.is_paged_help(), "{:?}", args);
+        }
+        let err = App::new("prog")
+            .subcommand(SubCommand::with_name("sub"))
+            .get_matches_from_safe(vec!["prog", "sub", "--help"])
+            .err()
+            .unwrap();
+        assert!(!err.is_paged_help());
+    }
+}
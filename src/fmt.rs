@@ -2,11 +2,16 @@
 use ansi_term::ANSIString;
 
 #[cfg(all(feature = "color", not(target_os = "windows")))]
-use ansi_term::Colour::{Green, Red, Yellow};
+use ansi_term::Colour::{self, Green, Red, Yellow};
+
+#[cfg(all(feature = "color", not(target_os = "windows")))]
+use ansi_term::Style as AnsiStyle;
 
 #[cfg(feature = "color")]
 use atty;
+use std::ascii::AsciiExt;
 use std::fmt;
+use std::str::FromStr;
 
 #[doc(hidden)]
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -33,10 +38,254 @@ pub fn is_a_tty(_: bool) -> bool {
     false
 }
 
+/// One of the colors of the terminal: the eight standard ones, or any of the 256 colors with
+/// `Fixed`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Color {
+    /// Black
+    Black,
+    /// Red
+    Red,
+    /// Green
+    Green,
+    /// Yellow
+    Yellow,
+    /// Blue
+    Blue,
+    /// Purple, or magenta
+    Purple,
+    /// Cyan
+    Cyan,
+    /// White
+    White,
+    /// A color of the 256 color palette, by number
+    Fixed(u8),
+}
+
+/// How a part of the help or error messages looks: its color, and whether it's bold or
+/// underlined. The plain text style is `Style::new()`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use clap::{Color, Style};
+/// let orange = Style::new().fg(Color::Fixed(208)).bold();
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct Style {
+    fg: Option<Color>,
+    bold: bool,
+    underline: bool,
+}
+
+impl Style {
+    /// Creates the style of plain text.
+    pub fn new() -> Self { Style::default() }
+
+    /// Sets the foreground color.
+    pub fn fg(mut self, color: Color) -> Self {
+        self.fg = Some(color);
+        self
+    }
+
+    /// Makes the text bold.
+    pub fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+
+    /// Underlines the text.
+    pub fn underline(mut self) -> Self {
+        self.underline = true;
+        self
+    }
+
+    #[cfg(all(feature = "color", not(target_os = "windows")))]
+    fn to_ansi(&self) -> AnsiStyle {
+        let mut style = AnsiStyle::new();
+        if let Some(fg) = self.fg {
+            style = style.fg(match fg {
+                Color::Black => Colour::Black,
+                Color::Red => Colour::Red,
+                Color::Green => Colour::Green,
+                Color::Yellow => Colour::Yellow,
+                Color::Blue => Colour::Blue,
+                Color::Purple => Colour::Purple,
+                Color::Cyan => Colour::Cyan,
+                Color::White => Colour::White,
+                Color::Fixed(n) => Colour::Fixed(n),
+            });
+        }
+        if self.bold {
+            style = style.bold();
+        }
+        if self.underline {
+            style = style.underline();
+        }
+        style
+    }
+}
+
+impl From<Color> for Style {
+    fn from(color: Color) -> Self { Style::new().fg(color) }
+}
+
+// The names of the colors, in the written form of the styles
+const COLOR_NAMES: [(&'static str, Color); 8] = [("black", Color::Black),
+                                                 ("red", Color::Red),
+                                                 ("green", Color::Green),
+                                                 ("yellow", Color::Yellow),
+                                                 ("blue", Color::Blue),
+                                                 ("purple", Color::Purple),
+                                                 ("cyan", Color::Cyan),
+                                                 ("white", Color::White)];
+
+/// Reads a style written as words separated by spaces: a color name (`black`, `red`, `green`,
+/// `yellow`, `blue`, `purple`, `cyan` or `white`) or number in the 256 color palette, `bold` and
+/// `underline`, or just `plain`. This is how the styles of [`App::theme`] are written in YAML,
+/// JSON and TOML files.
+///
+/// # Examples
+///
+/// ```rust
+/// # use clap::{Color, Style};
+/// assert_eq!("208 bold".parse::<Style>(), Ok(Style::new().fg(Color::Fixed(208)).bold()));
+/// assert_eq!("plain".parse::<Style>(), Ok(Style::new()));
+/// ```
+/// [`App::theme`]: ./struct.App.html#method.theme
+impl FromStr for Style {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, <Self as FromStr>::Err> {
+        let mut style = Style::new();
+        for word in s.split_whitespace() {
+            match &*word.to_ascii_lowercase() {
+                "plain" => (),
+                "bold" => style = style.bold(),
+                "underline" => style = style.underline(),
+                w => {
+                    style = style.fg(match COLOR_NAMES.iter().find(|&&(n, _)| n == w) {
+                        Some(&(_, c)) => c,
+                        None => {
+                            match w.parse::<u8>() {
+                                Ok(n) => Color::Fixed(n),
+                                Err(_) => return Err(format!("unknown style '{}'", word)),
+                            }
+                        }
+                    })
+                }
+            }
+        }
+        Ok(style)
+    }
+}
+
+impl fmt::Display for Style {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut words = vec![];
+        match self.fg {
+            Some(Color::Fixed(n)) => words.push(n.to_string()),
+            Some(c) => {
+                words.extend(COLOR_NAMES.iter().find(|&&(_, o)| o == c).map(|&(n, _)| n.to_owned()))
+            }
+            None => (),
+        }
+        if self.bold {
+            words.push("bold".to_owned());
+        }
+        if self.underline {
+            words.push("underline".to_owned());
+        }
+        if words.is_empty() {
+            write!(f, "plain")
+        } else {
+            write!(f, "{}", words.join(" "))
+        }
+    }
+}
+
+/// The styles of the help and error messages, used when they are colored, set with
+/// [`App::theme`]. The default theme is the usual look of `clap`'s messages.
+///
+/// # Examples
+///
+/// ```rust
+/// # use clap::{App, Color, Style, Theme};
+/// App::new("myprog")
+///     .theme(Theme {
+///         header: Style::new().fg(Color::Blue).bold().underline(),
+///         ..Theme::default()
+///     })
+/// # ;
+/// ```
+/// [`App::theme`]: ./struct.App.html#method.theme
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Theme {
+    /// The section headers of the help message, such as `USAGE:` (defaults to yellow)
+    pub header: Style,
+    /// What is typed as is: the names of the binary, arguments and subcommands, and the possible
+    /// and default values in the help message (defaults to green)
+    pub literal: Style,
+    /// The value names of the help message, such as `<FILE>` (defaults to green)
+    pub placeholder: Style,
+    /// The arguments and values quoted by the error messages (defaults to yellow)
+    pub value: Style,
+    /// The `error:` of the error messages (defaults to bold red)
+    pub error: Style,
+    /// The suggestions of the error messages, as in `Did you mean '--flag'?` (defaults to green)
+    pub suggestion: Style,
+    /// The `warning:` of the warnings, such as those about deprecated arguments (defaults to bold
+    /// yellow)
+    pub warning: Style,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            header: Color::Yellow.into(),
+            literal: Color::Green.into(),
+            placeholder: Color::Green.into(),
+            value: Color::Yellow.into(),
+            error: Style::new().fg(Color::Red).bold(),
+            suggestion: Color::Green.into(),
+            warning: Style::new().fg(Color::Yellow).bold(),
+        }
+    }
+}
+
+impl Theme {
+    // The parts of a theme by name, in the written form of the theme
+    #[doc(hidden)]
+    pub fn parts(&self) -> [(&'static str, Style); 7] {
+        [("header", self.header),
+         ("literal", self.literal),
+         ("placeholder", self.placeholder),
+         ("value", self.value),
+         ("error", self.error),
+         ("suggestion", self.suggestion),
+         ("warning", self.warning)]
+    }
+
+    #[doc(hidden)]
+    pub fn part_mut(&mut self, name: &str) -> Option<&mut Style> {
+        match name {
+            "header" => Some(&mut self.header),
+            "literal" => Some(&mut self.literal),
+            "placeholder" => Some(&mut self.placeholder),
+            "value" => Some(&mut self.value),
+            "error" => Some(&mut self.error),
+            "suggestion" => Some(&mut self.suggestion),
+            "warning" => Some(&mut self.warning),
+            _ => None,
+        }
+    }
+}
+
 #[doc(hidden)]
+#[derive(Debug, Copy, Clone)]
 pub struct Colorizer {
     pub use_stderr: bool,
     pub when: ColorWhen,
+    pub theme: Theme,
 }
 
 macro_rules! color {
@@ -51,28 +300,85 @@ macro_rules! color {
             ColorWhen::Never => Format::None($m),
         }
     };
+    (@theme $_self:ident, $part:ident, $m:expr) => {
+        match $_self.when {
+            ColorWhen::Auto => if is_a_tty($_self.use_stderr) {
+                Format::Styled($m, $_self.theme.$part)
+            } else {
+                Format::None($m)
+            },
+            ColorWhen::Always => Format::Styled($m, $_self.theme.$part),
+            ColorWhen::Never => Format::None($m),
+        }
+    };
+    (@style $_self:ident, $style:expr, $m:expr) => {
+        match $_self.when {
+            ColorWhen::Auto => if is_a_tty($_self.use_stderr) {
+                Format::Styled($m, $style)
+            } else {
+                Format::None($m)
+            },
+            ColorWhen::Always => Format::Styled($m, $style),
+            ColorWhen::Never => Format::None($m),
+        }
+    };
 }
 
 impl Colorizer {
-    pub fn good<T>(&self, msg: T) -> Format<T>
+    pub fn header<T>(&self, msg: T) -> Format<T>
         where T: fmt::Display + AsRef<str>
     {
-        debugln!("Colorizer::good;");
-        color!(self, Good, msg)
+        debugln!("Colorizer::header;");
+        color!(@theme self, header, msg)
     }
 
-    pub fn warning<T>(&self, msg: T) -> Format<T>
+    pub fn literal<T>(&self, msg: T) -> Format<T>
         where T: fmt::Display + AsRef<str>
     {
-        debugln!("Colorizer::warning;");
-        color!(self, Warning, msg)
+        debugln!("Colorizer::literal;");
+        color!(@theme self, literal, msg)
+    }
+
+    pub fn placeholder<T>(&self, msg: T) -> Format<T>
+        where T: fmt::Display + AsRef<str>
+    {
+        debugln!("Colorizer::placeholder;");
+        color!(@theme self, placeholder, msg)
+    }
+
+    pub fn value<T>(&self, msg: T) -> Format<T>
+        where T: fmt::Display + AsRef<str>
+    {
+        debugln!("Colorizer::value;");
+        color!(@theme self, value, msg)
+    }
+
+    pub fn suggestion<T>(&self, msg: T) -> Format<T>
+        where T: fmt::Display + AsRef<str>
+    {
+        debugln!("Colorizer::suggestion;");
+        color!(@theme self, suggestion, msg)
     }
 
     pub fn error<T>(&self, msg: T) -> Format<T>
         where T: fmt::Display + AsRef<str>
     {
         debugln!("Colorizer::error;");
-        color!(self, Error, msg)
+        color!(@theme self, error, msg)
+    }
+
+    pub fn warning<T>(&self, msg: T) -> Format<T>
+        where T: fmt::Display + AsRef<str>
+    {
+        debugln!("Colorizer::warning;");
+        color!(@theme self, warning, msg)
+    }
+
+    pub fn bold<T>(&self, msg: T) -> Format<T>
+        where T: fmt::Display + AsRef<str>
+    {
+        debugln!("Colorizer::bold;");
+        color!(@style self, Style::new().bold(), msg)
     }
 
     pub fn none<T>(&self, msg: T) -> Format<T>
@@ -88,6 +394,7 @@ impl Default for Colorizer {
         Colorizer {
             use_stderr: true,
             when: ColorWhen::Auto,
+            theme: Theme::default(),
         }
     }
 }
@@ -105,6 +412,8 @@ pub enum Format<T> {
     Good(T),
     /// Defines no formatting style
     None(T),
+    /// Defines a style of a `Theme`
+    Styled(T, Style),
 }
 
 #[cfg(all(feature = "color", not(target_os = "windows")))]
@@ -115,6 +424,7 @@ impl<T: AsRef<str>> Format<T> {
             Format::Warning(ref e) => Yellow.paint(e.as_ref()),
             Format::Good(ref e) => Green.paint(e.as_ref()),
             Format::None(ref e) => ANSIString::from(e.as_ref()),
+            Format::Styled(ref e, ref style) => style.to_ansi().paint(e.as_ref()),
         }
     }
 }
@@ -128,6 +438,7 @@ impl<T: fmt::Display> Format<T> {
             Format::Warning(ref e) => e,
             Format::Good(ref e) => e,
             Format::None(ref e) => e,
+            Format::Styled(ref e, _) => e,
         }
     }
 }
@@ -147,7 +458,9 @@ impl<T: fmt::Display> fmt::Display for Format<T> {
 mod test {
     use ansi_term::ANSIString;
     use ansi_term::Colour::{Green, Red, Yellow};
-    use super::Format;
+    use ansi_term::Style;
+    use ansi_term::Colour::Fixed;
+    use super::{Color, ColorWhen, Colorizer, Format, Theme};
 
     #[test]
     fn colored_output() {
@@ -162,4 +475,29 @@ mod test {
         assert_eq!(&*format!("{}", none),
                    &*format!("{}", ANSIString::from("none")));
     }
+
+    #[test]
+    fn default_theme() {
+        let theme = Theme::default();
+        assert_eq!(&*format!("{}", Format::Styled("error", theme.error)),
+                   &*format!("{}", Format::Error("error")));
+        assert_eq!(&*format!("{}", Format::Styled("header", theme.header)),
+                   &*format!("{}", Format::Warning("header")));
+        assert_eq!(&*format!("{}", Format::Styled("literal", theme.literal)),
+                   &*format!("{}", Format::Good("literal")));
+    }
+
+    #[test]
+    fn bold_output() {
+        let cizer = Colorizer { use_stderr: false, when: ColorWhen::Always, theme: Theme::default() };
+        assert_eq!(&*format!("{}", cizer.bold("bold")),
+                   &*format!("{}", Style::new().bold().paint("bold")));
+    }
+
+    #[test]
+    fn styled_output() {
+        let style = super::Style::new().fg(Color::Fixed(208)).bold().underline();
+        assert_eq!(&*format!("{}", Format::Styled("styled", style)),
+                   &*format!("{}", Fixed(208).bold().underline().paint("styled")));
+    }
 }
@@ -352,7 +352,9 @@
 //! 
 //! #### Opt-in features
 //! 
-//! * **"yaml"**: Enables building CLIs from YAML documents. (builds dependency `yaml-rust`)
+//! * **"yaml"**: Enables building CLIs from YAML documents, and exporting the definition of an `App` as YAML. (builds dependency `yaml-rust`)
+//! * **"serde"**: Enables exporting the definition of an `App` as JSON, generating Fig completion specs, and building CLIs from JSON documents. (builds dependency `serde_json`) (requires Rust 1.13.0 or newer)
+//! * **"toml"**: Enables building CLIs from TOML documents. (builds dependency `toml`) (requires Rust 1.13.0 or newer)
 //! * **"unstable"**: Enables unstable `clap` features that may change from release to release
 //! 
 //! ### Dependencies Tree
@@ -535,6 +537,10 @@ extern crate strsim;
 extern crate ansi_term;
 #[cfg(feature = "yaml")]
 extern crate yaml_rust;
+#[cfg(feature = "serde")]
+extern crate serde_json;
+#[cfg(feature = "toml")]
+extern crate toml;
 extern crate unicode_width;
 #[macro_use]
 extern crate bitflags;
@@ -547,9 +553,9 @@ extern crate atty;
 
 #[cfg(feature = "yaml")]
 pub use yaml_rust::YamlLoader;
-pub use args::{Arg, ArgGroup, ArgMatches, ArgSettings, SubCommand, Values, OsValues};
+pub use args::{Arg, ArgGroup, ArgMatches, ArgSettings, SubCommand, Values, OsValues, ValueHint};
 pub use app::{App, AppSettings};
-pub use fmt::Format;
+pub use fmt::{Format, Color, Style, Theme};
 pub use errors::{Error, ErrorKind, Result};
 pub use completions::Shell;
 
@@ -3,7 +3,7 @@
 use strsim;
 
 // Internal
-use fmt::Format;
+use fmt::Colorizer;
 
 /// Produces a string from a given list of possible values which is similar to
 /// the passed in value `v` with a certain confidence.
@@ -38,11 +38,20 @@ pub fn did_you_mean<'a, T: ?Sized, I>(_: &str, _: I) -> Option<&'a str>
     None
 }
 
+/// How similar `a` is to `b`, from `0.0` (nothing in common) to `1.0` (the same), with the same
+/// measure as [`did_you_mean`]
+#[cfg(feature = "suggestions")]
+pub fn similarity(a: &str, b: &str) -> f64 { strsim::jaro_winkler(a, b) }
+
+#[cfg(not(feature = "suggestions"))]
+pub fn similarity(_: &str, _: &str) -> f64 { 0.0 }
+
 /// Returns a suffix that can be empty, or is the standard 'did you mean' phrase
 #[cfg_attr(feature = "lints", allow(needless_lifetimes))]
 pub fn did_you_mean_suffix<'z, T, I>(arg: &str,
                                      values: I,
-                                     style: DidYouMeanMessageStyle)
+                                     style: DidYouMeanMessageStyle,
+                                     c: &Colorizer)
                                      -> (String, Option<&'z str>)
     where T: AsRef<str> + 'z,
           I: IntoIterator<Item = &'z T>
@@ -52,11 +61,11 @@ pub fn did_you_mean_suffix<'z, T, I>(arg: &str,
             let mut suffix = "\n\tDid you mean ".to_owned();
             match style {
                 DidYouMeanMessageStyle::LongFlag => {
-                    suffix.push_str(&Format::Good("--").to_string())
+                    suffix.push_str(&c.suggestion("--").to_string())
                 }
                 DidYouMeanMessageStyle::EnumValue => suffix.push('\''),
             }
-            suffix.push_str(&Format::Good(candidate).to_string()[..]);
+            suffix.push_str(&c.suggestion(candidate).to_string()[..]);
             if let DidYouMeanMessageStyle::EnumValue = style {
                 suffix.push('\'');
             }
@@ -96,7 +105,8 @@ mod test {
     fn suffix_long() {
         let p_vals = ["test", "possible", "values"];
         let suffix = "\n\tDid you mean \'--test\'?";
-        assert_eq!(did_you_mean_suffix("tst", p_vals.iter(), DidYouMeanMessageStyle::LongFlag),
+        assert_eq!(did_you_mean_suffix("tst", p_vals.iter(), DidYouMeanMessageStyle::LongFlag,
+                                      &Colorizer::default()),
                    (suffix, Some("test")));
     }
 
@@ -104,7 +114,8 @@ mod test {
     fn suffix_enum() {
         let p_vals = ["test", "possible", "values"];
         let suffix = "\n\tDid you mean \'test\'?";
-        assert_eq!(did_you_mean_suffix("tst", p_vals.iter(), DidYouMeanMessageStyle::EnumValue),
+        assert_eq!(did_you_mean_suffix("tst", p_vals.iter(), DidYouMeanMessageStyle::EnumValue,
+                                      &Colorizer::default()),
                    (suffix, Some("test")));
     }
 }
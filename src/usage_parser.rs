@@ -14,6 +14,8 @@ enum UsageToken {
     Long,
     Help,
     Multiple,
+    PossibleVals,
+    Attribute,
     Unknown,
 }
 
@@ -25,6 +27,8 @@ pub struct UsageParser<'a> {
     start: usize,
     prev: UsageToken,
     explicit_name_set: bool,
+    possible_vals: Vec<&'a str>,
+    attrs: Vec<(&'a str, &'a str)>,
 }
 
 impl<'a> UsageParser<'a> {
@@ -36,6 +40,8 @@ impl<'a> UsageParser<'a> {
             start: 0,
             prev: UsageToken::Unknown,
             explicit_name_set: false,
+            possible_vals: vec![],
+            attrs: vec![],
         }
     }
 
@@ -55,6 +61,8 @@ impl<'a> UsageParser<'a> {
                     b'-' => self.short_or_long(&mut arg),
                     b'.' => self.multiple(&mut arg),
                     b'\'' => self.help(&mut arg),
+                    b'{' => self.possible_vals(),
+                    b':' => self.attribute(),
                     _ => self.name(&mut arg),
                 }
             } else {
@@ -69,6 +77,25 @@ impl<'a> UsageParser<'a> {
             _ => None,
         };
         debugln!("UsageParser::parse: vals...{:?}", arg.v.val_names);
+        if !self.possible_vals.is_empty() {
+            arg = arg.possible_values(&*self.possible_vals);
+        }
+        for &(key, val) in &self.attrs {
+            let names = val.split(',').filter(|s| !s.is_empty()).collect::<Vec<_>>();
+            arg = match key {
+                "default" => arg.default_value(val),
+                "group" => arg.groups(&*names),
+                "requires" => arg.requires_all(&*names),
+                "conflicts" => arg.conflicts_with_all(&*names),
+                _ => {
+                    debug_assert!(false,
+                                  format!("Unknown attribute '{}' when parsing usage string: {}",
+                                          key,
+                                          self.usage));
+                    arg
+                }
+            };
+        }
         arg
     }
 
@@ -177,11 +204,42 @@ impl<'a> UsageParser<'a> {
         }
     }
 
+    // "{fast,slow}"
+    fn possible_vals(&mut self) {
+        debugln!("UsageParser::possible_vals;");
+        self.pos += 1;
+        self.stop_at(possible_vals_end);
+        let vals = &self.usage[self.start..self.pos];
+        debugln!("UsageParser::possible_vals: setting possible values...{}", vals);
+        self.possible_vals.extend(vals.split(',').map(str::trim).filter(|s| !s.is_empty()));
+        self.pos += 1;
+        self.prev = UsageToken::PossibleVals;
+    }
+
+    // "default:4", "group:mode", "requires:config", "conflicts:quiet,verbose"
+    fn attribute(&mut self) {
+        debugln!("UsageParser::attribute;");
+        let key_start = self.usage[..self.pos]
+            .rfind(|c: char| !c.is_alphabetic())
+            .map_or(0, |i| i + 1);
+        let key = &self.usage[key_start..self.pos];
+        self.pos += 1;
+        self.stop_at(attribute_end);
+        let val = &self.usage[self.start..self.pos];
+        debugln!("UsageParser::attribute: setting {}...{}", key, val);
+        self.attrs.push((key, val));
+        self.prev = UsageToken::Attribute;
+    }
+
     fn help(&mut self, arg: &mut Arg<'a, 'a>) {
         debugln!("UsageParser::help;");
         self.stop_at(help_start);
         self.start = self.pos + 1;
-        self.pos = self.usage.len() - 1;
+        // Anything after the closing quote is left for attributes
+        self.pos = match self.usage.rfind('\'') {
+            Some(end) if end >= self.start => end,
+            _ => self.usage.len() - 1,
+        };
         debugln!("UsageParser::help: setting help...{}", &self.usage[self.start..self.pos]);
         arg.b.help = Some(&self.usage[self.start..self.pos]);
         self.pos += 1;   // Move to next byte to keep from thinking ending ' is a start
@@ -193,7 +251,9 @@ impl<'a> UsageParser<'a> {
 fn name_end(b: u8) -> bool { b != b']' && b != b'>' }
 
 #[inline]
-fn token(b: u8) -> bool { b != b'\'' && b != b'.' && b != b'<' && b != b'[' && b != b'-' }
+fn token(b: u8) -> bool {
+    b != b'\'' && b != b'.' && b != b'<' && b != b'[' && b != b'-' && b != b'{' && b != b':'
+}
 
 #[inline]
 fn long_end(b: u8) -> bool {
@@ -203,6 +263,12 @@ fn long_end(b: u8) -> bool {
 #[inline]
 fn help_start(b: u8) -> bool { b != b'\'' }
 
+#[inline]
+fn possible_vals_end(b: u8) -> bool { b != b'}' }
+
+#[inline]
+fn attribute_end(b: u8) -> bool { b != b' ' && b != b'\t' && b != b'\'' }
+
 #[cfg(test)]
 mod test {
     use args::Arg;
@@ -1196,4 +1262,46 @@ mod test {
                    [&"üñíčöĐ€"]);
         assert_eq!(a.b.help, Some("hælp"));
     }
+
+    #[test]
+    fn default_attribute() {
+        let a = Arg::from_usage("--jobs=[N] 'Jobs to run' default:4");
+        assert_eq!(a.b.name, "jobs");
+        assert_eq!(a.b.help, Some("Jobs to run"));
+        assert_eq!(a.v.default_val.unwrap(), "4");
+        assert!(a.is_set(ArgSettings::TakesValue));
+
+        let b = Arg::from_usage("--level=[N] default:-1 'it's the level'");
+        assert_eq!(b.b.help, Some("it's the level"));
+        assert_eq!(b.v.default_val.unwrap(), "-1");
+    }
+
+    #[test]
+    fn possible_vals() {
+        let a = Arg::from_usage("<mode> {fast,slow} 'How to run'");
+        assert_eq!(a.b.name, "mode");
+        assert!(a.is_set(ArgSettings::Required));
+        assert_eq!(a.v.possible_vals.unwrap(), ["fast", "slow"]);
+        assert_eq!(a.b.help, Some("How to run"));
+
+        let b = Arg::from_usage("-m, --mode=[MODE] {fast, slow, medium}... 'How to run'");
+        assert_eq!(b.s.long, Some("mode"));
+        assert!(b.is_set(ArgSettings::Multiple));
+        assert_eq!(b.v.val_names.unwrap().values().collect::<Vec<_>>(), [&"MODE"]);
+        assert_eq!(b.v.possible_vals.unwrap(), ["fast", "slow", "medium"]);
+    }
+
+    #[test]
+    fn relation_attributes() {
+        let a = Arg::from_usage("--add [NAME] 'Adds' group:action requires:config conflicts:quiet,all");
+        assert_eq!(a.b.help, Some("Adds"));
+        assert_eq!(a.b.groups.unwrap(), ["action"]);
+        assert_eq!(a.b.requires.unwrap(), [(None, "config")]);
+        assert_eq!(a.b.blacklist.unwrap(), ["quiet", "all"]);
+
+        let b = Arg::from_usage("[flag] -f group:mode,output group:verbosity");
+        assert_eq!(b.b.name, "flag");
+        assert!(b.b.help.is_none());
+        assert_eq!(b.b.groups.unwrap(), ["mode", "output", "verbosity"]);
+    }
 }
@@ -620,3 +620,17 @@ fn allow_missing_positional() {
     assert_eq!(m.value_of("src"), Some("src"));
     assert_eq!(m.value_of("dest"), Some("file"));
 }
+
+#[test]
+fn paged_help_kept_in_error() {
+    // Only Error::exit pages, so the error always holds the whole help message
+    let res = App::new("test")
+        .setting(AppSettings::PagedHelp)
+        .arg_from_usage("-f, --flag 'some flag'")
+        .get_matches_from_safe(vec!["test", "--help"]);
+    assert!(res.is_err());
+    let err = res.unwrap_err();
+    assert_eq!(err.kind, ErrorKind::HelpDisplayed);
+    assert!(err.message.contains("some flag"));
+    assert_eq!(err.info, None);
+}
@@ -1,7 +1,7 @@
 extern crate regex;
 extern crate clap;
 
-use clap::{App, Arg, SubCommand, Shell};
+use clap::{App, AppSettings, Arg, ErrorKind, SubCommand, Shell, ValueHint};
 use regex::Regex;
 
 static BASH: &'static str = r#"_myapp() {
@@ -593,6 +593,132 @@ static BASH_WUS: &'static str = r#"_my_app() {
 complete -F _my_app -o bashdefault -o default my_app
 "#;
 
+static ELVISH: &'static str = r#"
+use builtin;
+use str;
+
+set edit:completion:arg-completer['myapp'] = {|@words|
+    fn spaces {|n|
+        builtin:repeat $n ' ' | str:join ''
+    }
+    fn cand {|text desc|
+        edit:complex-candidate $text &display=$text' '(spaces (- 14 (wcswidth $text)))$desc
+    }
+    var completions = [
+        &'myapp'= {
+            cand '-h' 'Prints help information'
+            cand '--help' 'Prints help information'
+            cand '-V' 'Prints version information'
+            cand '--version' 'Prints version information'
+            cand 'test' 'tests things'
+            cand 'help' 'Prints this message or the help of the given subcommand(s)'
+        }
+        &'myapp;test'= {
+            cand '--case' 'the case to test'
+            cand '-h' 'Prints help information'
+            cand '--help' 'Prints help information'
+            cand '-V' 'Prints version information'
+            cand '--version' 'Prints version information'
+        }
+        &'myapp;help'= {
+            cand '-h' 'Prints help information'
+            cand '--help' 'Prints help information'
+            cand '-V' 'Prints version information'
+            cand '--version' 'Prints version information'
+        }
+    ]
+    var values = [&]
+    var command = 'myapp'
+    for word $words[1..-1] {
+        if (has-key $completions $command';'$word) {
+            set command = $command';'$word
+        }
+    }
+    if (and (> (count $words) 2) (has-key $values $command';'$words[-2])) {
+        $values[$command';'$words[-2]]
+    } else {
+        $completions[$command]
+    }
+}
+"#;
+
+#[cfg(feature = "serde")]
+static FIG: &'static str = r#"{
+  "args": {
+    "description": "some input file",
+    "isOptional": true,
+    "name": "file"
+  },
+  "description": "Tests completions",
+  "name": "myapp",
+  "options": [
+    {
+      "description": "Prints help information",
+      "name": [
+        "-h",
+        "--help"
+      ]
+    },
+    {
+      "description": "Prints version information",
+      "name": [
+        "-V",
+        "--version"
+      ]
+    }
+  ],
+  "subcommands": [
+    {
+      "description": "tests things",
+      "name": "test",
+      "options": [
+        {
+          "description": "Prints help information",
+          "name": [
+            "-h",
+            "--help"
+          ]
+        },
+        {
+          "description": "Prints version information",
+          "name": [
+            "-V",
+            "--version"
+          ]
+        },
+        {
+          "args": {
+            "name": "case"
+          },
+          "description": "the case to test",
+          "name": "--case"
+        }
+      ]
+    },
+    {
+      "description": "Prints this message or the help of the given subcommand(s)",
+      "name": "help",
+      "options": [
+        {
+          "description": "Prints help information",
+          "name": [
+            "-h",
+            "--help"
+          ]
+        },
+        {
+          "description": "Prints version information",
+          "name": [
+            "-V",
+            "--version"
+          ]
+        }
+      ]
+    }
+  ]
+}
+"#;
+
 fn compare(left: &str, right: &str) -> bool {
     let b = left == right;
     if !b {
@@ -662,6 +788,131 @@ fn fish() {
     assert!(compare(&*string, FISH));
 }
 
+#[test]
+fn elvish() {
+    let mut app = build_app();
+    let mut buf = vec![];
+    app.gen_completions_to("myapp", Shell::Elvish, &mut buf);
+    let string = String::from_utf8(buf).unwrap();
+
+    assert!(compare(&*string, ELVISH));
+}
+
+#[test]
+fn elvish_aliases_and_values() {
+    let mut app = App::new("myapp")
+        .subcommand(SubCommand::with_name("test")
+            .about("tests things")
+            .visible_alias("t")
+            .alias("tst")
+            .arg(Arg::with_name("mode")
+                .long("mode")
+                .takes_value(true)
+                .possible_values(&["fast", "slow"])
+                .help("it's the mode")));
+    let mut buf = vec![];
+    app.gen_completions_to("myapp", Shell::Elvish, &mut buf);
+    let string = String::from_utf8(buf).unwrap();
+
+    assert!(string.contains("cand 'test' 'tests things'\n            cand 't' 'tests things'"));
+    assert!(!string.contains("cand 'tst'"));
+    assert!(string.contains("&'myapp;test'= {"));
+    assert!(string.contains("&'myapp;t'= {"));
+    assert!(string.contains("&'myapp;tst'= {"));
+    assert!(string.contains("cand '--mode' 'it''s the mode'"));
+    assert!(string.contains("&'myapp;test;--mode'= {\n            cand 'fast' ''\n            cand 'slow' ''\n        }"));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn fig() {
+    let mut app = build_app();
+    let mut buf = vec![];
+    app.gen_fig_spec_to("myapp", &mut buf);
+    let string = String::from_utf8(buf).unwrap();
+
+    assert!(compare(&*string, FIG));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn fig_args() {
+    let mut app = App::new("myapp")
+        .arg(Arg::with_name("output")
+            .long("output")
+            .short("o")
+            .takes_value(true)
+            .required(true)
+            .multiple(true)
+            .value_hint(ValueHint::FilePath)
+            .help("the \"output\" file"))
+        .arg(Arg::with_name("choice")
+            .possible_values(&["first", "second"])
+            .multiple(true)
+            .required(true))
+        .arg(Arg::with_name("secret").long("secret").hidden(true))
+        .subcommand(SubCommand::with_name("test").visible_alias("t").alias("tst"))
+        .subcommand(SubCommand::with_name("internal").setting(AppSettings::Hidden));
+    let mut buf = vec![];
+    app.gen_fig_spec_to("myapp", &mut buf);
+    let string = String::from_utf8(buf).unwrap();
+
+    assert!(string.contains(r#"
+    {
+      "args": {
+        "name": "output",
+        "template": "filepaths"
+      },
+      "description": "the \"output\" file",
+      "isRepeatable": true,
+      "isRequired": true,
+      "name": [
+        "-o",
+        "--output"
+      ]
+    }"#));
+    assert!(string.contains(r#"
+  "args": {
+    "isVariadic": true,
+    "name": "choice",
+    "suggestions": [
+      "first",
+      "second"
+    ]
+  },"#));
+    assert!(string.contains(r#"
+      "name": [
+        "test",
+        "t"
+      ],"#));
+    assert!(!string.contains("tst"));
+    assert!(!string.contains("internal"));
+    assert!(!string.contains("secret"));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn fig_nested() {
+    let mut app = App::new("myapp")
+        .setting(AppSettings::DisableHelpSubcommand)
+        .global_setting(AppSettings::DisableVersion)
+        .subcommand(SubCommand::with_name("remote")
+            .setting(AppSettings::DisableHelpSubcommand)
+            .subcommand(SubCommand::with_name("add"))
+            .subcommand(SubCommand::with_name("rm")))
+        .subcommand(SubCommand::with_name("fetch"));
+    let mut buf = vec![];
+    app.gen_fig_spec_to("myapp", &mut buf);
+    let string = String::from_utf8(buf).unwrap();
+
+    let remote = string.find(r#""name": "remote""#).unwrap();
+    let add = string.find(r#""name": "add""#).unwrap();
+    let rm = string.find(r#""name": "rm""#).unwrap();
+    let fetch = string.find(r#""name": "fetch""#).unwrap();
+    assert!(remote < add && add < rm && rm < fetch);
+    assert!(string[..add].ends_with("\n      \"subcommands\": [\n        {\n          "));
+}
+
 // Disabled until I figure out this windows line ending and AppVeyor issues
 //#[test]
 // fn powershell() {
@@ -713,3 +964,230 @@ fn zsh_with_underscore() {
 
     assert!(compare(&*string, ZSH_WUS));
 }
+
+fn build_app_with_value_hints() -> App<'static, 'static> {
+    App::new("myapp")
+        .arg(Arg::with_name("output")
+            .long("output")
+            .value_hint(ValueHint::FilePath)
+            .help("the output file"))
+        .arg(Arg::with_name("dir")
+            .long("dir")
+            .value_hint(ValueHint::DirPath)
+            .help("the working directory"))
+        .arg(Arg::with_name("host")
+            .long("host")
+            .value_hint(ValueHint::Hostname)
+            .help("the host to connect to"))
+        .arg(Arg::with_name("mode")
+            .long("mode")
+            .value_hint(ValueHint::FilePath)
+            .possible_values(&["fast", "slow"])
+            .help("possible values win over the hint"))
+        .arg(Arg::with_name("input")
+            .value_hint(ValueHint::FilePath)
+            .required(true)
+            .help("the input file"))
+        .arg(Arg::with_name("dest")
+            .value_hint(ValueHint::DirPath)
+            .multiple(true)
+            .help("the destination directories"))
+}
+
+#[test]
+fn powershell_value_hints() {
+    let mut app = build_app_with_value_hints();
+    let mut buf = vec![];
+    app.gen_completions_to("myapp", Shell::PowerShell, &mut buf);
+    let string = String::from_utf8(buf).unwrap();
+
+    assert!(string.contains("'--output' {\n                        return\n"));
+    assert!(string.contains("'--dir' {\n                        return Get-ChildItem -Directory"));
+    assert!(!string.contains("'--host' {") && !string.contains("'--mode' {"));
+    assert!(string.contains("if ($wordToComplete -notlike '-*') {
+                    [System.Management.Automation.CompletionCompleters]::CompleteFilename($wordToComplete)
+                }"));
+}
+
+#[test]
+fn bash_value_hints() {
+    let mut app = build_app_with_value_hints()
+        .arg(Arg::with_name("exe")
+            .long("exe")
+            .value_hint(ValueHint::ExecutablePath)
+            .help("the program to run"))
+        .arg(Arg::with_name("cmd")
+            .long("cmd")
+            .value_hint(ValueHint::CommandWithArgs)
+            .help("the command to run"));
+    let mut buf = vec![];
+    app.gen_completions_to("myapp", Shell::Bash, &mut buf);
+    let string = String::from_utf8(buf).unwrap();
+
+    assert!(string.contains("--output)\n                    COMPREPLY=($(compgen -f -- ${cur}))"));
+    assert!(string.contains("--dir)\n                    COMPREPLY=($(compgen -d -- ${cur}))"));
+    assert!(string.contains("COMPREPLY=($(compgen -A hostname -- ${cur}))"));
+    assert!(string.contains("--exe)\n                    COMPREPLY=($(compgen -f -- ${cur}))"));
+    assert!(string.contains("--cmd)\n                    COMPREPLY=($(compgen -c -- ${cur}))"));
+    assert!(string.contains("COMPREPLY=($(compgen -W \"fast slow\" -- ${cur}))"));
+    assert!(string.contains("COMPREPLY=( $(compgen -W \"${opts}\" -- ${cur}) $(compgen -f -- ${cur}) )"));
+}
+
+#[test]
+fn zsh_value_hints() {
+    let mut app = build_app_with_value_hints();
+    let mut buf = vec![];
+    app.gen_completions_to("myapp", Shell::Zsh, &mut buf);
+    let string = String::from_utf8(buf).unwrap();
+
+    assert!(string.contains("\"--output+[the output file]: :_files\""));
+    assert!(string.contains("\"--dir+[the working directory]: :_files -/\""));
+    assert!(string.contains("\"--host+[the host to connect to]: :_hosts\""));
+    assert!(string.contains("\"--mode+[possible values win over the hint]: :(fast slow)\""));
+    assert!(string.contains("\"1:INPUT:_files\" \\\n\"*:DEST:_files -/\" \\\n"));
+}
+
+#[test]
+fn fish_value_hints() {
+    let mut app = build_app_with_value_hints();
+    let mut buf = vec![];
+    app.gen_completions_to("myapp", Shell::Fish, &mut buf);
+    let string = String::from_utf8(buf).unwrap();
+
+    assert!(string.contains("-l output -d \"the output file\" -r -a \"(__fish_complete_path)\""));
+    assert!(string.contains("-l dir -d \"the working directory\" -r -f -a \"(__fish_complete_directories)\""));
+    assert!(string.contains("-l host -d \"the host to connect to\" -r -f -a \"(__fish_print_hostnames)\""));
+    assert!(string.contains("-l mode -d \"possible values win over the hint\" -r -f -a \"fast slow\""));
+    assert!(string.contains("-n \"__fish_using_command myapp\" -a \"(__fish_complete_path)\" -d \"the input file\""));
+    assert!(string.contains("-f -a \"(__fish_complete_directories)\" -d \"the destination directories\""));
+}
+
+fn build_dynamic_app() -> App<'static, 'static> {
+    App::new("myapp")
+        .setting(AppSettings::DynamicCompletion)
+        .arg(Arg::with_name("verbose").short("v").long("verbose"))
+        .arg(Arg::with_name("file").help("some input file"))
+        .arg(Arg::with_name("output")
+            .long("output")
+            .takes_value(true)
+            .value_hint(ValueHint::FilePath))
+        .arg(Arg::with_name("dir")
+            .long("dir")
+            .takes_value(true)
+            .value_hint(ValueHint::DirPath))
+        .subcommand(SubCommand::with_name("checkout")
+            .alias("co")
+            .visible_alias("switch")
+            .arg(Arg::with_name("remote")
+                .short("r")
+                .long("remote")
+                .completer(|partial| {
+                    vec![format!("{}-origin", partial), format!("{}-upstream", partial)]
+                }))
+            .arg(Arg::with_name("mode")
+                .long("mode")
+                .takes_value(true)
+                .possible_values(&["fast", "slow"]))
+            .arg(Arg::with_name("branch").completer(|_| vec!["master".into(), "dev".into()])))
+        .subcommand(SubCommand::with_name("secret").setting(AppSettings::Hidden))
+}
+
+fn complete(words: &[&str]) -> Vec<String> {
+    let mut args = vec!["myapp", "__complete", "--"];
+    args.extend_from_slice(words);
+    let err = build_dynamic_app().get_matches_from_safe(args).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::CompletionsDisplayed);
+    assert!(!err.use_stderr());
+    err.message.lines().map(|l| l.to_owned()).collect()
+}
+
+#[test]
+fn dynamic_subcommands() {
+    assert_eq!(complete(&[""]), ["checkout", "switch", "help"]);
+    assert_eq!(complete(&["ch"]), ["checkout"]);
+}
+
+#[test]
+fn dynamic_switches() {
+    assert_eq!(complete(&["--v"]), ["--verbose", "--version"]);
+    assert_eq!(complete(&["checkout", "--m"]), ["--mode"]);
+    assert_eq!(complete(&["co", "-"]),
+               ["--help", "-h", "--version", "-V", "--remote", "-r", "--mode"]);
+}
+
+#[test]
+fn dynamic_option_values() {
+    assert_eq!(complete(&["checkout", "--mode", "f"]), ["fast"]);
+    assert_eq!(complete(&["checkout", "--mode=s"]), ["--mode=slow"]);
+    assert_eq!(complete(&["switch", "-r", "my"]), ["my-origin", "my-upstream"]);
+    assert_eq!(complete(&["checkout", "--remote", "x", "d"]), ["master", "dev"]);
+}
+
+#[test]
+fn dynamic_value_hints() {
+    assert_eq!(complete(&["--output", "sr"]), [":files"]);
+    assert_eq!(complete(&["--output=sr"]), [":files"]);
+    assert_eq!(complete(&["--dir", ""]), [":dirs"]);
+    assert!(complete(&["checkout", "--mode", "x"]).is_empty());
+}
+
+#[test]
+fn dynamic_positional_values() {
+    assert_eq!(complete(&["checkout", ""]), ["master", "dev"]);
+    assert!(complete(&["checkout", "master", ""]).is_empty());
+}
+
+#[test]
+fn dynamic_requires_setting() {
+    let res = App::new("myapp")
+        .arg(Arg::with_name("file"))
+        .get_matches_from_safe(vec!["myapp", "__complete"]);
+    assert!(res.is_ok());
+    assert_eq!(res.unwrap().value_of("file"), Some("__complete"));
+}
+
+#[test]
+fn dynamic_scripts() {
+    for &(shell, call) in &[(Shell::Bash, "myapp __complete -- \"${words[@]}\""),
+                            (Shell::Zsh, "myapp __complete -- \"${(@)words[2,CURRENT]}\""),
+                            (Shell::Fish, "myapp __complete -- $words"),
+                            (Shell::PowerShell, "& 'myapp' __complete -- @words"),
+                            (Shell::Elvish, "myapp __complete -- $@words[1..]")] {
+        let mut buf = vec![];
+        build_dynamic_app().gen_dynamic_completions_to("myapp", shell, &mut buf);
+        let string = String::from_utf8(buf).unwrap();
+        assert!(string.contains(call), "{} script:\n{}", shell, string);
+        assert!(!string.contains("checkout"));
+    }
+}
+
+fn build_app_with_value_help() -> App<'static, 'static> {
+    App::new("myapp")
+        .arg(Arg::with_name("mode")
+            .long("mode")
+            .takes_value(true)
+            .help("the mode")
+            .possible_value_with_help("fast", "skips checks")
+            .possible_value("medium")
+            .possible_value_with_help("slow", "runs the \"full\" check"))
+}
+
+#[test]
+fn zsh_possible_values_with_help() {
+    let mut app = build_app_with_value_help();
+    let mut buf = vec![];
+    app.gen_completions_to("myapp", Shell::Zsh, &mut buf);
+    let string = String::from_utf8(buf).unwrap();
+
+    assert!(string.contains(r#""--mode+[the mode]: :((fast\:\"skips checks\" medium slow\:\"runs the \\\"full\\\" check\"))""#));
+}
+
+#[test]
+fn fish_possible_values_with_help() {
+    let mut app = build_app_with_value_help();
+    let mut buf = vec![];
+    app.gen_completions_to("myapp", Shell::Fish, &mut buf);
+    let string = String::from_utf8(buf).unwrap();
+
+    assert!(string.contains(r#"-l mode -d "the mode" -r -f -a "{fast\t'skips checks',medium\t'',slow\t'runs the \"full\" check'}""#));
+}
@@ -0,0 +1,138 @@
+extern crate clap;
+extern crate regex;
+
+use clap::{App, AppSettings, Arg, SubCommand, Shell};
+use regex::Regex;
+
+include!("../clap-test.rs");
+
+static DEPRECATED: &'static str = "prog 1.0
+
+USAGE:
+    prog [OPTIONS] [SUBCOMMAND]
+
+FLAGS:
+    -h, --help       Prints help information
+    -V, --version    Prints version information
+
+OPTIONS:
+        --out <FILE>       Writes to FILE [deprecated]
+        --output <FILE>    Writes to FILE
+
+SUBCOMMANDS:
+    help      Prints this message or the help of the given subcommand(s)
+    remove    Removes a file
+    rm        Removes a file [deprecated]";
+
+static HIDE_DEPRECATED: &'static str = "prog 1.0
+
+USAGE:
+    prog [OPTIONS] [SUBCOMMAND]
+
+FLAGS:
+    -h, --help       Prints help information
+    -V, --version    Prints version information
+
+OPTIONS:
+        --output <FILE>    Writes to FILE
+
+SUBCOMMANDS:
+    help      Prints this message or the help of the given subcommand(s)
+    remove    Removes a file";
+
+fn app() -> App<'static, 'static> {
+    App::new("prog")
+        .version("1.0")
+        .arg(Arg::with_name("out")
+                 .long("out")
+                 .value_name("FILE")
+                 .help("Writes to FILE")
+                 .deprecated("use --output instead"))
+        .arg(Arg::with_name("output").long("output").value_name("FILE").help("Writes to FILE"))
+        .subcommand(SubCommand::with_name("rm")
+                        .about("Removes a file")
+                        .deprecated("use 'remove' instead")
+                        .arg(Arg::with_name("force").short("f").deprecated("it's the default")))
+        .subcommand(SubCommand::with_name("remove").about("Removes a file"))
+}
+
+#[test]
+fn deprecated_still_parse() {
+    let m = app().get_matches_from(vec!["prog", "--out", "a.txt", "rm", "-f"]);
+    assert_eq!(m.value_of("out"), Some("a.txt"));
+    assert!(m.subcommand_matches("rm").unwrap().is_present("force"));
+
+    let m = app()
+        .global_setting(AppSettings::NoDeprecationWarnings)
+        .get_matches_from(vec!["prog", "rm"]);
+    assert_eq!(m.subcommand_name(), Some("rm"));
+}
+
+#[test]
+fn deprecated_in_help() {
+    assert!(test::compare_output(app(), "prog --help", DEPRECATED, false));
+}
+
+#[test]
+fn hide_deprecated() {
+    let hiding = app().setting(AppSettings::HideDeprecated);
+    assert!(test::compare_output(hiding, "prog --help", HIDE_DEPRECATED, false));
+
+    let err = app()
+        .setting(AppSettings::HideDeprecated)
+        .setting(AppSettings::HelpAll)
+        .get_matches_from_safe(vec!["prog", "--help-all"])
+        .unwrap_err();
+    assert!(err.message.contains("Writes to FILE [hidden]"));
+    assert!(err.message.contains("Removes a file [hidden]"));
+    assert_eq!(err.message.matches("[deprecated]").count(), 2);
+}
+
+#[test]
+fn deprecated_not_completed() {
+    let rm = Regex::new(r"\brm\b").unwrap();
+    let script = |shell| {
+        let mut buf = vec![];
+        app().gen_completions_to("prog", shell, &mut buf);
+        String::from_utf8(buf).unwrap()
+    };
+
+    // The candidates leave them out, but an option typed anyway still completes its value
+    let bash = script(Shell::Bash);
+    for opts in bash.lines().filter(|l| l.trim_left().starts_with("opts=")) {
+        assert!(!opts.contains("--out ") && !rm.is_match(opts), "{}", opts);
+    }
+    assert!(bash.contains("--output") && bash.contains(" remove"));
+    assert!(bash.contains("--out)"));
+
+    let fish = script(Shell::Fish);
+    assert!(fish.contains("-l output ") && fish.contains("-a \"remove\""));
+    assert!(!fish.contains("-l out ") && !fish.contains("-a \"rm\""));
+
+    let zsh = script(Shell::Zsh);
+    assert!(zsh.contains("\"--output+[Writes to FILE]\""));
+    assert!(zsh.contains("\"remove:Removes a file\""));
+    assert!(zsh.contains("\"!--out+[Writes to FILE]\"") && zsh.contains("\"!-f[]\""));
+    assert!(!zsh.contains("\"rm:"));
+}
+
+#[test]
+fn deprecated_not_completed_dynamically() {
+    let complete = |words: &[&str]| {
+        let mut args = vec!["prog", "__complete", "--"];
+        args.extend_from_slice(words);
+        let err = App::new("prog")
+            .setting(AppSettings::DynamicCompletion)
+            .arg(Arg::with_name("out").long("out").takes_value(true).deprecated("use --output"))
+            .arg(Arg::with_name("legacy").possible_values(&["old"]).deprecated("it's ignored"))
+            .arg(Arg::with_name("mode").possible_values(&["fast", "slow"]))
+            .get_matches_from_safe(args)
+            .unwrap_err();
+        err.message.lines().map(|l| l.to_owned()).collect::<Vec<_>>()
+    };
+
+    assert_eq!(complete(&["--o"]), Vec::<String>::new());
+    assert_eq!(complete(&[""]), Vec::<String>::new());
+    // `a.txt` is the value of --out and `x` the deprecated first positional
+    assert_eq!(complete(&["--out", "a.txt", "x", ""]), ["fast", "slow"]);
+}
@@ -0,0 +1,200 @@
+extern crate clap;
+extern crate regex;
+
+use clap::{App, ErrorKind};
+
+include!("../clap-test.rs");
+
+static NAVAL_FATE: &'static str = "Naval Fate.
+
+Usage:
+  naval_fate ship new <name>...
+  naval_fate ship <name> move <x> <y> [--speed=<kn>]
+  naval_fate ship shoot <x> <y>
+  naval_fate mine (set|remove) <x> <y> [--moored|--drifting]
+  naval_fate -h | --help
+  naval_fate --version
+
+Options:
+  -h --help     Show this screen.
+  --version     Show version.
+  --speed=<kn>  Speed in knots [default: 10].
+  --moored      Moored (anchored) mine.
+  --drifting    Drifting mine.
+";
+
+static COPY: &'static str = "Copies files.
+
+Usage:
+  cp [options] <src> <dst>
+  cp [options] --list
+  cp -vq <src>
+
+Options:
+  -v, --verbose    Talks more.
+  -o FILE          Logs to FILE.
+  --jobs=N         Jobs to run
+                   [default: 4]
+  -q --quiet       Talks less.
+  -l, --list       Lists the copies.
+";
+
+static COPY_HELP: &'static str = "cp 
+Copies files.
+
+USAGE:
+    cp [FLAGS] [OPTIONS] <src> <dst>
+
+FLAGS:
+    -h, --help       Prints help information
+    -l, --list       Lists the copies.
+    -q, --quiet      Talks less.
+    -V, --version    Prints version information
+    -v, --verbose    Talks more.
+
+OPTIONS:
+        --jobs <N>    Jobs to run [default: 4]
+    -o <FILE>         Logs to FILE.
+
+ARGS:
+    <src>    
+    <dst>    ";
+
+#[test]
+fn docopt_subcommands() {
+    let m = App::from_docopt(NAVAL_FATE)
+        .get_matches_from(vec!["naval_fate", "ship", "new", "Guardian", "Titanic"]);
+    let new = m.subcommand_matches("ship").unwrap().subcommand_matches("new").unwrap();
+    assert_eq!(new.values_of("name").unwrap().collect::<Vec<_>>(), ["Guardian", "Titanic"]);
+
+    let m = App::from_docopt(NAVAL_FATE)
+        .get_matches_from(vec!["naval_fate", "ship", "Guardian", "move", "10", "50", "--speed", "20"]);
+    let ship = m.subcommand_matches("ship").unwrap();
+    assert_eq!(ship.value_of("name"), Some("Guardian"));
+    let mv = ship.subcommand_matches("move").unwrap();
+    assert_eq!(mv.value_of("x"), Some("10"));
+    assert_eq!(mv.value_of("y"), Some("50"));
+    assert_eq!(mv.value_of("speed"), Some("20"));
+
+    let m = App::from_docopt(NAVAL_FATE)
+        .get_matches_from(vec!["naval_fate", "mine", "remove", "1", "2", "--drifting"]);
+    let remove = m.subcommand_matches("mine").unwrap().subcommand_matches("remove").unwrap();
+    assert!(remove.is_present("drifting"));
+    assert!(remove.is_present("--moored|--drifting"));
+
+    let err = App::from_docopt(NAVAL_FATE)
+        .get_matches_from_safe(vec!["naval_fate", "ship"])
+        .unwrap_err();
+    assert_eq!(err.kind, ErrorKind::MissingArgumentOrSubcommand);
+}
+
+#[test]
+fn docopt_requirements() {
+    let err = App::from_docopt(NAVAL_FATE)
+        .get_matches_from_safe(vec!["naval_fate", "ship", "shoot", "1"])
+        .unwrap_err();
+    assert_eq!(err.kind, ErrorKind::MissingRequiredArgument);
+
+    let err = App::from_docopt(NAVAL_FATE)
+        .get_matches_from_safe(vec!["naval_fate", "mine", "set", "1", "2", "--moored", "--drifting"])
+        .unwrap_err();
+    assert_eq!(err.kind, ErrorKind::ArgumentConflict);
+
+    // Args of different patterns can't be mixed, and one of the patterns must be used
+    let remote = App::from_docopt("Usage:
+  remote --add <name> [--url=<url>]
+  remote --remove <name>");
+    let err = remote.clone()
+        .get_matches_from_safe(vec!["remote", "--remove", "a", "--url", "b"])
+        .unwrap_err();
+    assert_eq!(err.kind, ErrorKind::ArgumentConflict);
+    let err = remote.clone().get_matches_from_safe(vec!["remote", "a"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::MissingRequiredArgument);
+    let m = remote.get_matches_from(vec!["remote", "--add", "a", "--url", "b"]);
+    assert_eq!(m.value_of("name"), Some("a"));
+    assert_eq!(m.value_of("url"), Some("b"));
+
+    let err = App::from_docopt(COPY).get_matches_from_safe(vec!["cp"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::MissingRequiredArgument);
+    let m = App::from_docopt(COPY).get_matches_from(vec!["cp", "-vq", "a"]);
+    assert!(m.is_present("verbose") && m.is_present("quiet"));
+    let m = App::from_docopt(COPY).get_matches_from(vec!["cp", "--list"]);
+    assert!(m.is_present("list"));
+}
+
+#[test]
+fn docopt_options() {
+    let m = App::from_docopt(COPY).get_matches_from(vec!["cp", "-o", "log.txt", "a", "b"]);
+    assert_eq!(m.value_of("o"), Some("log.txt"));
+    assert_eq!(m.value_of("jobs"), Some("4"));
+    assert_eq!(m.value_of("src"), Some("a"));
+    assert_eq!(m.value_of("dst"), Some("b"));
+
+    let m = App::from_docopt(COPY).get_matches_from(vec!["cp", "--list", "--jobs=2"]);
+    assert_eq!(m.value_of("jobs"), Some("2"));
+}
+
+#[test]
+fn docopt_help() {
+    assert!(test::compare_output(App::from_docopt(COPY), "cp --help", COPY_HELP, false));
+}
+
+#[test]
+#[should_panic]
+fn docopt_no_usage() { App::from_docopt("Copies files.\n\nOptions:\n  -v  Talks more.\n"); }
+
+#[test]
+fn docopt_double_dash() {
+    let app = App::from_docopt("Usage:\n  prog [-v] [--] <file>...");
+    let m = app.clone().get_matches_from(vec!["prog", "-v", "--", "-a", "b"]);
+    assert!(m.is_present("v"));
+    assert_eq!(m.values_of("file").unwrap().collect::<Vec<_>>(), ["-a", "b"]);
+    let m = app.get_matches_from(vec!["prog", "a", "-v"]);
+    assert!(!m.is_present("v"));
+    assert_eq!(m.values_of("file").unwrap().collect::<Vec<_>>(), ["a", "-v"]);
+}
+
+#[test]
+fn docopt_stdin_dash() {
+    let app = App::from_docopt("Usage:\n  prog [-q] [-]");
+    let m = app.clone().get_matches_from(vec!["prog", "-q", "-"]);
+    assert!(m.is_present("q"));
+    assert!(m.is_present("-"));
+    assert!(app.p.subcommands.is_empty());
+}
+
+#[test]
+fn docopt_invalid() {
+    let err = App::try_from_docopt("Copies files.").err().unwrap();
+    assert_eq!(err.kind, ErrorKind::InvalidDefinition);
+    let err = App::try_from_docopt("Usage:\n  cp [options <src>").err().unwrap();
+    assert_eq!(err.kind, ErrorKind::InvalidDefinition);
+    assert_eq!(err.info.unwrap(), ["[options <src>", "unclosed '['"]);
+    let err = App::try_from_docopt("Usage:\n  cp (<src>]").err().unwrap();
+    assert_eq!(err.info.unwrap(), ["(<src>]", "unexpected ']'"]);
+    assert!(App::try_from_docopt(COPY).is_ok());
+}
+
+#[test]
+fn docopt_nameless_option() {
+    let err = App::try_from_docopt("Usage:\n  prog -\n\nOptions:\n  -  stdin\n").err().unwrap();
+    assert_eq!(err.kind, ErrorKind::InvalidDefinition);
+    assert_eq!(err.info.unwrap(), ["-  stdin", "'-' has no name"]);
+}
+
+#[test]
+fn docopt_missing_positional() {
+    let app = App::try_from_docopt("Usage:\n  prog <a> [<b>] <c>").unwrap();
+    let m = app.clone().get_matches_from_safe(vec!["prog", "x", "z"]).unwrap();
+    assert_eq!(m.value_of("a"), Some("x"));
+    assert_eq!(m.value_of("b"), None);
+    assert_eq!(m.value_of("c"), Some("z"));
+    let m = app.get_matches_from_safe(vec!["prog", "x", "y", "z"]).unwrap();
+    assert_eq!(m.value_of("b"), Some("y"));
+    assert_eq!(m.value_of("c"), Some("z"));
+
+    let err = App::try_from_docopt("Usage:\n  prog [<a>] <b> <c>").err().unwrap();
+    assert_eq!(err.kind, ErrorKind::InvalidDefinition);
+    assert_eq!(err.info.unwrap(),
+               ["prog", "the optional argument <a> can't come before the required argument <c>"]);
+}
@@ -179,3 +179,24 @@ fn group_multiple_args_error() {
     let err = result.unwrap_err();
     assert_eq!(err.kind, ErrorKind::ArgumentConflict);
 }
+
+#[test]
+fn group_from_usage() {
+    let app = App::new("group")
+        .args_from_usage("--add [NAME] 'Adds a remote' group:action requires:url
+                          --remove [NAME] 'Removes a remote' group:action
+                          --url [URL] 'The remote url'
+                          --mode [MODE] {fast,slow} 'How to sync' default:fast conflicts:remove");
+    let m = app.clone().get_matches_from(vec!["group", "--add", "origin", "--url", "x"]);
+    assert_eq!(m.value_of("action"), Some("origin"));
+    assert_eq!(m.value_of("mode"), Some("fast"));
+
+    let err = app.clone().get_matches_from_safe(vec!["group", "--add", "a", "--remove", "b"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::ArgumentConflict);
+    let err = app.clone().get_matches_from_safe(vec!["group", "--add", "a"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::MissingRequiredArgument);
+    let err = app.clone().get_matches_from_safe(vec!["group", "--mode", "medium"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::InvalidValue);
+    let err = app.get_matches_from_safe(vec!["group", "--remove", "a", "--mode", "slow"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::ArgumentConflict);
+}
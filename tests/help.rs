@@ -4,7 +4,7 @@ extern crate regex;
 
 include!("../clap-test.rs");
 
-use clap::{App, AppSettings, SubCommand, ErrorKind, Arg};
+use clap::{App, AppSettings, SubCommand, ErrorKind, Arg, ArgGroup};
 
 static HELP: &'static str = "clap-test v1.4.8
 Kevin K. <kbknapp@gmail.com>
@@ -925,4 +925,516 @@ fn override_help() {
 
     assert!(m.is_ok());
     assert!(m.unwrap().is_present("help"));
-}
\ No newline at end of file
+}
+static MARKUP: &'static str = "myapp 
+Copies every file.
+
+- skips .git
+- keeps the permissions, the ownership and the
+  timestamps
+
+USAGE:
+    myapp [FLAGS]
+
+FLAGS:
+    -f, --force
+            Overwrites the files which already
+            exist.
+
+            Use with care.
+    -h, --help
+            Prints help information
+
+    -V, --version
+            Prints version information
+
+
+Reads config from ~/.myapp";
+
+#[test]
+fn help_markup() {
+    let app = App::new("myapp")
+        .setting(AppSettings::HelpMarkup)
+        .set_term_width(50)
+        .about("Copies **every** file.\n\n- skips `.git`\n- keeps the permissions,\n  the ownership and the timestamps")
+        .after_help("Reads config from `~/.myapp`")
+        .arg(Arg::with_name("force")
+            .short("f")
+            .long("force")
+            .help("Overwrites the files which **already** exist.\n\nUse with care."));
+    assert!(test::compare_output(app, "myapp -h", MARKUP, false));
+}
+
+#[test]
+fn help_markup_colored() {
+    let mut app = App::new("myapp")
+        .setting(AppSettings::HelpMarkup)
+        .setting(AppSettings::ColoredHelp)
+        .setting(AppSettings::ColorAlways)
+        .arg(Arg::with_name("force").long("force").help("Overwrites **all** of `dir`"));
+    let mut help = Vec::new();
+    app.write_help(&mut help).unwrap();
+    let help = String::from_utf8(help).unwrap();
+    assert!(!help.contains("**") && !help.contains('`'));
+    if cfg!(all(feature = "color", not(target_os = "windows"))) {
+        assert!(help.contains("Overwrites \u{1b}[1mall\u{1b}[0m of \u{1b}[32mdir\u{1b}[0m"));
+    }
+
+    let err = app.get_matches_from_safe(vec!["myapp", "--help"]).err().unwrap();
+    assert_eq!(err.kind, ErrorKind::HelpDisplayed);
+}
+
+#[test]
+fn help_markup_keeps_spec_vals() {
+    let app = App::new("myapp")
+        .setting(AppSettings::HelpMarkup)
+        .arg(Arg::with_name("mode")
+            .long("mode")
+            .takes_value(true)
+            .default_value("**x**")
+            .possible_values(&["**x**", "`y`"])
+            .help("Sets the **mode**"));
+    let mut help = Vec::new();
+    app.write_help(&mut help).unwrap();
+    let help = String::from_utf8(help).unwrap();
+    assert!(help.contains("Sets the mode [default: **x**] [values: **x**, `y`]"));
+}
+
+static HELP_HEADINGS: &'static str = "ctest 0.1
+
+USAGE:
+    ctest [FLAGS] [OPTIONS] [file]
+
+FLAGS:
+    -h, --help       Prints help information
+    -V, --version    Prints version information
+
+OPTIONS:
+    -o, --out <out>    where to write
+
+ARGS:
+    <file>    the input
+
+NETWORK OPTIONS:
+        --host <host>    the host to connect to
+        --port <port>    the port to connect to
+        --tls            use TLS
+
+OUTPUT FORMATS:
+        --json    prints JSON
+        --yaml    prints YAML";
+
+fn headings_app() -> App<'static, 'static> {
+    App::new("ctest")
+        .version("0.1")
+        .arg(Arg::with_name("port")
+            .long("port")
+            .takes_value(true)
+            .help_heading("NETWORK OPTIONS")
+            .help("the port to connect to"))
+        .arg(Arg::with_name("out")
+            .short("o")
+            .long("out")
+            .takes_value(true)
+            .help("where to write"))
+        .arg(Arg::with_name("file").help("the input"))
+        .arg(Arg::with_name("tls")
+            .long("tls")
+            .help_heading("NETWORK OPTIONS")
+            .help("use TLS"))
+        .arg(Arg::with_name("host")
+            .long("host")
+            .takes_value(true)
+            .help_heading("NETWORK OPTIONS")
+            .help("the host to connect to"))
+        .arg(Arg::with_name("json").long("json").help("prints JSON"))
+        .arg(Arg::with_name("yaml").long("yaml").help("prints YAML"))
+        .arg(Arg::with_name("secret")
+            .long("secret")
+            .hidden(true)
+            .help_heading("SECRET OPTIONS"))
+        .group(ArgGroup::with_name("format")
+            .args(&["json", "yaml", "tls"])
+            .help_heading("OUTPUT FORMATS"))
+}
+
+#[test]
+fn help_headings() {
+    assert!(test::compare_output(headings_app(), "ctest --help", HELP_HEADINGS, false));
+}
+
+static HELP_HEADINGS_UNIFIED: &'static str = "ctest 0.1
+
+USAGE:
+    ctest [OPTIONS] [file]
+
+OPTIONS:
+    -h, --help         Prints help information
+    -o, --out <out>    where to write
+    -V, --version      Prints version information
+
+ARGS:
+    <file>    the input
+
+NETWORK OPTIONS:
+        --host <host>    the host to connect to
+        --port <port>    the port to connect to
+        --tls            use TLS
+
+OUTPUT FORMATS:
+        --json    prints JSON
+        --yaml    prints YAML";
+
+#[test]
+fn help_headings_unified() {
+    let app = headings_app().setting(AppSettings::UnifiedHelpMessage);
+    assert!(test::compare_output(app, "ctest --help", HELP_HEADINGS_UNIFIED, false));
+}
+
+static EXAMPLES: &'static str = "tool 
+
+USAGE:
+    tool <config> --env <env>
+
+FLAGS:
+    -h, --help       Prints help information
+    -V, --version    Prints version information
+
+OPTIONS:
+        --env <env>    the environment
+
+ARGS:
+    <config>    the config file
+
+EXAMPLES:
+    tool --env prod app.toml
+        Deploys the app to production, which is
+        only ever done on Fridays
+    tool --env staging app.toml
+        Deploys the app to staging
+
+See the docs.";
+
+fn examples_app() -> App<'static, 'static> {
+    App::new("tool")
+        .set_term_width(50)
+        .after_help("See the docs.")
+        .arg_from_usage("--env <env> 'the environment'")
+        .arg_from_usage("<config> 'the config file'")
+        .example("tool --env prod app.toml",
+                 "Deploys the app to production, which is only ever done on Fridays")
+        .example("tool --env staging app.toml", "Deploys the app to staging")
+}
+
+#[test]
+fn examples_section() {
+    assert!(test::compare_output(examples_app(), "tool --help", EXAMPLES, false));
+}
+
+#[test]
+fn verify_examples() {
+    assert!(examples_app().verify_examples().is_ok());
+
+    let app = App::new("tool")
+        .subcommand(SubCommand::with_name("deploy")
+            .arg_from_usage("--env <env> 'the environment'")
+            .example("tool deploy --env 'prod eu'", "Deploys to Europe")
+            .example("tool deploy --help", "Shows the help")
+            .example("tool deploy --environment prod", "Went stale"));
+    let err = app.verify_examples().err().unwrap();
+    assert_eq!(err.kind, ErrorKind::UnknownArgument);
+    assert!(err.message.ends_with("\n\nExample: tool deploy --environment prod"));
+}
+
+fn search_app() -> App<'static, 'static> {
+    App::new("git")
+        .setting(AppSettings::HelpSearch)
+        .arg(Arg::with_name("verbose").short("v").help("Prints more"))
+        .subcommand(SubCommand::with_name("remote")
+                        .about("Manages the remotes")
+                        .subcommand(SubCommand::with_name("add")
+                                        .about("Adds a remote")
+                                        .arg(Arg::with_name("tags")
+                                                 .long("tags")
+                                                 .help("Imports every tag from the remote"))))
+        .subcommand(SubCommand::with_name("tag")
+                        .about("Creates, lists or deletes a tag")
+                        .arg(Arg::with_name("message")
+                                 .short("m")
+                                 .takes_value(true)
+                                 .help("The tag message")))
+        .subcommand(SubCommand::with_name("secret").setting(AppSettings::Hidden))
+}
+
+static SEARCH_TAG: &'static str = "git remote add --tags    Imports every tag from the remote
+git tag                  Creates, lists or deletes a tag
+git tag -m <message>     The tag message
+";
+
+#[test]
+fn help_search() {
+    let err = search_app()
+        .get_matches_from_safe(vec!["git", "help", "--search", "tag"])
+        .unwrap_err();
+    assert_eq!(err.kind, ErrorKind::HelpDisplayed);
+    assert_eq!(err.message, SEARCH_TAG);
+    let err = search_app()
+        .get_matches_from_safe(vec!["git", "help", "--search=TAG"])
+        .unwrap_err();
+    assert_eq!(err.message, SEARCH_TAG);
+}
+
+#[test]
+fn help_search_fuzzy() {
+    let err = search_app()
+        .get_matches_from_safe(vec!["git", "help", "--search", "remtoe"])
+        .unwrap_err();
+    assert_eq!(err.kind, ErrorKind::HelpDisplayed);
+    assert!(err.message.starts_with("git remote     "));
+}
+
+#[test]
+fn help_search_no_match() {
+    let err = search_app()
+        .get_matches_from_safe(vec!["git", "help", "--search", "secret"])
+        .unwrap_err();
+    assert_eq!(err.kind, ErrorKind::HelpDisplayed);
+    assert_eq!(err.message, "No subcommands or arguments match 'secret'\n");
+}
+
+#[test]
+fn help_search_no_term() {
+    let res = search_app().get_matches_from_safe(vec!["git", "help", "--search"]);
+    assert_eq!(res.unwrap_err().kind, ErrorKind::EmptyValue);
+}
+
+#[test]
+fn help_search_off() {
+    let res = App::new("git")
+        .subcommand(SubCommand::with_name("tag"))
+        .get_matches_from_safe(vec!["git", "help", "--search", "tag"]);
+    assert_eq!(res.unwrap_err().kind, ErrorKind::UnrecognizedSubcommand);
+}
+
+#[test]
+fn themed_help_and_errors() {
+    use clap::{Color, Style, Theme};
+
+    let app = App::new("ctest")
+        .setting(AppSettings::ColoredHelp)
+        .global_setting(AppSettings::ColorAlways)
+        .theme(Theme {
+                   header: Style::new().fg(Color::Blue).bold(),
+                   placeholder: Style::new().fg(Color::Fixed(244)).underline(),
+                   error: Color::Purple.into(),
+                   ..Theme::default()
+               })
+        .arg(Arg::with_name("cfg").long("config").takes_value(true).value_name("FILE"))
+        .subcommand(SubCommand::with_name("run"));
+    let err = app.clone().get_matches_from_safe(vec!["ctest", "--help"]).unwrap_err();
+    assert!(err.message.contains("\u{1b}[1;34m\nUSAGE:\u{1b}[0m"));
+    assert!(err.message.contains("\u{1b}[4;38;5;244m<FILE>\u{1b}[0m"));
+    assert!(err.message.contains("\u{1b}[32m--config\u{1b}[0m"));
+    let err = app.clone().get_matches_from_safe(vec!["ctest", "run", "--bogus"]).unwrap_err();
+    assert!(err.message.starts_with("\u{1b}[35merror:\u{1b}[0m"));
+    let err = app.clone().get_matches_from_safe(vec!["ctest", "help", "bogus"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::UnrecognizedSubcommand);
+    assert!(err.message.contains("\u{1b}[1;34mUSAGE:\u{1b}[0m"));
+
+    let err = App::new("ctest")
+        .setting(AppSettings::ColorAlways)
+        .get_matches_from_safe(vec!["ctest", "--bogus"])
+        .unwrap_err();
+    assert!(err.message.starts_with("\u{1b}[1;31merror:\u{1b}[0m"));
+}
+
+#[test]
+fn colored_help_keeps_columns() {
+    use regex::Regex;
+
+    let app = |colored: bool| {
+        let app = App::new("ctest")
+            .set_term_width(50)
+            .arg(Arg::with_name("mode")
+                     .long("mode")
+                     .takes_value(true)
+                     .possible_values(&["fast", "slow", "balanced"])
+                     .help("Picks how hard to try when packing the archive"))
+            .arg(Arg::with_name("v").short("v").help("Prints more"));
+        if colored {
+            app.setting(AppSettings::ColoredHelp).setting(AppSettings::ColorAlways)
+        } else {
+            app.setting(AppSettings::ColorNever)
+        }
+    };
+    let plain = app(false).get_matches_from_safe(vec!["ctest", "--help"]).unwrap_err().message;
+    let colored = app(true).get_matches_from_safe(vec!["ctest", "--help"]).unwrap_err().message;
+    assert!(colored.contains("\u{1b}[32mbalanced\u{1b}[0m"));
+    let re = Regex::new("\x1b[^m]*m").unwrap();
+    assert_eq!(re.replace_all(&*colored, ""), plain);
+}
+
+static WIDE_OPTION: &'static str = "pack 1.0
+
+USAGE:
+    pack [FLAGS] [OPTIONS]
+
+FLAGS:
+    -h, --help       Prints help information
+    -q, --quiet      Prints nothing
+    -V, --version    Prints version information
+
+OPTIONS:
+        --exclude-from-file <PATTERN_FILE>
+            Skips the files matching a pattern in the file
+
+    -l, --level <N>     Compression level to use
+    -o, --out <FILE>    Writes the archive to FILE";
+
+static NARROW_TERM: &'static str = "pack 1.0
+
+USAGE:
+    pack [FLAGS] [OPTIONS]
+
+FLAGS:
+    -h, --help
+            Prints help
+            information
+    -q, --quiet
+            Prints nothing
+
+    -V, --version
+            Prints version
+            information
+
+OPTIONS:
+        --exclude-from-file <PATTERN_FILE>
+            Skips the files
+            matching a pattern
+            in the file
+    -l, --level <N>
+            Compression level
+            to use
+    -o, --out <FILE>
+            Writes the archive
+            to FILE";
+
+fn pack_app(width: usize) -> App<'static, 'static> {
+    App::new("pack")
+        .version("1.0")
+        .set_term_width(width)
+        .args_from_usage("-o, --out [FILE] 'Writes the archive to FILE'
+                          -l, --level [N] 'Compression level to use'
+                          -q, --quiet 'Prints nothing'")
+        .arg(Arg::with_name("exclude")
+                 .long("exclude-from-file")
+                 .value_name("PATTERN_FILE")
+                 .help("Skips the files matching a pattern in the file"))
+}
+
+#[test]
+fn wide_option_on_its_own_line() {
+    assert!(test::compare_output(pack_app(60), "pack --help", WIDE_OPTION, false));
+}
+
+#[test]
+fn narrow_term_next_line_help() {
+    assert!(test::compare_output(pack_app(30), "pack --help", NARROW_TERM, false));
+}
+
+fn pack_help(width: usize) -> String {
+    let mut buf = vec![];
+    pack_app(width).write_help(&mut buf).unwrap();
+    String::from_utf8(buf).unwrap()
+}
+
+#[test]
+fn layout_boundary_widths() {
+    // At 50 columns "Writes the archive to FILE" exactly fits after "-o, --out <FILE>"
+    assert!(pack_help(49).contains("\n    -o, --out <FILE>\n            Writes the archive to FILE"));
+    assert!(pack_help(50).contains("\n    -o, --out <FILE>    Writes the archive to FILE"));
+    assert!(pack_help(50).contains("\n        --exclude-from-file <PATTERN_FILE>\n"));
+    // From 92 columns the longest name leaves room for all the help on the same line
+    assert!(pack_help(91).contains("\n        --exclude-from-file <PATTERN_FILE>\n"));
+    assert!(pack_help(92).contains("\n        --exclude-from-file <PATTERN_FILE>    Skips"));
+}
+
+static EXPLICIT_USAGES: &'static str = "tool 
+Packs files
+
+USAGE:
+    tool --list
+    tool [OPTIONS] <FILE>...
+
+FLAGS:
+    -h, --help       Prints help information
+        --list       Lists the packed files
+    -V, --version    Prints version information
+
+ARGS:
+    <FILE>...    The files to pack";
+
+static DERIVED_USAGES: &'static str = "vcs 
+
+USAGE:
+    vcs [FLAGS] --list <SUBCOMMAND>
+    vcs [FLAGS] --add <NAME> <SUBCOMMAND>
+    vcs [FLAGS] --remove <NAME> <SUBCOMMAND>
+    vcs [FLAGS] --show <SUBCOMMAND>
+
+FLAGS:
+    -h, --help       Prints help information
+        --list       Lists the remotes
+        --show       Shows the current remote
+    -v               Prints more
+    -V, --version    Prints version information
+
+OPTIONS:
+        --add <NAME>       Adds a remote
+        --remove <NAME>    Removes a remote
+
+SUBCOMMANDS:
+    help    Prints this message or the help of the given subcommand(s)
+    init    Creates a repository";
+
+#[test]
+fn explicit_usages() {
+    let app = App::new("tool")
+        .about("Packs files")
+        .usages(&["tool --list", "tool [OPTIONS] <FILE>..."])
+        .arg(Arg::with_name("list").long("list").help("Lists the packed files"))
+        .arg(Arg::with_name("FILE").multiple(true).help("The files to pack"));
+    assert!(test::compare_output(app, "tool --help", EXPLICIT_USAGES, false));
+}
+
+#[test]
+fn derived_usages() {
+    let app = App::new("vcs")
+        .setting(AppSettings::DeriveUsages)
+        .setting(AppSettings::SubcommandRequired)
+        .args_from_usage("--add [NAME] 'Adds a remote'
+                          --remove [NAME] 'Removes a remote'
+                          --show 'Shows the current remote'
+                          --list 'Lists the remotes'
+                          -v 'Prints more'")
+        .group(ArgGroup::with_name("action")
+                   .args(&["add", "remove", "show"])
+                   .required(true)
+                   .conflicts_with("list"))
+        .subcommand(SubCommand::with_name("init").about("Creates a repository"));
+    assert!(test::compare_output(app.clone(), "vcs --help", DERIVED_USAGES, false));
+
+    // Errors about the args used keep their usual usage line
+    let err = app.get_matches_from_safe(vec!["vcs", "--list", "--add", "x", "init"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::ArgumentConflict);
+    assert!(err.message.contains("USAGE:\n    vcs <--add <NAME>|--remove <NAME>|--show> <SUBCOMMAND>\n"));
+}
+
+#[test]
+fn required_flag_keeps_flags_tag() {
+    // Outside of AppSettings::DeriveUsages a required flag still counts towards `[FLAGS]`
+    let app = App::new("prog").arg(Arg::from_usage("-f, --force 'Forces it'").required(true));
+    let err = app.get_matches_from_safe(vec!["prog"]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::MissingRequiredArgument);
+    assert!(err.message.contains("USAGE:\n    prog [FLAGS] --force\n"), "{}", err.message);
+}
@@ -1,7 +1,7 @@
 extern crate clap;
 extern crate regex;
 
-use clap::{App, Arg};
+use clap::{App, AppSettings, Arg, SubCommand};
 
 include!("../clap-test.rs");
 
@@ -32,3 +32,59 @@ fn hidden_args() {
                     Arg::with_name("DUMMY").required(false).hidden(true)]);
     assert!(test::compare_output(app, "test --help", HIDDEN_ARGS, false));
 }
+
+static HELP_ALL: &'static str = "test 1.4
+
+USAGE:
+    test [FLAGS] [SUBCOMMAND]
+
+FLAGS:
+    -f, --flag        some flag [aliases: fl]  [hidden aliases: hf]  [hidden]
+    -F, --flag2       some other flag
+    -h, --help        Prints help information
+        --help-all    Prints help information, including hidden arguments and subcommands
+    -V, --version     Prints version information
+
+ARGS:
+    <DUMMY>    a dummy [hidden]
+
+SUBCOMMANDS:
+    debug    debugging tools [hidden]
+    help     Prints this message or the help of the given subcommand(s)
+    run      runs things [hidden aliases: r]";
+
+fn help_all_app() -> App<'static, 'static> {
+    App::new("test")
+        .version("1.4")
+        .setting(AppSettings::HelpAll)
+        .args(&[Arg::from_usage("-f, --flag 'some flag'")
+                    .hidden(true)
+                    .visible_alias("fl")
+                    .alias("hf"),
+                Arg::from_usage("-F, --flag2 'some other flag'"),
+                Arg::with_name("DUMMY").help("a dummy").hidden(true)])
+        .subcommand(SubCommand::with_name("run").about("runs things").alias("r"))
+        .subcommand(SubCommand::with_name("debug")
+                        .about("debugging tools")
+                        .setting(AppSettings::Hidden))
+}
+
+#[test]
+fn help_all() {
+    assert!(test::compare_output(help_all_app(), "test --help-all", HELP_ALL, false));
+}
+
+#[test]
+fn help_all_not_in_help() {
+    let err = help_all_app().get_matches_from_safe(vec!["test", "--help"]).unwrap_err();
+    assert!(err.message.contains("--help-all"));
+    assert!(!err.message.contains("--flag "));
+    assert!(!err.message.contains("debug"));
+    assert!(!err.message.contains("[hidden"));
+}
+
+#[test]
+fn help_all_off() {
+    let res = App::new("test").get_matches_from_safe(vec!["test", "--help-all"]);
+    assert_eq!(res.unwrap_err().kind, clap::ErrorKind::UnknownArgument);
+}
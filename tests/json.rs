@@ -0,0 +1,178 @@
+#![cfg(feature = "serde")]
+
+extern crate clap;
+extern crate serde_json;
+
+use clap::{App, AppSettings, Arg, ArgGroup, Color, Style, SubCommand, Theme, ValueHint};
+use serde_json::Value;
+
+fn build_app() -> App<'static, 'static> {
+    App::new("myapp")
+        .version("1.0")
+        .author("Kevin K. <kbknapp@gmail.com>")
+        .about("Tests json")
+        .setting(AppSettings::ArgRequiredElseHelp)
+        .global_setting(AppSettings::ColoredHelp)
+        .theme(Theme { header: Style::new().fg(Color::Blue).bold(), ..Theme::default() })
+        .arg(Arg::with_name("debug")
+            .short("d")
+            .long("debug")
+            .multiple(true)
+            .alias("dbg")
+            .visible_alias("verbose")
+            .help("turns on debugging")
+            .overrides_with("quiet"))
+        .arg(Arg::with_name("quiet").long("quiet").display_order(1))
+        .arg(Arg::with_name("mode")
+            .long("mode")
+            .takes_value(true)
+            .possible_values(&["fast", "slow"])
+            .default_value("fast")
+            .default_value_if("debug", None, "slow")
+            .value_hint(ValueHint::Other)
+            .requires_if("slow", "input")
+            .conflicts_with("quiet"))
+        .arg(Arg::with_name("input")
+            .value_name("FILE")
+            .required_unless("quiet")
+            .required_if("mode", "fast")
+            .group("sources"))
+        .group(ArgGroup::with_name("sources").required(false))
+        .subcommand(SubCommand::with_name("test")
+            .about("tests things")
+            .visible_alias("t")
+            .setting(AppSettings::Hidden))
+}
+
+fn find<'a>(v: &'a Value, name: &str) -> &'a Value {
+    v["args"].as_array().unwrap().iter().find(|a| a["name"] == name).unwrap()
+}
+
+#[test]
+fn app_metadata() {
+    let v: Value = serde_json::from_str(&build_app().to_json()).unwrap();
+
+    assert_eq!(v["schema_version"], 1);
+    assert_eq!(v["name"], "myapp");
+    assert_eq!(v["version"], "1.0");
+    assert_eq!(v["author"], "Kevin K. <kbknapp@gmail.com>");
+    assert_eq!(v["about"], "Tests json");
+    assert!(v["settings"].as_array().unwrap().contains(&Value::from("ArgRequiredElseHelp")));
+    assert!(v["settings"].as_array().unwrap().contains(&Value::from("ColoredHelp")));
+    assert!(v["global_settings"].as_array().unwrap().contains(&Value::from("ColoredHelp")));
+    assert!(!v["global_settings"]
+        .as_array()
+        .unwrap()
+        .contains(&Value::from("ArgRequiredElseHelp")));
+    assert!(v.get("long_about").is_none());
+    assert_eq!(v["theme"]["header"], "blue bold");
+    assert_eq!(v["theme"]["error"], "red bold");
+}
+
+#[test]
+fn arg_definitions() {
+    let v: Value = serde_json::from_str(&build_app().to_json()).unwrap();
+
+    let debug = find(&v, "debug");
+    assert_eq!(debug["kind"], "flag");
+    assert_eq!(debug["short"], "d");
+    assert_eq!(debug["long"], "debug");
+    assert_eq!(debug["help"], "turns on debugging");
+    assert_eq!(debug["aliases"],
+               serde_json::json!([{"name": "dbg", "visible": false},
+                                  {"name": "verbose", "visible": true}]));
+    assert!(debug["settings"].as_array().unwrap().contains(&Value::from("Multiple")));
+    assert_eq!(debug["overrides_with"], serde_json::json!(["quiet"]));
+
+    assert_eq!(find(&v, "quiet")["display_order"], 1);
+
+    let mode = find(&v, "mode");
+    assert_eq!(mode["kind"], "option");
+    assert_eq!(mode["possible_values"], serde_json::json!(["fast", "slow"]));
+    assert_eq!(mode["default_value"], "fast");
+    assert_eq!(mode["default_value_ifs"],
+               serde_json::json!([{"arg": "debug", "value": null, "default": "slow"}]));
+    assert_eq!(mode["value_hint"], "Other");
+    assert_eq!(mode["requires"], serde_json::json!([{"arg": "input", "if_value": "slow"}]));
+    assert_eq!(mode["conflicts_with"], serde_json::json!(["quiet"]));
+
+    let input = find(&v, "input");
+    assert_eq!(input["kind"], "positional");
+    assert_eq!(input["index"], 1);
+    assert_eq!(input["value_names"], serde_json::json!(["FILE"]));
+    assert_eq!(input["required_unless"], serde_json::json!(["quiet"]));
+    assert_eq!(input["required_if"], serde_json::json!([{"arg": "mode", "value": "fast"}]));
+    assert_eq!(input["groups"], serde_json::json!(["sources"]));
+}
+
+#[test]
+fn groups_and_subcommands() {
+    let v: Value = serde_json::from_str(&build_app().to_json()).unwrap();
+
+    assert_eq!(v["groups"],
+               serde_json::json!([{"name": "sources", "args": ["input"], "required": false,
+                                   "multiple": false}]));
+    let test = &v["subcommands"][0];
+    assert_eq!(test["name"], "test");
+    assert_eq!(test["about"], "tests things");
+    assert_eq!(test["aliases"], serde_json::json!([{"name": "t", "visible": true}]));
+    assert!(test["settings"].as_array().unwrap().contains(&Value::from("Hidden")));
+    assert_eq!(test["args"], serde_json::json!([]));
+    assert!(test.get("schema_version").is_none());
+}
+
+#[test]
+fn from_json_str() {
+    let app = App::from_json_str(r#"{
+        "name": "myapp",
+        "version": "1.0",
+        "args": [
+            { "verbose": { "short": "v", "multiple": true } },
+            { "name": "mode", "long": "mode", "takes_value": true,
+              "possible_values": ["fast", "slow"] }
+        ],
+        "subcommands": [
+            { "name": "test", "about": "tests things",
+              "args": [{ "name": "input", "index": 1, "required": true }] }
+        ]
+    }"#)
+        .unwrap();
+
+    let m = app.get_matches_from(vec!["myapp", "-vv", "--mode", "fast", "test", "file"]);
+    assert_eq!(m.occurrences_of("verbose"), 2);
+    assert_eq!(m.value_of("mode"), Some("fast"));
+    assert_eq!(m.subcommand_matches("test").unwrap().value_of("input"), Some("file"));
+}
+
+#[test]
+fn from_json_str_errors() {
+    use clap::ErrorKind;
+
+    let err = App::from_json_str(r#"{
+        "name": "myapp",
+        "subcommands": [
+            { "name": "build" },
+            { "name": "test", "args": [{ "verbose": { "multiple": "yes" } }] }
+        ]
+    }"#)
+        .err()
+        .unwrap();
+    assert_eq!(err.kind, ErrorKind::InvalidDefinition);
+    assert_eq!(err.info,
+               Some(vec!["subcommands[1].args.verbose.multiple".to_owned(),
+                         "expected bool".to_owned()]));
+
+    let err = App::from_json_str(r#"{ "name": "myapp", "settings": ["NoSuchSetting"] }"#)
+        .err()
+        .unwrap();
+    assert_eq!(err.info.unwrap()[0], "settings[0]");
+
+    let err = App::from_json_str(r#"{ "name": "myapp", "colour": "always" }"#).err().unwrap();
+    assert_eq!(err.info.unwrap()[0], "colour");
+
+    let err = App::from_json_str(r#"{ "name": "myapp", }"#).err().unwrap();
+    assert_eq!(err.kind, ErrorKind::InvalidDefinition);
+    let info = err.info.unwrap();
+    assert_eq!(info[0], "");
+    assert!(info[1].contains("line 1"), "{}", info[1]);
+}
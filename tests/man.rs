@@ -0,0 +1,172 @@
+extern crate clap;
+
+use std::env;
+use std::fs::{self, File};
+use std::io::Read;
+
+use clap::{App, AppSettings, Arg, SubCommand};
+
+static FULL_PAGE: &'static str = r#".TH MYAPP 1 "" "myapp 1.0"
+.SH NAME
+myapp \- Tests man\-pages
+.SH SYNOPSIS
+myapp [FLAGS] [OPTIONS] <file> [SUBCOMMAND]
+.SH DESCRIPTION
+Tests man pages.
+.sp
+In great detail.
+.SH OPTIONS
+.TP
+\fB\-h\fR, \fB\-\-help\fR
+Prints help information
+.TP
+\fB\-m\fR, \fB\-\-mode\fR=\fI<mode>\fR
+the mode
+.sp
+[default: fast] [possible values: fast, slow]
+.TP
+\fB\-v\fR
+sets the level
+.sp
+of verbosity
+.TP
+\fB\-V\fR, \fB\-\-version\fR
+Prints version information
+.TP
+\fI<file>\fR
+some input file
+.SH SUBCOMMANDS
+.TP
+\fBhelp\fR
+Prints this message or the help of the given subcommand(s)
+.TP
+\fBtest\fR
+tests things
+.SH AUTHOR
+Kevin K. <kbknapp@gmail.com>
+.SH VERSION
+myapp 1.0
+"#;
+
+static SUBCOMMAND_PAGE: &'static str = r#".TH MYAPP\-TEST 1 "" "myapp test 1.0"
+.SH NAME
+myapp\-test \- tests things
+.SH SYNOPSIS
+myapp test [OPTIONS]
+.SH DESCRIPTION
+tests things
+.SH OPTIONS
+.TP
+\fB\-\-case\fR=\fI<from>\fR \fI<to>\fR
+the case to test
+.TP
+\fB\-h\fR, \fB\-\-help\fR
+Prints help information
+.TP
+\fB\-V\fR, \fB\-\-version\fR
+Prints version information
+.SH VERSION
+myapp test 1.0
+"#;
+
+fn build_app() -> App<'static, 'static> {
+    App::new("myapp")
+        .version("1.0")
+        .author("Kevin K. <kbknapp@gmail.com>")
+        .about("Tests man-pages")
+        .long_about("Tests man pages.\n\nIn great detail.")
+        .setting(AppSettings::GlobalVersion)
+        .arg(Arg::with_name("file").help("some input file").required(true))
+        .arg(Arg::with_name("mode")
+            .short("m")
+            .long("mode")
+            .takes_value(true)
+            .possible_values(&["fast", "slow"])
+            .default_value("fast")
+            .help("the mode"))
+        .arg(Arg::with_name("v")
+            .short("v")
+            .multiple(true)
+            .help("verbosity")
+            .long_help("sets the level\n\nof verbosity"))
+        .arg(Arg::with_name("secret").long("secret").hidden(true))
+        .subcommand(SubCommand::with_name("test")
+            .about("tests things")
+            .arg(Arg::with_name("case")
+                .long("case")
+                .takes_value(true)
+                .value_names(&["from", "to"])
+                .help("the case to test")))
+        .subcommand(SubCommand::with_name("internal").setting(AppSettings::Hidden))
+}
+
+fn read(path: &std::path::Path) -> String {
+    let mut s = String::new();
+    File::open(path).unwrap().read_to_string(&mut s).unwrap();
+    s
+}
+
+#[test]
+fn full_page() {
+    let mut buf = vec![];
+    build_app().gen_manpage_to(&mut buf).unwrap();
+
+    assert_eq!(String::from_utf8(buf).unwrap(), FULL_PAGE);
+}
+
+#[test]
+fn escapes_roff() {
+    let mut buf = vec![];
+    App::new("myapp")
+        .about("C:\\dir")
+        .arg(Arg::with_name("opt").long("opt").help(".starts with a dot"))
+        .gen_manpage_to(&mut buf)
+        .unwrap();
+    let page = String::from_utf8(buf).unwrap();
+
+    assert!(page.contains("myapp \\- C:\\edir\n"));
+    assert!(page.contains("\n\\&.starts with a dot\n"));
+}
+
+#[test]
+fn page_per_subcommand() {
+    let dir = env::temp_dir().join("clap_man_page_per_subcommand");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+
+    build_app().gen_manpages(&dir).unwrap();
+
+    assert!(read(&dir.join("myapp.1")).contains(".TP\n\\fBtest\\fR\ntests things\nSee \\fBmyapp\\-test\\fR(1).\n"));
+    assert_eq!(read(&dir.join("myapp-test.1")), SUBCOMMAND_PAGE);
+    assert!(dir.join("myapp-help.1").exists());
+    assert!(!dir.join("myapp-internal.1").exists());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn examples_section() {
+    let mut buf = vec![];
+    App::new("myapp")
+        .example("myapp --mode fast", "Runs fast")
+        .example("myapp -vv", "Runs loudly")
+        .gen_manpage_to(&mut buf)
+        .unwrap();
+    let page = String::from_utf8(buf).unwrap();
+
+    assert!(page.contains("\n.SH EXAMPLES\n.TP\n\\fBmyapp \\-\\-mode fast\\fR\nRuns fast\n\
+                           .TP\n\\fBmyapp \\-vv\\fR\nRuns loudly\n"));
+}
+
+#[test]
+fn indented_text() {
+    let mut buf = vec![];
+    App::new("myapp")
+        .long_about("Runs things.   \n\nFor example:\n    myapp --mode fast\n      -v\nand so on.\n")
+        .gen_manpage_to(&mut buf)
+        .unwrap();
+    let page = String::from_utf8(buf).unwrap();
+
+    assert!(page.contains("\n.SH DESCRIPTION\nRuns things.\n.sp\nFor example:\n\
+                           .nf\n    myapp \\-\\-mode fast\n      \\-v\n.fi\nand so on.\n.SH"));
+}
@@ -0,0 +1,141 @@
+extern crate clap;
+
+use clap::{App, AppSettings, Arg, SubCommand};
+
+static REFERENCE: &'static str = r#"# myapp
+
+Tests markdown
+
+```text
+myapp [OPTIONS] <file>... [SUBCOMMAND]
+```
+
+| Argument | Value | Default | Possible values | Description |
+|---|---|---|---|---|
+| `-h`, `--help` |  |  |  | Prints help information |
+| `-m`, `--mode`, `--mod` | `<mode>` | `fast` | `fast`, `slow` | the mode \| how fast |
+| `-V`, `--version` |  |  |  | Prints version information |
+| `<file>...` |  |  |  | some input file |
+
+| Subcommand | Description |
+|---|---|
+| `help` | Prints this message or the help of the given subcommand(s) |
+| `test` | tests things |
+
+## myapp help
+
+Prints this message or the help of the given subcommand(s)
+
+```text
+myapp help
+```
+
+| Argument | Value | Default | Possible values | Description |
+|---|---|---|---|---|
+| `-h`, `--help` |  |  |  | Prints help information |
+| `-V`, `--version` |  |  |  | Prints version information |
+
+## myapp test
+
+tests things
+
+```text
+myapp test [OPTIONS]
+```
+
+**Aliases:** `t`
+
+| Argument | Value | Default | Possible values | Description |
+|---|---|---|---|---|
+| `--case` | `<from> <to>` |  |  | the case to test |
+| `-h`, `--help` |  |  |  | Prints help information |
+| `-V`, `--version` |  |  |  | Prints version information |
+"#;
+
+#[test]
+fn command_tree_reference() {
+    let mut app = App::new("myapp")
+        .version("1.0")
+        .about("Tests markdown")
+        .arg(Arg::with_name("file").help("some input file").required(true).multiple(true))
+        .arg(Arg::with_name("mode")
+            .short("m")
+            .long("mode")
+            .visible_alias("mod")
+            .takes_value(true)
+            .possible_values(&["fast", "slow"])
+            .default_value("fast")
+            .help("the mode | how fast"))
+        .arg(Arg::with_name("secret").long("secret").hidden(true))
+        .subcommand(SubCommand::with_name("test")
+            .about("tests things")
+            .visible_alias("t")
+            .alias("tst")
+            .arg(Arg::with_name("case")
+                .long("case")
+                .takes_value(true)
+                .value_names(&["from", "to"])
+                .help("the case to test")))
+        .subcommand(SubCommand::with_name("internal").setting(AppSettings::Hidden));
+    let mut buf = vec![];
+    app.gen_markdown_to(&mut buf).unwrap();
+
+    assert_eq!(String::from_utf8(buf).unwrap(), REFERENCE);
+}
+
+#[test]
+fn nested_sections() {
+    let mut app = App::new("myapp")
+        .subcommand(SubCommand::with_name("remote")
+            .long_about("Manages remotes\n\nin detail")
+            .subcommand(SubCommand::with_name("add")))
+        .subcommand(SubCommand::with_name("tag")
+            .display_order(1)
+            .alias("t")
+            .subcommand(SubCommand::with_name("list")));
+    let mut buf = vec![];
+    app.gen_markdown_to(&mut buf).unwrap();
+    let reference = String::from_utf8(buf).unwrap();
+
+    assert!(reference.contains("\n## myapp remote\n\nManages remotes\n\nin detail\n"));
+    assert!(reference.contains("\n### myapp remote add\n\n```text\nmyapp remote add\n```\n"));
+    let headings: Vec<_> = reference.lines().filter(|l| l.starts_with('#')).collect();
+    assert_eq!(headings,
+               ["# myapp",
+                "## myapp tag",
+                "### myapp tag help",
+                "### myapp tag list",
+                "## myapp help",
+                "## myapp remote",
+                "### myapp remote add",
+                "### myapp remote help"]);
+}
+
+#[test]
+fn deep_sections() {
+    let mut sc = SubCommand::with_name("g");
+    for name in &["f", "e", "d", "c", "b"] {
+        sc = SubCommand::with_name(name).subcommand(sc);
+    }
+    let mut app = App::new("a").subcommand(sc);
+    let mut buf = vec![];
+    app.gen_markdown_to(&mut buf).unwrap();
+
+    // Markdown has no headings deeper than six levels
+    assert!(String::from_utf8(buf).unwrap().contains("\n###### a b c d e f g\n"));
+}
+
+#[test]
+fn examples_block() {
+    let mut app = App::new("myapp")
+        .subcommand(SubCommand::with_name("deploy")
+            .example("myapp deploy --env prod", "Deploys to production")
+            .example("myapp deploy", "Deploys to staging\nwhich is the default"));
+    let mut buf = vec![];
+    app.gen_markdown_to(&mut buf).unwrap();
+    let reference = String::from_utf8(buf).unwrap();
+
+    assert!(reference.contains("\n**Examples:**\n\n```text\n\
+                                # Deploys to production\nmyapp deploy --env prod\n\n\
+                                # Deploys to staging\n# which is the default\nmyapp deploy\n```\n"));
+}
@@ -162,3 +162,52 @@ fn possible_values_of_option_multiple_fail() {
 fn possible_values_output() {
     assert!(test::compare_output(test::complex_app(), "clap-test -O slo", PV_ERROR, true));
 }
+
+static PV_LONG_HELP: &'static str = "pv 
+
+USAGE:
+    pv [OPTIONS]
+
+FLAGS:
+    -h, --help       
+            Prints help information
+
+    -V, --version    
+            Prints version information
+
+
+OPTIONS:
+        --mode <mode>    
+            how to run
+
+            Possible values:
+                fast        skips checks
+                medium
+                paranoid    runs every check, twice";
+
+fn pv_help_app() -> App<'static, 'static> {
+    App::new("pv")
+        .arg(Arg::with_name("mode")
+            .long("mode")
+            .takes_value(true)
+            .help("how to run")
+            .possible_value_with_help("fast", "skips checks")
+            .possible_value("medium")
+            .possible_value_with_help("paranoid", "runs every check, twice"))
+}
+
+#[test]
+fn possible_values_with_help_long() {
+    assert!(test::compare_output(pv_help_app(), "pv --help", PV_LONG_HELP, false));
+}
+
+#[test]
+fn possible_values_with_help_short() {
+    let res = pv_help_app().get_matches_from_safe(vec!["pv", "-h"]);
+    let help = res.unwrap_err().message;
+    assert!(help.contains("how to run [values: fast, medium, paranoid]"));
+    assert!(!help.contains("skips checks"));
+
+    let m = pv_help_app().get_matches_from(vec!["pv", "--mode", "medium"]);
+    assert_eq!(m.value_of("mode"), Some("medium"));
+}
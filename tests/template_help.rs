@@ -1,7 +1,7 @@
 extern crate clap;
 extern crate regex;
 
-use clap::{App, SubCommand};
+use clap::{App, AppSettings, Arg, SubCommand};
 
 include!("../clap-test.rs");
 
@@ -49,6 +49,20 @@ SUBCOMMANDS:
     help    Prints this message or the help of the given subcommand(s)
     test    does testing things";
 
+static CONDITIONAL_TEMPL_HELP: &'static str = "MyApp
+SUBCOMMANDS:
+    help    Prints this message or the help of the given subcommand(s)
+    test    does testing things
+
+That's all";
+
+static NEW_TAGS_TEMPL_HELP: &'static str = "Does awesome things, at length
+NETWORK:
+        --net <net>    Network interface
+EXAMPLES:
+    MyApp --net eth0
+        Uses eth0";
+
 #[test]
 fn with_template() {
     let app = app_example1().template(EXAMPLE1_TMPL_S);
@@ -101,6 +115,73 @@ fn template_author_version() {
     assert!(test::compare_output(app, "MyApp --help", "Kevin K. <kbknapp@gmail.com>\n1.0\nDoes awesome things\nMyApp", false));
 }
 
+#[test]
+fn template_conditionals() {
+    let tmpl = "{bin}{?subcommands}\nSUBCOMMANDS:\n{subcommands}{/subcommands}\
+                {?after-help}\n\n{after-help}{/after-help}";
+    let app = App::new("MyApp")
+                    .template(tmpl)
+                    .setting(AppSettings::DisableHelpSubcommand);
+    assert!(test::compare_output(app, "MyApp --help", "MyApp", false));
+    let app = App::new("MyApp")
+                    .template(tmpl)
+                    .after_help("That's all")
+                    .subcommand(SubCommand::with_name("test").about("does testing things"));
+    assert!(test::compare_output(app, "MyApp --help", CONDITIONAL_TEMPL_HELP, false));
+}
+
+#[test]
+fn template_all_args_hidden() {
+    let tmpl = "{bin}{?all-args}\n{all-args}{/all-args}";
+    let app = App::new("MyApp")
+                    .template(tmpl)
+                    .arg(Arg::with_name("secret").long("secret").hidden(true))
+                    .subcommand(SubCommand::with_name("internal").setting(AppSettings::Hidden));
+    let mut buf = vec![];
+    app.write_help(&mut buf).unwrap();
+    assert_eq!(String::from_utf8(buf).unwrap(), "MyApp");
+}
+
+#[test]
+fn template_nested_conditionals() {
+    let app = App::new("MyApp")
+                    .version("1.0")
+                    .template("{?version}v{version}{?author} by {author}{/author}.{/version} {?x}{/y}");
+    assert!(test::compare_output(app, "MyApp --help", "v1.0. ", false));
+}
+
+#[test]
+fn template_new_tags() {
+    let app = App::new("MyApp")
+                    .about("Does awesome things")
+                    .long_about("Does awesome things, at length")
+                    .example("MyApp --net eth0", "Uses eth0")
+                    .arg(Arg::with_name("net")
+                             .long("net")
+                             .takes_value(true)
+                             .help("Network interface")
+                             .help_heading("NETWORK"))
+                    .template("{long-about}{?options}\nOPTIONS:\n{options}{/options}\
+                               \nNETWORK:\n{heading:NETWORK}\nEXAMPLES:\n{examples}");
+    assert!(test::compare_output(app, "MyApp --help", NEW_TAGS_TEMPL_HELP, false));
+}
+
+#[test]
+fn template_global() {
+    let app = App::new("MyApp")
+                    .template("{bin}: {about}")
+                    .setting(AppSettings::GlobalTemplate)
+                    .subcommand(SubCommand::with_name("test").about("does testing things"))
+                    .subcommand(SubCommand::with_name("other")
+                                    .about("does other things")
+                                    .template("{about}"));
+    assert!(test::compare_output(app.clone(),
+                                 "MyApp test --help",
+                                 "MyApp-test: does testing things",
+                                 false));
+    assert!(test::compare_output(app, "MyApp other --help", "does other things", false));
+}
+
 // ----------
 
 fn app_example1<'b, 'c>() -> App<'b, 'c> {
@@ -0,0 +1,56 @@
+#![cfg(feature = "toml")]
+
+extern crate clap;
+
+use clap::{App, ErrorKind};
+
+#[test]
+fn from_toml_str() {
+    let app = App::from_toml_str(r#"
+        name = "myapp"
+        settings = ["SubcommandRequired"]
+
+        [[args]]
+        name = "config"
+        short = "c"
+        takes_value = true
+        default_value_if = [["debug", "debug.toml"]]
+
+        [[args]]
+        name = "debug"
+        long = "debug"
+
+        [[subcommands]]
+        name = "run"
+        aliases = ["r"]
+    "#)
+        .unwrap();
+
+    let m = app.get_matches_from(vec!["myapp", "--debug", "r"]);
+    assert_eq!(m.value_of("config"), Some("debug.toml"));
+    assert_eq!(m.subcommand_name(), Some("run"));
+}
+
+#[test]
+fn from_toml_str_errors() {
+    let err = App::from_toml_str(r#"
+        name = "myapp"
+
+        [[subcommands]]
+        name = "run"
+
+        [[subcommands.args]]
+        name = "jobs"
+        number_of_values = "two"
+    "#)
+        .err()
+        .unwrap();
+    assert_eq!(err.kind, ErrorKind::InvalidDefinition);
+    assert_eq!(err.info,
+               Some(vec!["subcommands[0].args.jobs.number_of_values".to_owned(),
+                         "expected non-negative integer".to_owned()]));
+
+    let err = App::from_toml_str("name = ").err().unwrap();
+    assert_eq!(err.kind, ErrorKind::InvalidDefinition);
+    assert_eq!(err.info.unwrap()[0], "");
+}
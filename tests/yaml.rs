@@ -38,3 +38,194 @@ fn author() {
     assert!(help_string.contains(
         "Kevin K. <kbknapp@gmail.com>"));
 }
+
+#[test]
+fn try_from_yaml_reports_key_path() {
+    use clap::{ErrorKind, YamlLoader};
+
+    let docs = YamlLoader::load_from_str("
+name: myapp
+subcommands:
+    - test:
+        args:
+            - verbose:
+                short: v
+                multiple: yes please
+").unwrap();
+    let err = App::try_from_yaml(&docs[0]).err().unwrap();
+    assert_eq!(err.kind, ErrorKind::InvalidDefinition);
+    assert_eq!(err.info,
+               Some(vec!["subcommands[0].args.verbose.multiple".to_owned(),
+                         "expected bool".to_owned()]));
+}
+
+#[test]
+fn try_from_yaml_unknown_key() {
+    use clap::{ErrorKind, YamlLoader};
+
+    let docs = YamlLoader::load_from_str("
+name: myapp
+groups:
+    - mode:
+        args: [fast, slow]
+        requried: true
+").unwrap();
+    let err = App::try_from_yaml(&docs[0]).err().unwrap();
+    assert_eq!(err.kind, ErrorKind::InvalidDefinition);
+    assert_eq!(err.info.unwrap()[0], "groups.mode.requried");
+}
+
+#[test]
+fn from_yaml_ignores_unknown_app_keys() {
+    use clap::ErrorKind;
+
+    // app.yml predates the `groups` key and still spells it `arg_groups`
+    let yml = load_yaml!("app.yml");
+    App::from_yaml(yml);
+
+    let err = App::try_from_yaml(yml).err().unwrap();
+    assert_eq!(err.kind, ErrorKind::InvalidDefinition);
+    assert_eq!(err.info.unwrap()[0], "arg_groups");
+}
+
+#[test]
+fn try_from_yaml_schema() {
+    use clap::ErrorKind;
+
+    let yml = load_yaml!("app_schema.yml");
+    let app = App::try_from_yaml(yml).unwrap();
+    let err = app.clone()
+        .get_matches_from_safe(vec!["prog", "--minvals2", "a", "b", "--option3", "c"])
+        .err().unwrap();
+    assert_eq!(err.kind, ErrorKind::ArgumentConflict);
+
+    let err = app.get_matches_from_safe(vec!["prog", "--help"]).err().unwrap();
+    assert!(err.message.contains("Possible values:\n"));
+    assert!(err.message.contains(" vi       the visual editor\n"));
+    assert!(err.message.contains(" emacs    the extensible editor\n"));
+    assert!(err.message.contains("EXAMPLES:\n    claptests-schema --option3 val\n        uses an option"));
+}
+
+#[test]
+fn to_yaml_round_trips_app_schema_yml() {
+    use clap::YamlLoader;
+
+    let yml = load_yaml!("app_schema.yml");
+    let yaml = App::try_from_yaml(yml).unwrap().to_yaml();
+    let docs = YamlLoader::load_from_str(&yaml).unwrap();
+    assert_eq!(App::try_from_yaml(&docs[0]).unwrap().to_yaml(), yaml);
+}
+
+#[test]
+fn builder_parity_keys() {
+    use clap::{ErrorKind, YamlLoader};
+
+    let docs = YamlLoader::load_from_str("
+name: myapp
+long_about: a longer description
+set_term_width: 80
+unset_settings: [ColorAuto]
+args:
+    - mode:
+        long: mode
+        takes_value: true
+        require_equals: true
+        hide_default_value: true
+        default_value: fast
+        visible_aliases: [style]
+    - debug:
+        long: debug
+        conflicts_with_all: [quiet, mode]
+    - quiet:
+        long: quiet
+    - cmd:
+        multiple: true
+        last: true
+").unwrap();
+    let app = App::try_from_yaml(&docs[0]).unwrap();
+
+    let m = app.clone().get_matches_from(vec!["myapp", "--style=slow", "--", "a", "b"]);
+    assert_eq!(m.value_of("mode"), Some("slow"));
+    assert_eq!(m.values_of("cmd").unwrap().collect::<Vec<_>>(), ["a", "b"]);
+
+    let err = app.clone().get_matches_from_safe(vec!["myapp", "--mode", "slow"]).err().unwrap();
+    assert_eq!(err.kind, ErrorKind::EmptyValue);
+
+    let err = app.get_matches_from_safe(vec!["myapp", "--debug", "--quiet"]).err().unwrap();
+    assert_eq!(err.kind, ErrorKind::ArgumentConflict);
+}
+
+#[test]
+fn to_yaml_round_trips_app_yml() {
+    use clap::YamlLoader;
+
+    let yml = load_yaml!("app.yml");
+    let yaml = App::from_yaml(yml).to_yaml();
+    let docs = YamlLoader::load_from_str(&yaml).unwrap();
+    assert_eq!(App::try_from_yaml(&docs[0]).unwrap().to_yaml(), yaml);
+}
+
+#[test]
+fn theme() {
+    use clap::{Color, Style};
+
+    let yml = load_yaml!("app_schema.yml");
+    let yaml = App::try_from_yaml(yml).unwrap().to_yaml();
+    assert!(yaml.contains("theme:\n  header: \"blue bold underline\"\n  placeholder: \"244\"\n"));
+
+    let err = App::try_from_yaml(&clap::YamlLoader::load_from_str("name: a\ntheme:\n  header: \
+                                                                   pink")
+                                     .unwrap()[0])
+        .err()
+        .unwrap();
+    assert_eq!(err.info, Some(vec!["theme.header".to_owned(), "unknown style 'pink'".to_owned()]));
+    assert_eq!("cyan underline".parse::<Style>(), Ok(Style::new().fg(Color::Cyan).underline()));
+}
+
+#[test]
+fn to_yaml_round_trips_builder() {
+    use clap::{AppSettings, Arg, ArgGroup, SubCommand, ValueHint, YamlLoader};
+
+    let app = App::new("myapp")
+        .version("1.0")
+        .about("Does \"quoted\" things\nover two lines")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .unset_setting(AppSettings::ColorAuto)
+        .global_setting(AppSettings::ColoredHelp)
+        .arg(Arg::with_name("verbose").short("v").multiple(true).help("more output"))
+        .arg(Arg::with_name("config")
+            .long("config")
+            .visible_alias("cfg")
+            .value_name("FILE")
+            .value_hint(ValueHint::FilePath)
+            .default_value_if("verbose", None, "verbose.toml")
+            .required_unless("verbose"))
+        .arg(Arg::with_name("mode")
+            .long("mode")
+            .possible_values(&["fast", "slow"])
+            .value_delimiter(";")
+            .required_if("verbose", "x")
+            .group("style"))
+        .arg(Arg::with_name("yes")
+            .long("yes")
+            .conflicts_with("mode")
+            .group("style")
+            .deprecated("it's the default"))
+        .group(ArgGroup::with_name("style").required(false))
+        .subcommand(SubCommand::with_name("test")
+            .visible_alias("t")
+            .arg(Arg::with_name("input").multiple(true).last(true)))
+        .subcommand(SubCommand::with_name("true"));
+
+    let yaml = app.to_yaml();
+    let docs = YamlLoader::load_from_str(&yaml).unwrap();
+    let loaded = App::try_from_yaml(&docs[0]).unwrap();
+    assert_eq!(loaded.to_yaml(), yaml);
+    assert!(yaml.contains("\n  - \"true\": {}\n"));
+    assert!(yaml.contains("\n      deprecated: \"it's the default\"\n"));
+
+    let m = loaded.get_matches_from(vec!["myapp", "-v", "--mode", "fast;slow", "t", "--", "a"]);
+    assert_eq!(m.value_of("config"), Some("verbose.toml"));
+    assert_eq!(m.values_of("mode").unwrap().collect::<Vec<_>>(), ["fast", "slow"]);
+    assert_eq!(m.subcommand_matches("test").unwrap().value_of("input"), Some("a"));
+}